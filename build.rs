@@ -0,0 +1,47 @@
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    // Commit de git: se puede forzar con GIT_COMMIT cuando se compila sin el directorio .git
+    let commit = std::env::var("GIT_COMMIT").ok().or_else(|| git(&["rev-parse", "--short", "HEAD"]));
+
+    let build_timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    println!("cargo:rustc-env=GIT_COMMIT={}", commit.unwrap_or_else(|| "unknown".to_string()));
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={}", build_timestamp);
+    println!("cargo:rerun-if-env-changed=GIT_COMMIT");
+    watch_git_head();
+    // Las migraciones se embeben con embed_migrations!, que no detecta directorios nuevos
+    println!("cargo:rerun-if-changed=migrations");
+}
+
+/// Recompila cuando cambia el commit de HEAD. `.git/HEAD` solo cambia al cambiar
+/// de rama; un commit nuevo en la misma rama modifica el fichero de la referencia
+/// o, si está empaquetada, `packed-refs`.
+fn watch_git_head() {
+    let git_dir = git(&["rev-parse", "--git-dir"]).unwrap_or_else(|| ".git".to_string());
+    let git_dir = Path::new(&git_dir);
+
+    // Cargo recompila siempre si un fichero vigilado no existe, así que solo se
+    // vigilan los que hay
+    let mut watched = vec![git_dir.join("HEAD"), git_dir.join("packed-refs")];
+    if let Some(reference) = git(&["symbolic-ref", "-q", "HEAD"]) {
+        watched.push(git_dir.join(reference));
+    }
+    for path in watched.iter().filter(|path| path.exists()) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
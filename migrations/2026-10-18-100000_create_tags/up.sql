@@ -0,0 +1,18 @@
+-- Your SQL goes here
+
+CREATE TABLE tags (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR NOT NULL,
+  slug VARCHAR NOT NULL UNIQUE,
+  created_at TIMESTAMP,
+  updated_at TIMESTAMP
+);
+
+CREATE TABLE post_tags (
+  id SERIAL PRIMARY KEY,
+  post_id INTEGER NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+  UNIQUE(post_id, tag_id)
+);
+
+CREATE INDEX post_tags_tag_id_idx ON post_tags(tag_id);
@@ -0,0 +1,6 @@
+-- Your SQL goes here
+
+ALTER TABLE categories ADD COLUMN parent_id INTEGER REFERENCES categories(id) ON DELETE SET NULL;
+ALTER TABLE categories ADD CONSTRAINT categories_parent_not_self CHECK (parent_id <> id);
+
+CREATE INDEX categories_parent_id_idx ON categories(parent_id);
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+
+ALTER TABLE categories ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
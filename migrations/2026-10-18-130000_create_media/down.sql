@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE post_media;
+DROP TABLE media_variants;
+DROP TABLE media;
@@ -0,0 +1,40 @@
+-- Your SQL goes here
+
+CREATE TABLE media (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  storage_key VARCHAR NOT NULL UNIQUE,
+  original_filename VARCHAR NOT NULL,
+  mime_type VARCHAR NOT NULL,
+  size_bytes BIGINT NOT NULL,
+  width INTEGER,
+  height INTEGER,
+  alt_text TEXT,
+  created_at TIMESTAMP,
+  updated_at TIMESTAMP
+);
+
+CREATE INDEX media_user_id_idx ON media(user_id);
+
+CREATE TABLE media_variants (
+  id SERIAL PRIMARY KEY,
+  media_id INTEGER NOT NULL REFERENCES media(id) ON DELETE CASCADE,
+  kind VARCHAR NOT NULL,
+  mime_type VARCHAR NOT NULL,
+  width INTEGER NOT NULL,
+  height INTEGER NOT NULL,
+  size_bytes BIGINT NOT NULL,
+  storage_key VARCHAR NOT NULL UNIQUE
+);
+
+CREATE INDEX media_variants_media_id_idx ON media_variants(media_id);
+
+CREATE TABLE post_media (
+  id SERIAL PRIMARY KEY,
+  post_id INTEGER NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+  media_id INTEGER NOT NULL REFERENCES media(id) ON DELETE CASCADE,
+  position INTEGER NOT NULL DEFAULT 0,
+  UNIQUE(post_id, media_id)
+);
+
+CREATE INDEX post_media_media_id_idx ON post_media(media_id);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE posts DROP COLUMN featured_image_url;
+ALTER TABLE posts DROP COLUMN featured_media_id;
+ALTER TABLE posts DROP COLUMN excerpt;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+
+ALTER TABLE posts ADD COLUMN excerpt TEXT;
+ALTER TABLE posts ADD COLUMN featured_media_id INTEGER REFERENCES media(id) ON DELETE SET NULL;
+ALTER TABLE posts ADD COLUMN featured_image_url VARCHAR;
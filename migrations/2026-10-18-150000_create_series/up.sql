@@ -0,0 +1,20 @@
+-- Your SQL goes here
+
+CREATE TABLE series (
+  id SERIAL PRIMARY KEY,
+  title VARCHAR NOT NULL,
+  slug VARCHAR NOT NULL UNIQUE,
+  description TEXT,
+  created_at TIMESTAMP,
+  updated_at TIMESTAMP
+);
+
+-- Un post pertenece como mucho a una serie, para que la navegación sea única
+CREATE TABLE series_posts (
+  id SERIAL PRIMARY KEY,
+  series_id INTEGER NOT NULL REFERENCES series(id) ON DELETE CASCADE,
+  post_id INTEGER NOT NULL UNIQUE REFERENCES posts(id) ON DELETE CASCADE,
+  position INTEGER NOT NULL
+);
+
+CREATE INDEX series_posts_series_id_idx ON series_posts(series_id, position);
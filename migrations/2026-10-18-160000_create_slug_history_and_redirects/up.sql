@@ -0,0 +1,23 @@
+-- Your SQL goes here
+
+-- Slugs anteriores de posts y categorías, para redirigir los enlaces antiguos
+CREATE TABLE slug_history (
+  id SERIAL PRIMARY KEY,
+  entity_type VARCHAR(20) NOT NULL,
+  entity_id INTEGER NOT NULL,
+  slug VARCHAR NOT NULL,
+  created_at TIMESTAMP,
+  UNIQUE(entity_type, slug)
+);
+
+CREATE INDEX slug_history_entity_idx ON slug_history(entity_type, entity_id);
+
+-- Redirecciones de rutas arbitrarias gestionadas por los administradores
+CREATE TABLE redirects (
+  id SERIAL PRIMARY KEY,
+  source_path VARCHAR NOT NULL UNIQUE,
+  target_path VARCHAR NOT NULL,
+  status_code INTEGER NOT NULL DEFAULT 301 CHECK (status_code IN (301, 302, 307, 308)),
+  created_at TIMESTAMP,
+  updated_at TIMESTAMP
+);
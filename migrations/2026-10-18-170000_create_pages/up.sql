@@ -0,0 +1,24 @@
+-- Your SQL goes here
+
+-- Páginas estáticas ("Acerca de", "Contacto", textos legales), separadas de los posts
+-- para que no aparezcan en los listados. `path` es la ruta completa desde la raíz
+-- (`legal/privacidad`) y se mantiene al cambiar el slug o el padre.
+CREATE TABLE pages (
+  id SERIAL PRIMARY KEY,
+  title VARCHAR NOT NULL,
+  slug VARCHAR NOT NULL,
+  path VARCHAR NOT NULL,
+  body TEXT NOT NULL,
+  template VARCHAR(50) NOT NULL DEFAULT 'default',
+  parent_id INTEGER REFERENCES pages(id) ON DELETE RESTRICT,
+  menu_order INTEGER NOT NULL DEFAULT 0,
+  created_at TIMESTAMP,
+  updated_at TIMESTAMP,
+  CONSTRAINT pages_parent_not_self CHECK (parent_id <> id)
+);
+
+-- Dos páginas hermanas no pueden compartir slug; COALESCE hace que también choquen las raíces.
+-- Con esto las rutas ya son únicas, así que `path` solo necesita un índice para las búsquedas.
+CREATE UNIQUE INDEX pages_parent_slug_key ON pages (COALESCE(parent_id, 0), slug);
+CREATE INDEX pages_path_idx ON pages(path);
+CREATE INDEX pages_parent_id_idx ON pages(parent_id);
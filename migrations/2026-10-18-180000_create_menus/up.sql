@@ -0,0 +1,38 @@
+-- Your SQL goes here
+
+-- Menús de navegación gestionados por los administradores; `location` es el
+-- identificador con el que los pide el frontend (header, footer...)
+CREATE TABLE menus (
+  id SERIAL PRIMARY KEY,
+  name VARCHAR NOT NULL,
+  location VARCHAR(50) NOT NULL UNIQUE,
+  created_at TIMESTAMP,
+  updated_at TIMESTAMP
+);
+
+-- Elementos de un menú, anidables. Cada uno apunta a una página, un post, una
+-- categoría o una URL; se guarda el ID y no el slug para que los enlaces
+-- sobrevivan a los cambios de slug. Si el destino se elimina, el elemento también.
+CREATE TABLE menu_items (
+  id SERIAL PRIMARY KEY,
+  menu_id INTEGER NOT NULL REFERENCES menus(id) ON DELETE CASCADE,
+  parent_id INTEGER REFERENCES menu_items(id) ON DELETE CASCADE,
+  position INTEGER NOT NULL,
+  label VARCHAR,
+  item_type VARCHAR(20) NOT NULL CHECK (item_type IN ('page', 'post', 'category', 'url')),
+  page_id INTEGER REFERENCES pages(id) ON DELETE CASCADE,
+  post_id INTEGER REFERENCES posts(id) ON DELETE CASCADE,
+  category_id INTEGER REFERENCES categories(id) ON DELETE CASCADE,
+  url VARCHAR,
+  CONSTRAINT menu_items_target_matches_type CHECK (
+    (item_type = 'page') = (page_id IS NOT NULL)
+    AND (item_type = 'post') = (post_id IS NOT NULL)
+    AND (item_type = 'category') = (category_id IS NOT NULL)
+    AND (item_type = 'url') = (url IS NOT NULL)
+  )
+);
+
+CREATE INDEX menu_items_menu_id_idx ON menu_items(menu_id, position);
+CREATE INDEX menu_items_page_id_idx ON menu_items(page_id);
+CREATE INDEX menu_items_post_id_idx ON menu_items(post_id);
+CREATE INDEX menu_items_category_id_idx ON menu_items(category_id);
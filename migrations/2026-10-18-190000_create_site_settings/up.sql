@@ -0,0 +1,9 @@
+-- Your SQL goes here
+
+-- Ajustes del sitio (título, idioma, política de comentarios...). Solo se guardan
+-- los valores modificados; los que faltan toman su valor por defecto en la aplicación.
+CREATE TABLE site_settings (
+  key VARCHAR(100) PRIMARY KEY,
+  value JSONB NOT NULL,
+  updated_at TIMESTAMP
+);
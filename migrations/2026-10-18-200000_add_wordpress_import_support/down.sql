@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE import_keys;
+
+ALTER TABLE comments DROP COLUMN parent_id;
+
+ALTER TABLE posts
+  DROP COLUMN status,
+  DROP COLUMN author_id;
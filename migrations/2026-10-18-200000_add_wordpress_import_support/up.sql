@@ -0,0 +1,28 @@
+-- Your SQL goes here
+
+-- Autor y estado de publicación de los posts. Los posts existentes quedan publicados.
+ALTER TABLE posts
+  ADD COLUMN author_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  ADD COLUMN status VARCHAR(20) NOT NULL DEFAULT 'published'
+    CHECK (status IN ('published', 'draft', 'private'));
+
+CREATE INDEX posts_author_id_idx ON posts (author_id);
+CREATE INDEX posts_status_idx ON posts (status);
+
+-- Respuestas a comentarios: al borrar un comentario se borran sus respuestas
+ALTER TABLE comments
+  ADD COLUMN parent_id INTEGER REFERENCES comments(id) ON DELETE CASCADE;
+
+CREATE INDEX comments_parent_id_idx ON comments (parent_id);
+
+-- Correspondencia entre los identificadores de un sistema externo y las entidades
+-- importadas, para que repetir una importación no duplique nada
+CREATE TABLE import_keys (
+  id SERIAL PRIMARY KEY,
+  source VARCHAR(255) NOT NULL,
+  entity_type VARCHAR(20) NOT NULL,
+  external_id VARCHAR(255) NOT NULL,
+  entity_id INTEGER NOT NULL,
+  created_at TIMESTAMP,
+  UNIQUE (source, entity_type, external_id)
+);
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+
+ALTER TABLE users
+  DROP COLUMN avatar_media_id,
+  DROP COLUMN social_links,
+  DROP COLUMN website,
+  DROP COLUMN bio,
+  DROP COLUMN display_name;
@@ -0,0 +1,9 @@
+-- Your SQL goes here
+
+-- Perfil público de los usuarios. `social_links` es un objeto red → URL.
+ALTER TABLE users
+  ADD COLUMN display_name VARCHAR(100),
+  ADD COLUMN bio TEXT,
+  ADD COLUMN website VARCHAR(255),
+  ADD COLUMN social_links JSONB NOT NULL DEFAULT '{}',
+  ADD COLUMN avatar_media_id INTEGER REFERENCES media(id) ON DELETE SET NULL;
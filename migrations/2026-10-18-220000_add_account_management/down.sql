@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+
+DROP INDEX IF EXISTS idx_users_email_change_token_hash;
+
+ALTER TABLE users
+  DROP COLUMN suspended_at,
+  DROP COLUMN email_change_expires_at,
+  DROP COLUMN email_change_token_hash,
+  DROP COLUMN pending_email;
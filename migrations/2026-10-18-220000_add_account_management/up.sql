@@ -0,0 +1,11 @@
+-- Your SQL goes here
+
+-- Cambio de email pendiente de confirmar: solo se guarda el SHA-256 del código
+-- enviado a la nueva dirección. `suspended_at` bloquea el inicio de sesión.
+ALTER TABLE users
+  ADD COLUMN pending_email VARCHAR(255),
+  ADD COLUMN email_change_token_hash VARCHAR(64),
+  ADD COLUMN email_change_expires_at TIMESTAMP,
+  ADD COLUMN suspended_at TIMESTAMP;
+
+CREATE UNIQUE INDEX idx_users_email_change_token_hash ON users(email_change_token_hash);
@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+
+DROP TABLE IF EXISTS bans;
+
+ALTER TABLE users
+  DROP COLUMN suspended_by,
+  DROP COLUMN suspension_reason,
+  DROP COLUMN suspended_until;
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+
+-- Suspensión de cuentas: sin `suspended_until` es un bloqueo permanente
+ALTER TABLE users
+  ADD COLUMN suspended_until TIMESTAMP,
+  ADD COLUMN suspension_reason TEXT,
+  ADD COLUMN suspended_by INTEGER REFERENCES users(id) ON DELETE SET NULL;
+
+-- Emails e IPs que no pueden comentar
+CREATE TABLE bans (
+  id SERIAL PRIMARY KEY,
+  kind VARCHAR(10) NOT NULL CHECK (kind IN ('email', 'ip')),
+  value VARCHAR(255) NOT NULL,
+  reason TEXT,
+  expires_at TIMESTAMP,
+  created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  created_at TIMESTAMP,
+  UNIQUE(kind, value)
+);
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+
+-- Códigos de invitación para el registro; solo se guarda el SHA-256 del código
+CREATE TABLE invitations (
+  id SERIAL PRIMARY KEY,
+  code_hash VARCHAR(64) NOT NULL UNIQUE,
+  role VARCHAR(50) NOT NULL DEFAULT 'user',
+  max_uses INTEGER NOT NULL DEFAULT 1 CHECK (max_uses > 0),
+  uses INTEGER NOT NULL DEFAULT 0 CHECK (uses >= 0),
+  expires_at TIMESTAMP,
+  note TEXT,
+  created_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+  created_at TIMESTAMP
+);
+
@@ -0,0 +1,22 @@
+-- This file should undo anything in `up.sql`
+
+-- Los elementos sin destino no cumplen la restricción original
+DELETE FROM menu_items
+WHERE item_type <> 'url' AND page_id IS NULL AND post_id IS NULL AND category_id IS NULL;
+
+ALTER TABLE menu_items DROP CONSTRAINT menu_items_target_matches_type;
+
+ALTER TABLE menu_items
+  DROP CONSTRAINT menu_items_page_id_fkey,
+  ADD CONSTRAINT menu_items_page_id_fkey FOREIGN KEY (page_id) REFERENCES pages(id) ON DELETE CASCADE,
+  DROP CONSTRAINT menu_items_post_id_fkey,
+  ADD CONSTRAINT menu_items_post_id_fkey FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE CASCADE,
+  DROP CONSTRAINT menu_items_category_id_fkey,
+  ADD CONSTRAINT menu_items_category_id_fkey FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE CASCADE;
+
+ALTER TABLE menu_items ADD CONSTRAINT menu_items_target_matches_type CHECK (
+  (item_type = 'page') = (page_id IS NOT NULL)
+  AND (item_type = 'post') = (post_id IS NOT NULL)
+  AND (item_type = 'category') = (category_id IS NOT NULL)
+  AND (item_type = 'url') = (url IS NOT NULL)
+);
@@ -0,0 +1,22 @@
+-- Your SQL goes here
+
+-- Al eliminar el destino de un elemento de menú, el elemento se conserva sin
+-- destino en lugar de borrarse: así no arrastra a sus subelementos. Al mostrar el
+-- menú, los elementos sin destino se omiten y sus hijos ocupan su lugar.
+ALTER TABLE menu_items DROP CONSTRAINT menu_items_target_matches_type;
+
+ALTER TABLE menu_items
+  DROP CONSTRAINT menu_items_page_id_fkey,
+  ADD CONSTRAINT menu_items_page_id_fkey FOREIGN KEY (page_id) REFERENCES pages(id) ON DELETE SET NULL,
+  DROP CONSTRAINT menu_items_post_id_fkey,
+  ADD CONSTRAINT menu_items_post_id_fkey FOREIGN KEY (post_id) REFERENCES posts(id) ON DELETE SET NULL,
+  DROP CONSTRAINT menu_items_category_id_fkey,
+  ADD CONSTRAINT menu_items_category_id_fkey FOREIGN KEY (category_id) REFERENCES categories(id) ON DELETE SET NULL;
+
+-- Cada destino solo puede estar en los elementos de su tipo, pero puede faltar
+ALTER TABLE menu_items ADD CONSTRAINT menu_items_target_matches_type CHECK (
+  (item_type = 'page' OR page_id IS NULL)
+  AND (item_type = 'post' OR post_id IS NULL)
+  AND (item_type = 'category' OR category_id IS NULL)
+  AND (item_type = 'url') = (url IS NOT NULL)
+);
@@ -0,0 +1,21 @@
+use actix_web::{web, HttpResponse, Responder, get};
+use crate::db::DbPool;
+use crate::services::ProfileService;
+use crate::utils::error::{error_body, AppError};
+
+#[get("/{username}")]
+pub async fn get_author(path: web::Path<String>, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match ProfileService::get_author_page(&path.into_inner(), &mut conn) {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener el autor: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener el autor"))
+        }
+    }
+}
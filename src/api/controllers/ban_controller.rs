@@ -0,0 +1,62 @@
+use actix_web::{web, HttpResponse, Responder, get, post, delete};
+use crate::auth::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::models::dto::CreateBanDto;
+use crate::services::BanService;
+use crate::utils::error::{error_body, AppError};
+
+#[get("")]
+pub async fn get_all_bans(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match BanService::get_all_bans(&mut conn) {
+        Ok(bans) => HttpResponse::Ok().json(bans),
+        Err(e) => {
+            log::error!("Error al obtener bloqueos: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener bloqueos"))
+        }
+    }
+}
+
+#[post("")]
+pub async fn create_ban(ban: web::Json<CreateBanDto>, admin: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let admin_id = admin.0.sub.parse::<i32>().unwrap_or(0);
+    if admin_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match BanService::create_ban(ban.into_inner(), admin_id, &mut conn) {
+        Ok(ban) => HttpResponse::Created().json(ban),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al crear bloqueo: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al crear bloqueo"))
+        }
+    }
+}
+
+#[delete("/{id}")]
+pub async fn delete_ban(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let ban_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match BanService::delete_ban(ban_id, &mut conn) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al eliminar bloqueo: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar bloqueo"))
+        }
+    }
+}
@@ -1,21 +1,81 @@
-use actix_web::{web, HttpResponse, Responder, get, post, put, delete};
-use crate::config::database::DbPool;
-use crate::models::dto::{CreateCategoryDto, UpdateCategoryDto};
-use crate::services::CategoryService;
-use crate::utils::error::AppError;
+use actix_web::{web, http::header, HttpResponse, Responder, get, post, put, delete};
+use crate::db::DbPool;
+use crate::models::dto::{CategoryPostsQuery, CreateCategoryDto, DeleteCategoryQuery, MergeCategoryDto, UpdateCategoryDto};
+use crate::services::{CategoryService, SlugLookup};
+use crate::utils::error::{error_body, AppError};
 
 #[get("")]
 pub async fn get_all_categories(pool: web::Data<DbPool>) -> impl Responder {
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match CategoryService::get_all_categories(&mut conn) {
         Ok(categories) => HttpResponse::Ok().json(categories),
         Err(e) => {
             log::error!("Error al obtener categorías: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener categorías")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener categorías"))
+        }
+    }
+}
+
+#[get("/tree")]
+pub async fn get_category_tree(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match CategoryService::get_category_tree(&mut conn) {
+        Ok(tree) => HttpResponse::Ok().json(tree),
+        Err(e) => {
+            log::error!("Error al obtener el árbol de categorías: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener el árbol de categorías"))
+        }
+    }
+}
+
+#[get("/slug/{slug}")]
+pub async fn get_category_by_slug(path: web::Path<String>, pool: web::Data<DbPool>) -> impl Responder {
+    let slug = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match CategoryService::get_category_by_slug(&slug, &mut conn) {
+        Ok(SlugLookup::Found(category)) => HttpResponse::Ok().json(category),
+        Ok(SlugLookup::Moved(current)) => HttpResponse::MovedPermanently()
+            .insert_header((header::LOCATION, format!("/api/categories/slug/{}", current)))
+            .finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener categoría por slug: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener categoría por slug"))
+        }
+    }
+}
+
+#[get("/{id}/posts")]
+pub async fn get_category_posts(
+    path: web::Path<i32>,
+    query: web::Query<CategoryPostsQuery>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let category_id = path.into_inner();
+    let include_descendants = query.include_descendants.unwrap_or(true);
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match CategoryService::get_category_posts(category_id, include_descendants, &mut conn) {
+        Ok(posts) => HttpResponse::Ok().json(posts),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener posts de la categoría: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener posts de la categoría"))
         }
     }
 }
@@ -25,15 +85,15 @@ pub async fn get_category_by_id(path: web::Path<i32>, pool: web::Data<DbPool>) -
     let category_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match CategoryService::get_category_by_id(category_id, &mut conn) {
         Ok(category) => HttpResponse::Ok().json(category),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al obtener categoría: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener categoría")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener categoría"))
         }
     }
 }
@@ -42,15 +102,15 @@ pub async fn get_category_by_id(path: web::Path<i32>, pool: web::Data<DbPool>) -
 pub async fn create_category(category: web::Json<CreateCategoryDto>, pool: web::Data<DbPool>) -> impl Responder {
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match CategoryService::create_category(category.into_inner(), &mut conn) {
         Ok(category) => HttpResponse::Created().json(category),
-        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(msg),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al crear categoría: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al crear categoría")
+            HttpResponse::InternalServerError().json(error_body("Error al crear categoría"))
         }
     }
 }
@@ -64,34 +124,63 @@ pub async fn update_category(
     let category_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match CategoryService::update_category(category_id, category.into_inner(), &mut conn) {
         Ok(category) => HttpResponse::Ok().json(category),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
-        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al actualizar categoría: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al actualizar categoría")
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar categoría"))
+        }
+    }
+}
+
+#[post("/{id}/merge")]
+pub async fn merge_category(
+    path: web::Path<i32>,
+    merge: web::Json<MergeCategoryDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let category_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match CategoryService::merge_categories(category_id, merge.target_id, &mut conn) {
+        Ok(category) => HttpResponse::Ok().json(category),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al fusionar categorías: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al fusionar categorías"))
         }
     }
 }
 
 #[delete("/{id}")]
-pub async fn delete_category(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+pub async fn delete_category(
+    path: web::Path<i32>,
+    query: web::Query<DeleteCategoryQuery>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
     let category_id = path.into_inner();
+    let force = query.force.unwrap_or(false);
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match CategoryService::delete_category(category_id, &mut conn) {
+    match CategoryService::delete_category(category_id, query.reassign_to, force, &mut conn) {
         Ok(_) => HttpResponse::NoContent().finish(),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al eliminar categoría: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al eliminar categoría")
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar categoría"))
         }
     }
-} 
\ No newline at end of file
+}
@@ -1,22 +1,23 @@
-use actix_web::{web, HttpResponse, Responder, get, post, put, delete};
-use crate::config::database::DbPool;
-use crate::models::dto::{CreateCommentDto, UpdateCommentDto};
-use crate::services::CommentService;
-use crate::utils::error::AppError;
-use crate::auth::{AuthenticatedUser, OptionalAuthenticatedUser};
+use actix_web::{web, HttpRequest, HttpResponse, Responder, get, post, put, delete};
+use crate::db::DbPool;
+use crate::models::dto::{CreateCommentDto, PurgeCommenterEmailDto, UpdateCommentDto};
+use crate::services::{CommentService, PrivacyService};
+use crate::utils::client_ip;
+use crate::utils::error::{error_body, AppError};
+use crate::auth::OptionalAuthenticatedUser;
 
 #[get("")]
 pub async fn get_all_comments(pool: web::Data<DbPool>) -> impl Responder {
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match CommentService::get_all_comments(&mut conn) {
         Ok(comments) => HttpResponse::Ok().json(comments),
         Err(e) => {
             log::error!("Error al obtener comentarios: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener comentarios")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener comentarios"))
         }
     }
 }
@@ -26,15 +27,15 @@ pub async fn get_comment_by_id(path: web::Path<i32>, pool: web::Data<DbPool>) ->
     let comment_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match CommentService::get_comment_by_id(comment_id, &mut conn) {
         Ok(comment) => HttpResponse::Ok().json(comment),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al obtener comentario: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener comentario")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener comentario"))
         }
     }
 }
@@ -44,33 +45,37 @@ pub async fn get_comments_by_post(path: web::Path<i32>, pool: web::Data<DbPool>)
     let post_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match CommentService::get_comments_by_post_id(post_id, &mut conn) {
         Ok(comments) => HttpResponse::Ok().json(comments),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al obtener comentarios del post: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener comentarios del post")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener comentarios del post"))
         }
     }
 }
 
 #[post("")]
 pub async fn create_comment(
+    req: HttpRequest,
     comment: web::Json<CreateCommentDto>, 
     auth_user: OptionalAuthenticatedUser, 
     pool: web::Data<DbPool>
 ) -> impl Responder {
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     // Extraer el ID de usuario del token JWT si está disponible
     let mut comment_data = comment.into_inner();
+    comment_data.author_ip = client_ip::from_request(&req);
     
-    // Si hay un usuario autenticado, usar su ID
+    // Si hay un usuario autenticado, usar su ID; sin sesión, nunca el que venga en el cuerpo
+    comment_data.user_id = None;
     if let Some(claims) = auth_user.0 {
         let user_id = claims.sub.parse::<i32>().unwrap_or(0);
         if user_id > 0 {
@@ -85,10 +90,11 @@ pub async fn create_comment(
 
     match CommentService::create_comment(comment_data, &mut conn) {
         Ok(comment) => HttpResponse::Created().json(comment),
-        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(msg),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al crear comentario: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al crear comentario")
+            HttpResponse::InternalServerError().json(error_body("Error al crear comentario"))
         }
     }
 }
@@ -102,16 +108,16 @@ pub async fn update_comment(
     let comment_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match CommentService::update_comment(comment_id, comment.into_inner(), &mut conn) {
         Ok(comment) => HttpResponse::Ok().json(comment),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
-        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al actualizar comentario: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al actualizar comentario")
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar comentario"))
         }
     }
 }
@@ -121,15 +127,33 @@ pub async fn delete_comment(path: web::Path<i32>, pool: web::Data<DbPool>) -> im
     let comment_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match CommentService::delete_comment(comment_id, &mut conn) {
         Ok(_) => HttpResponse::NoContent().finish(),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al eliminar comentario: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al eliminar comentario")
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar comentario"))
         }
     }
-} 
\ No newline at end of file
+} 
+
+/// Borra el email de un comentarista anónimo de todos sus comentarios (`/api/admin`)
+#[post("/comments/purge-email")]
+pub async fn purge_commenter_email(request: web::Json<PurgeCommenterEmailDto>, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match PrivacyService::purge_commenter_email(&request.email, &mut conn) {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al borrar el email del comentarista: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al borrar el email del comentarista"))
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use actix_web::{web, HttpResponse, Responder, get};
+use crate::db::DbPool;
+use crate::metrics;
+use crate::services::HealthService;
+use crate::utils::error::error_body;
+
+#[get("/live")]
+pub async fn live() -> impl Responder {
+    HttpResponse::Ok().json(HealthService::liveness())
+}
+
+#[get("/ready")]
+pub async fn ready(pool: web::Data<DbPool>) -> impl Responder {
+    // La comprobación bloquea mientras espera una conexión, así que se saca del hilo del worker
+    let readiness = match web::block(move || HealthService::readiness(&pool)).await {
+        Ok(readiness) => readiness,
+        Err(e) => {
+            log::error!("Error al comprobar el estado del servicio: {:?}", e);
+            return HttpResponse::ServiceUnavailable().json(error_body("Error al comprobar el estado del servicio"));
+        }
+    };
+
+    if readiness.errors.is_empty() {
+        HttpResponse::Ok().json(readiness)
+    } else {
+        HttpResponse::ServiceUnavailable().json(readiness)
+    }
+}
+
+#[get("/version")]
+pub async fn version() -> impl Responder {
+    HttpResponse::Ok().json(HealthService::version())
+}
+
+#[get("/metrics")]
+pub async fn prometheus_metrics(pool: web::Data<DbPool>) -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(metrics::render(&pool))
+}
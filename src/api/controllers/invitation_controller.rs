@@ -0,0 +1,62 @@
+use actix_web::{web, HttpResponse, Responder, get, post, delete};
+use crate::auth::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::models::dto::CreateInvitationDto;
+use crate::services::InvitationService;
+use crate::utils::error::{error_body, AppError};
+
+#[get("")]
+pub async fn get_all_invitations(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match InvitationService::get_all_invitations(&mut conn) {
+        Ok(invitations) => HttpResponse::Ok().json(invitations),
+        Err(e) => {
+            log::error!("Error al obtener invitaciones: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener invitaciones"))
+        }
+    }
+}
+
+#[post("")]
+pub async fn create_invitation(invitation: web::Json<CreateInvitationDto>, admin: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let admin_id = admin.0.sub.parse::<i32>().unwrap_or(0);
+    if admin_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match InvitationService::create_invitation(invitation.into_inner(), admin_id, &mut conn) {
+        Ok(invitation) => HttpResponse::Created().json(invitation),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al crear invitación: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al crear invitación"))
+        }
+    }
+}
+
+#[delete("/{id}")]
+pub async fn delete_invitation(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let invitation_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match InvitationService::delete_invitation(invitation_id, &mut conn) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al eliminar invitación: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar invitación"))
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use actix_web::{web, HttpResponse, Responder, get, post, put, patch, delete};
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use crate::auth::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::models::dto::{ChangeEmailDto, ChangePasswordDto, CreateApiTokenDto, DeleteAccountDto, UpdateProfileDto};
+use crate::services::{ApiTokenService, PrivacyService, ProfileService, UserService};
+use crate::utils::error::{error_body, AppError};
+
+#[get("")]
+pub async fn get_my_profile(user: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let user_id = user.0.sub.parse::<i32>().unwrap_or(0);
+    if user_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match ProfileService::get_profile(user_id, &mut conn) {
+        Ok(profile) => HttpResponse::Ok().json(profile),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener el perfil: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener el perfil"))
+        }
+    }
+}
+
+#[patch("")]
+pub async fn update_my_profile(
+    user: AuthenticatedUser,
+    profile: web::Json<UpdateProfileDto>,
+    pool: web::Data<DbPool>,
+) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let user_id = user.0.sub.parse::<i32>().unwrap_or(0);
+    if user_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match ProfileService::update_profile(user_id, profile.into_inner(), &mut conn) {
+        Ok(profile) => HttpResponse::Ok().json(profile),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al actualizar el perfil: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar el perfil"))
+        }
+    }
+}
+
+#[put("/password")]
+pub async fn change_my_password(
+    user: AuthenticatedUser,
+    passwords: web::Json<ChangePasswordDto>,
+    pool: web::Data<DbPool>,
+) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let user_id = user.0.sub.parse::<i32>().unwrap_or(0);
+    if user_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match UserService::change_password(user_id, passwords.into_inner(), &mut conn) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(AppError::AuthenticationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al cambiar la contraseña: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al cambiar la contraseña"))
+        }
+    }
+}
+
+#[put("/email")]
+pub async fn change_my_email(
+    user: AuthenticatedUser,
+    change: web::Json<ChangeEmailDto>,
+    pool: web::Data<DbPool>,
+) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let user_id = user.0.sub.parse::<i32>().unwrap_or(0);
+    if user_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match UserService::request_email_change(user_id, change.into_inner(), &mut conn) {
+        Ok(user) => HttpResponse::Accepted().json(user),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(AppError::AuthenticationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al cambiar el email: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al cambiar el email"))
+        }
+    }
+}
+
+#[get("/export")]
+pub async fn export_my_data(user: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let user_id = user.0.sub.parse::<i32>().unwrap_or(0);
+    if user_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match PrivacyService::export_personal_data(user_id, &mut conn) {
+        Ok(export) => HttpResponse::Ok()
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(format!("datos-{}.json", export.profile.username))],
+            })
+            .json(export),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al exportar los datos personales: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al exportar los datos personales"))
+        }
+    }
+}
+
+#[delete("")]
+pub async fn delete_my_account(
+    user: AuthenticatedUser,
+    confirmation: web::Json<DeleteAccountDto>,
+    pool: web::Data<DbPool>,
+) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let user_id = user.0.sub.parse::<i32>().unwrap_or(0);
+    if user_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match PrivacyService::delete_account(user_id, confirmation.into_inner(), &mut conn) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(AppError::AuthenticationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al eliminar la cuenta: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar la cuenta"))
+        }
+    }
+}
+
+#[get("/tokens")]
+pub async fn get_my_tokens(user: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let user_id = user.0.sub.parse::<i32>().unwrap_or(0);
+    if user_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match ApiTokenService::get_user_tokens(user_id, &mut conn) {
+        Ok(tokens) => HttpResponse::Ok().json(tokens),
+        Err(e) => {
+            log::error!("Error al obtener los tokens: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener los tokens"))
+        }
+    }
+}
+
+#[post("/tokens")]
+pub async fn create_my_token(
+    user: AuthenticatedUser,
+    token: web::Json<CreateApiTokenDto>,
+    pool: web::Data<DbPool>,
+) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let user_id = user.0.sub.parse::<i32>().unwrap_or(0);
+    if user_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match ApiTokenService::create_token(user_id, token.into_inner(), &mut conn) {
+        Ok(token) => HttpResponse::Created().json(token),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al crear el token: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al crear el token"))
+        }
+    }
+}
+
+#[delete("/tokens/{id}")]
+pub async fn revoke_my_token(user: AuthenticatedUser, path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let token_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let user_id = user.0.sub.parse::<i32>().unwrap_or(0);
+    if user_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match ApiTokenService::revoke_token(token_id, user_id, &mut conn) {
+        Ok(()) => HttpResponse::NoContent().finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al revocar el token: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al revocar el token"))
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use actix_multipart::{Field, Multipart};
+use actix_web::http::header;
+use actix_web::{web, HttpResponse, Responder, get, post, put, delete};
+use futures::StreamExt;
+use crate::auth::{AuthenticatedUser, OptionalAuthenticatedUser};
+use crate::config;
+use crate::db::DbPool;
+use crate::models::dto::{AttachMediaDto, UpdateMediaDto};
+use crate::services::media_service::UploadedFile;
+use crate::services::MediaService;
+use crate::storage;
+use crate::utils::error::{error_body, AppError};
+
+/// Tamaño máximo de los campos de texto del formulario de subida
+const MAX_TEXT_FIELD_BYTES: usize = 1024;
+
+#[get("")]
+pub async fn get_all_media(user: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    // Los administradores ven toda la biblioteca; el resto, solo sus ficheros
+    let owner = if user.0.role == "admin" { None } else { Some(user_id(&user)) };
+
+    match MediaService::get_all_media(owner, &mut conn) {
+        Ok(media) => HttpResponse::Ok().json(media),
+        Err(e) => {
+            log::error!("Error al obtener la biblioteca de medios: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener la biblioteca de medios"))
+        }
+    }
+}
+
+#[get("/{id}")]
+pub async fn get_media_by_id(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let media_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MediaService::get_media_by_id(media_id, &mut conn) {
+        Ok(media) => HttpResponse::Ok().json(media),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener fichero: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener fichero"))
+        }
+    }
+}
+
+/// Sube un fichero en un formulario `multipart/form-data` con los campos `file` y,
+/// opcionalmente, `alt_text`
+#[post("")]
+pub async fn upload_media(user: AuthenticatedUser, mut payload: Multipart, pool: web::Data<DbPool>) -> impl Responder {
+    let max_bytes = config::media_max_upload_bytes();
+    let mut file: Option<(String, Vec<u8>)> = None;
+    let mut alt_text = None;
+
+    while let Some(field) = payload.next().await {
+        let mut field = match field {
+            Ok(field) => field,
+            Err(e) => return HttpResponse::BadRequest().json(error_body(format!("Formulario no válido: {}", e))),
+        };
+
+        match field.name() {
+            Some("file") => {
+                let filename = field
+                    .content_disposition()
+                    .and_then(|cd| cd.get_filename())
+                    .unwrap_or("fichero")
+                    .to_string();
+                match read_field(&mut field, max_bytes).await {
+                    Ok(Some(data)) => file = Some((filename, data)),
+                    Ok(None) => {
+                        return HttpResponse::PayloadTooLarge()
+                            .json(error_body(format!("El fichero supera el tamaño máximo de {} bytes", max_bytes)))
+                    }
+                    Err(e) => return HttpResponse::BadRequest().json(error_body(format!("Formulario no válido: {}", e))),
+                }
+            }
+            Some("alt_text") => match read_field(&mut field, MAX_TEXT_FIELD_BYTES).await {
+                Ok(Some(data)) => alt_text = Some(String::from_utf8_lossy(&data).into_owned()),
+                Ok(None) => return HttpResponse::BadRequest().json(error_body("El texto alternativo es demasiado largo")),
+                Err(e) => return HttpResponse::BadRequest().json(error_body(format!("Formulario no válido: {}", e))),
+            },
+            // Los campos desconocidos se descartan
+            _ => while let Some(Ok(_)) = field.next().await {},
+        }
+    }
+
+    let (filename, data) = match file {
+        Some(file) => file,
+        None => return HttpResponse::BadRequest().json(error_body("Falta el campo 'file'")),
+    };
+
+    let user_id = user_id(&user);
+    let is_admin = user.0.role == "admin";
+    let upload = UploadedFile { filename, data, alt_text };
+
+    // Procesar imágenes y escribir en el almacenamiento bloquea: fuera del hilo del worker
+    let result = web::block(move || {
+        let mut conn = pool.get().map_err(|_| {
+            AppError::InternalServerError("Error de conexión a la base de datos".to_string())
+        })?;
+        MediaService::upload(user_id, is_admin, upload, &mut conn)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(media)) => HttpResponse::Created().json(media),
+        Ok(Err(AppError::ValidationError(msg))) => HttpResponse::BadRequest().json(error_body(msg)),
+        Ok(Err(e)) => {
+            log::error!("Error al subir fichero: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al subir fichero"))
+        }
+        Err(e) => {
+            log::error!("Error al subir fichero: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al subir fichero"))
+        }
+    }
+}
+
+#[put("/{id}")]
+pub async fn update_media(
+    user: AuthenticatedUser,
+    path: web::Path<i32>,
+    media: web::Json<UpdateMediaDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let media_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let is_admin = user.0.role == "admin";
+    match MediaService::update_media(media_id, media.into_inner(), user_id(&user), is_admin, &mut conn) {
+        Ok(media) => HttpResponse::Ok().json(media),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al actualizar fichero: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar fichero"))
+        }
+    }
+}
+
+#[delete("/{id}")]
+pub async fn delete_media(user: AuthenticatedUser, path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let media_id = path.into_inner();
+    let is_admin = user.0.role == "admin";
+    let user_id = user_id(&user);
+
+    // Borrar del almacenamiento bloquea: fuera del hilo del worker
+    let result = web::block(move || {
+        let mut conn = pool.get().map_err(|_| {
+            AppError::InternalServerError("Error de conexión a la base de datos".to_string())
+        })?;
+        MediaService::delete_media(media_id, user_id, is_admin, &mut conn)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(_)) => HttpResponse::NoContent().finish(),
+        Ok(Err(AppError::NotFound(msg))) => HttpResponse::NotFound().json(error_body(msg)),
+        Ok(Err(AppError::AuthorizationError(msg))) => HttpResponse::Forbidden().json(error_body(msg)),
+        Ok(Err(e)) => {
+            log::error!("Error al eliminar fichero: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar fichero"))
+        }
+        Err(e) => {
+            log::error!("Error al eliminar fichero: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar fichero"))
+        }
+    }
+}
+
+#[get("/{id}/media")]
+pub async fn get_post_media(
+    path: web::Path<i32>,
+    auth_user: OptionalAuthenticatedUser,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let post_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MediaService::get_post_media(post_id, auth_user.0.as_ref(), &mut conn) {
+        Ok(media) => HttpResponse::Ok().json(media),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener la galería del post: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener la galería del post"))
+        }
+    }
+}
+
+#[post("/{id}/media")]
+pub async fn attach_media(
+    path: web::Path<i32>,
+    attach: web::Json<AttachMediaDto>,
+    user: AuthenticatedUser,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let post_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MediaService::attach_to_post(post_id, attach.into_inner(), &user.0, &mut conn) {
+        Ok(media) => HttpResponse::Ok().json(media),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al asociar fichero al post: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al asociar fichero al post"))
+        }
+    }
+}
+
+#[delete("/{id}/media/{media_id}")]
+pub async fn detach_media(path: web::Path<(i32, i32)>, user: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
+    let (post_id, media_id) = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MediaService::detach_from_post(post_id, media_id, &user.0, &mut conn) {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al quitar fichero del post: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al quitar fichero del post"))
+        }
+    }
+}
+
+/// Sirve los ficheros guardados en el backend de almacenamiento. Las claves llevan
+/// un UUID, así que su contenido nunca cambia y se puede cachear indefinidamente.
+#[get("/{key:.*}")]
+pub async fn serve_file(path: web::Path<String>) -> impl Responder {
+    let key = path.into_inner();
+    let content_type = storage::content_type_for(&key);
+
+    match web::block(move || storage::backend().get(&key)).await {
+        Ok(Ok(data)) => HttpResponse::Ok()
+            .content_type(content_type)
+            .insert_header((header::CACHE_CONTROL, "public, max-age=31536000, immutable"))
+            .body(data),
+        Ok(Err(AppError::NotFound(msg))) => HttpResponse::NotFound().json(error_body(msg)),
+        Ok(Err(e)) => {
+            log::error!("Error al leer fichero: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al leer fichero"))
+        }
+        Err(e) => {
+            log::error!("Error al leer fichero: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al leer fichero"))
+        }
+    }
+}
+
+/// Lee un campo completo; `None` si supera `max_bytes`
+async fn read_field(field: &mut Field, max_bytes: usize) -> Result<Option<Vec<u8>>, actix_multipart::MultipartError> {
+    let mut data = Vec::new();
+    while let Some(chunk) = field.next().await {
+        let chunk = chunk?;
+        if data.len() + chunk.len() > max_bytes {
+            return Ok(None);
+        }
+        data.extend_from_slice(&chunk);
+    }
+    Ok(Some(data))
+}
+
+fn user_id(user: &AuthenticatedUser) -> i32 {
+    user.0.sub.parse::<i32>().unwrap_or(0)
+}
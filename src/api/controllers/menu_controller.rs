@@ -0,0 +1,120 @@
+use actix_web::{web, HttpResponse, Responder, get, post, put, delete};
+use crate::db::DbPool;
+use crate::models::dto::{CreateMenuDto, SetMenuItemsDto, UpdateMenuDto};
+use crate::services::MenuService;
+use crate::utils::error::{error_body, AppError};
+
+#[get("/{location}")]
+pub async fn get_menu_by_location(path: web::Path<String>, pool: web::Data<DbPool>) -> impl Responder {
+    let location = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MenuService::get_menu_by_location(&location, &mut conn) {
+        Ok(menu) => HttpResponse::Ok().json(menu),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener menú: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener menú"))
+        }
+    }
+}
+
+#[get("")]
+pub async fn get_all_menus(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MenuService::get_all_menus(&mut conn) {
+        Ok(menus) => HttpResponse::Ok().json(menus),
+        Err(e) => {
+            log::error!("Error al obtener menús: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener menús"))
+        }
+    }
+}
+
+#[post("")]
+pub async fn create_menu(menu: web::Json<CreateMenuDto>, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MenuService::create_menu(menu.into_inner(), &mut conn) {
+        Ok(menu) => HttpResponse::Created().json(menu),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al crear menú: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al crear menú"))
+        }
+    }
+}
+
+#[put("/{id}")]
+pub async fn update_menu(
+    path: web::Path<i32>,
+    menu: web::Json<UpdateMenuDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let menu_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MenuService::update_menu(menu_id, menu.into_inner(), &mut conn) {
+        Ok(menu) => HttpResponse::Ok().json(menu),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al actualizar menú: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar menú"))
+        }
+    }
+}
+
+#[put("/{id}/items")]
+pub async fn set_menu_items(
+    path: web::Path<i32>,
+    items: web::Json<SetMenuItemsDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let menu_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MenuService::set_menu_items(menu_id, items.into_inner().items, &mut conn) {
+        Ok(menu) => HttpResponse::Ok().json(menu),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al guardar los elementos del menú: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al guardar los elementos del menú"))
+        }
+    }
+}
+
+#[delete("/{id}")]
+pub async fn delete_menu(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let menu_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match MenuService::delete_menu(menu_id, &mut conn) {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al eliminar menú: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar menú"))
+        }
+    }
+}
@@ -2,4 +2,16 @@ pub mod user_controller;
 pub mod category_controller;
 pub mod post_controller;
 pub mod comment_controller;
-pub mod auth_controller; 
\ No newline at end of file
+pub mod auth_controller;
+pub mod health_controller;
+pub mod tag_controller;
+pub mod media_controller;
+pub mod series_controller;
+pub mod redirect_controller;
+pub mod page_controller;
+pub mod menu_controller;
+pub mod settings_controller;
+pub mod me_controller;
+pub mod author_controller;
+pub mod ban_controller;
+pub mod invitation_controller;
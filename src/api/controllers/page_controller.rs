@@ -0,0 +1,132 @@
+use actix_web::{web, HttpResponse, Responder, get, post, put, delete};
+use crate::db::DbPool;
+use crate::models::dto::{CreatePageDto, UpdatePageDto};
+use crate::services::PageService;
+use crate::utils::error::{error_body, AppError};
+
+#[get("")]
+pub async fn get_all_pages(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match PageService::get_all_pages(&mut conn) {
+        Ok(pages) => HttpResponse::Ok().json(pages),
+        Err(e) => {
+            log::error!("Error al obtener páginas: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener páginas"))
+        }
+    }
+}
+
+#[get("/tree")]
+pub async fn get_page_tree(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match PageService::get_page_tree(&mut conn) {
+        Ok(tree) => HttpResponse::Ok().json(tree),
+        Err(e) => {
+            log::error!("Error al obtener el árbol de páginas: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener el árbol de páginas"))
+        }
+    }
+}
+
+#[get("/path/{path:.*}")]
+pub async fn get_page_by_path(path: web::Path<String>, pool: web::Data<DbPool>) -> impl Responder {
+    let page_path = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match PageService::get_page_by_path(&page_path, &mut conn) {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener página: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener página"))
+        }
+    }
+}
+
+#[get("/{id}")]
+pub async fn get_page_by_id(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let page_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match PageService::get_page_by_id(page_id, &mut conn) {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener página: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener página"))
+        }
+    }
+}
+
+#[post("")]
+pub async fn create_page(page: web::Json<CreatePageDto>, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match PageService::create_page(page.into_inner(), &mut conn) {
+        Ok(page) => HttpResponse::Created().json(page),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al crear página: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al crear página"))
+        }
+    }
+}
+
+#[put("/{id}")]
+pub async fn update_page(
+    path: web::Path<i32>,
+    page: web::Json<UpdatePageDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let page_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match PageService::update_page(page_id, page.into_inner(), &mut conn) {
+        Ok(page) => HttpResponse::Ok().json(page),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al actualizar página: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar página"))
+        }
+    }
+}
+
+#[delete("/{id}")]
+pub async fn delete_page(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let page_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match PageService::delete_page(page_id, &mut conn) {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al eliminar página: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar página"))
+        }
+    }
+}
@@ -1,92 +1,125 @@
-use actix_web::{web, HttpResponse, Responder, get, post, put, delete};
-use crate::config::database::DbPool;
-use crate::models::dto::{CreatePostDto, UpdatePostDto};
-use crate::services::PostService;
-use crate::utils::error::AppError;
+use actix_web::{web, http::header, HttpResponse, Responder, get, post, put, delete};
+use crate::auth::{AuthenticatedUser, OptionalAuthenticatedUser};
+use crate::db::DbPool;
+use crate::models::dto::{CreatePostDto, PostListQuery, UpdatePostDto};
+use crate::services::{PostService, SlugLookup};
+use crate::utils::error::{error_body, AppError};
 
 #[get("")]
-pub async fn get_all_posts(pool: web::Data<DbPool>) -> impl Responder {
+pub async fn get_all_posts(query: web::Query<PostListQuery>, pool: web::Data<DbPool>) -> impl Responder {
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match PostService::get_all_posts(&mut conn) {
-        Ok(posts) => HttpResponse::Ok().json(posts),
+    let result = match query.view.as_deref() {
+        None | Some("full") => PostService::get_all_posts(query.page, &mut conn).map(|posts| HttpResponse::Ok().json(posts)),
+        Some("summary") => PostService::get_post_summaries(query.page, &mut conn).map(|posts| HttpResponse::Ok().json(posts)),
+        Some(other) => Err(AppError::ValidationError(format!(
+            "Vista '{}' no válida; usa 'full' o 'summary'",
+            other
+        ))),
+    };
+
+    match result {
+        Ok(response) => response,
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al obtener posts: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener posts")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener posts"))
         }
     }
 }
 
 #[get("/{id}")]
-pub async fn get_post_by_id(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+pub async fn get_post_by_id(
+    path: web::Path<i32>,
+    auth_user: OptionalAuthenticatedUser,
+    pool: web::Data<DbPool>
+) -> impl Responder {
     let post_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match PostService::get_post_by_id(post_id, &mut conn) {
+    match PostService::get_post_by_id(post_id, auth_user.0.as_ref(), &mut conn) {
         Ok(post) => HttpResponse::Ok().json(post),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al obtener post: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener post")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener post"))
         }
     }
 }
 
 #[get("/{id}/detail")]
-pub async fn get_post_detail(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+pub async fn get_post_detail(
+    path: web::Path<i32>,
+    auth_user: OptionalAuthenticatedUser,
+    pool: web::Data<DbPool>
+) -> impl Responder {
     let post_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match PostService::get_post_detail(post_id, &mut conn) {
+    match PostService::get_post_detail(post_id, auth_user.0.as_ref(), &mut conn) {
         Ok(post) => HttpResponse::Ok().json(post),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al obtener detalle del post: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener detalle del post")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener detalle del post"))
         }
     }
 }
 
 #[get("/slug/{slug}")]
-pub async fn get_post_by_slug(path: web::Path<String>, pool: web::Data<DbPool>) -> impl Responder {
+pub async fn get_post_by_slug(
+    path: web::Path<String>,
+    auth_user: OptionalAuthenticatedUser,
+    pool: web::Data<DbPool>
+) -> impl Responder {
     let slug = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match PostService::get_post_by_slug(&slug, &mut conn) {
-        Ok(post) => HttpResponse::Ok().json(post),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+    match PostService::get_post_by_slug(&slug, auth_user.0.as_ref(), &mut conn) {
+        Ok(SlugLookup::Found(post)) => HttpResponse::Ok().json(post),
+        Ok(SlugLookup::Moved(current)) => HttpResponse::MovedPermanently()
+            .insert_header((header::LOCATION, format!("/api/posts/slug/{}", current)))
+            .finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al obtener post por slug: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener post por slug")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener post por slug"))
         }
     }
 }
 
 #[post("")]
-pub async fn create_post(post: web::Json<CreatePostDto>, pool: web::Data<DbPool>) -> impl Responder {
+pub async fn create_post(
+    post: web::Json<CreatePostDto>,
+    user: AuthenticatedUser,
+    pool: web::Data<DbPool>
+) -> impl Responder {
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match PostService::create_post(post.into_inner(), &mut conn) {
+    // El usuario autenticado firma el post
+    let author_id = user.0.sub.parse::<i32>().ok();
+
+    match PostService::create_post(post.into_inner(), author_id, &mut conn) {
         Ok(post) => HttpResponse::Created().json(post),
-        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(msg),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al crear post: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al crear post")
+            HttpResponse::InternalServerError().json(error_body("Error al crear post"))
         }
     }
 }
@@ -95,39 +128,42 @@ pub async fn create_post(post: web::Json<CreatePostDto>, pool: web::Data<DbPool>
 pub async fn update_post(
     path: web::Path<i32>,
     post: web::Json<UpdatePostDto>,
+    user: AuthenticatedUser,
     pool: web::Data<DbPool>
 ) -> impl Responder {
     let post_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match PostService::update_post(post_id, post.into_inner(), &mut conn) {
+    match PostService::update_post(post_id, post.into_inner(), &user.0, &mut conn) {
         Ok(post) => HttpResponse::Ok().json(post),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
-        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al actualizar post: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al actualizar post")
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar post"))
         }
     }
 }
 
 #[delete("/{id}")]
-pub async fn delete_post(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+pub async fn delete_post(path: web::Path<i32>, user: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
     let post_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match PostService::delete_post(post_id, &mut conn) {
+    match PostService::delete_post(post_id, &user.0, &mut conn) {
         Ok(_) => HttpResponse::NoContent().finish(),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al eliminar post: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al eliminar post")
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar post"))
         }
     }
 } 
\ No newline at end of file
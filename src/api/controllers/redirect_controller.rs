@@ -0,0 +1,113 @@
+use actix_web::{web, http::header, http::Method, http::StatusCode, HttpRequest, HttpResponse, Responder, get, post, put, delete};
+use crate::db::DbPool;
+use crate::models::dto::{CreateRedirectDto, UpdateRedirectDto};
+use crate::services::RedirectService;
+use crate::utils::error::{error_body, AppError};
+
+#[get("")]
+pub async fn get_all_redirects(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match RedirectService::get_all_redirects(&mut conn) {
+        Ok(redirects) => HttpResponse::Ok().json(redirects),
+        Err(e) => {
+            log::error!("Error al obtener redirecciones: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener redirecciones"))
+        }
+    }
+}
+
+#[post("")]
+pub async fn create_redirect(redirect: web::Json<CreateRedirectDto>, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match RedirectService::create_redirect(redirect.into_inner(), &mut conn) {
+        Ok(redirect) => HttpResponse::Created().json(redirect),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al crear redirección: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al crear redirección"))
+        }
+    }
+}
+
+#[put("/{id}")]
+pub async fn update_redirect(
+    path: web::Path<i32>,
+    redirect: web::Json<UpdateRedirectDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let redirect_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match RedirectService::update_redirect(redirect_id, redirect.into_inner(), &mut conn) {
+        Ok(redirect) => HttpResponse::Ok().json(redirect),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al actualizar redirección: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar redirección"))
+        }
+    }
+}
+
+#[delete("/{id}")]
+pub async fn delete_redirect(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let redirect_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match RedirectService::delete_redirect(redirect_id, &mut conn) {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al eliminar redirección: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar redirección"))
+        }
+    }
+}
+
+/// Servicio por defecto: las rutas que no corresponden a ningún endpoint se
+/// buscan en la tabla de redirecciones antes de responder 404. Solo para GET y HEAD.
+pub async fn resolve_redirect(req: HttpRequest, pool: web::Data<DbPool>) -> HttpResponse {
+    // Solo se redirigen las lecturas; el resto de métodos reciben el 404 de siempre
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return HttpResponse::NotFound().json(error_body("Recurso no encontrado"));
+    }
+
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match RedirectService::resolve(req.path(), &mut conn) {
+        Ok(Some(redirect)) => {
+            // Se conservan los parámetros de la petición original
+            let location = match req.query_string() {
+                "" => redirect.target_path,
+                query if redirect.target_path.contains('?') => format!("{}&{}", redirect.target_path, query),
+                query => format!("{}?{}", redirect.target_path, query),
+            };
+            let status = StatusCode::from_u16(redirect.status_code as u16).unwrap_or(StatusCode::MOVED_PERMANENTLY);
+            HttpResponse::build(status)
+                .insert_header((header::LOCATION, location))
+                .finish()
+        }
+        Ok(None) => HttpResponse::NotFound().json(error_body("Recurso no encontrado")),
+        Err(e) => {
+            log::error!("Error al resolver redirección: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al resolver redirección"))
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use actix_web::{web, HttpResponse, Responder, get, post, put, delete};
+use crate::db::DbPool;
+use crate::models::dto::{CreateSeriesDto, SetSeriesPostsDto, UpdateSeriesDto};
+use crate::services::SeriesService;
+use crate::utils::error::{error_body, AppError};
+
+#[get("")]
+pub async fn get_all_series(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match SeriesService::get_all_series(&mut conn) {
+        Ok(series) => HttpResponse::Ok().json(series),
+        Err(e) => {
+            log::error!("Error al obtener series: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener series"))
+        }
+    }
+}
+
+#[get("/{slug}")]
+pub async fn get_series_by_slug(path: web::Path<String>, pool: web::Data<DbPool>) -> impl Responder {
+    let slug = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match SeriesService::get_series_by_slug(&slug, &mut conn) {
+        Ok(series) => HttpResponse::Ok().json(series),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener serie: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener serie"))
+        }
+    }
+}
+
+#[post("")]
+pub async fn create_series(series: web::Json<CreateSeriesDto>, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match SeriesService::create_series(series.into_inner(), &mut conn) {
+        Ok(series) => HttpResponse::Created().json(series),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al crear serie: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al crear serie"))
+        }
+    }
+}
+
+#[put("/{id}")]
+pub async fn update_series(
+    path: web::Path<i32>,
+    series: web::Json<UpdateSeriesDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let series_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match SeriesService::update_series(series_id, series.into_inner(), &mut conn) {
+        Ok(series) => HttpResponse::Ok().json(series),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al actualizar serie: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar serie"))
+        }
+    }
+}
+
+#[put("/{id}/posts")]
+pub async fn set_series_posts(
+    path: web::Path<i32>,
+    posts: web::Json<SetSeriesPostsDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let series_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match SeriesService::set_series_posts(series_id, posts.into_inner().post_ids, &mut conn) {
+        Ok(series) => HttpResponse::Ok().json(series),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al ordenar los posts de la serie: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al ordenar los posts de la serie"))
+        }
+    }
+}
+
+#[delete("/{id}")]
+pub async fn delete_series(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+    let series_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match SeriesService::delete_series(series_id, &mut conn) {
+        Ok(_) => HttpResponse::NoContent().finish(),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al eliminar serie: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar serie"))
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use actix_web::{web, HttpResponse, Responder, get, patch};
+use crate::db::DbPool;
+use crate::models::dto::UpdateSiteSettingsDto;
+use crate::services::SiteSettingsService;
+use crate::utils::error::{error_body, AppError};
+
+#[get("")]
+pub async fn get_settings(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match SiteSettingsService::get(&mut conn) {
+        Ok(settings) => HttpResponse::Ok().json(settings),
+        Err(e) => {
+            log::error!("Error al obtener los ajustes: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener los ajustes"))
+        }
+    }
+}
+
+#[patch("")]
+pub async fn update_settings(changes: web::Json<UpdateSiteSettingsDto>, pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match SiteSettingsService::update(changes.into_inner(), &mut conn) {
+        Ok(settings) => HttpResponse::Ok().json(settings),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al actualizar los ajustes: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar los ajustes"))
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use actix_web::{web, HttpResponse, Responder, get, post, put};
+use crate::db::DbPool;
+use crate::models::dto::{MergeTagDto, UpdateTagDto};
+use crate::services::TagService;
+use crate::utils::error::{error_body, AppError};
+
+#[get("")]
+pub async fn get_all_tags(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match TagService::get_all_tags(&mut conn) {
+        Ok(tags) => HttpResponse::Ok().json(tags),
+        Err(e) => {
+            log::error!("Error al obtener etiquetas: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener etiquetas"))
+        }
+    }
+}
+
+#[get("/cloud")]
+pub async fn get_tag_cloud(pool: web::Data<DbPool>) -> impl Responder {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match TagService::get_tag_cloud(&mut conn) {
+        Ok(cloud) => HttpResponse::Ok().json(cloud),
+        Err(e) => {
+            log::error!("Error al obtener la nube de etiquetas: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener la nube de etiquetas"))
+        }
+    }
+}
+
+#[get("/{slug}/posts")]
+pub async fn get_posts_by_tag(path: web::Path<String>, pool: web::Data<DbPool>) -> impl Responder {
+    let slug = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match TagService::get_posts_by_tag(&slug, &mut conn) {
+        Ok(posts) => HttpResponse::Ok().json(posts),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al obtener posts de la etiqueta: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al obtener posts de la etiqueta"))
+        }
+    }
+}
+
+#[put("/{id}")]
+pub async fn update_tag(
+    path: web::Path<i32>,
+    tag: web::Json<UpdateTagDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let tag_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match TagService::update_tag(tag_id, tag.into_inner(), &mut conn) {
+        Ok(tag) => HttpResponse::Ok().json(tag),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al actualizar etiqueta: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar etiqueta"))
+        }
+    }
+}
+
+#[post("/{id}/merge")]
+pub async fn merge_tag(
+    path: web::Path<i32>,
+    merge: web::Json<MergeTagDto>,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let tag_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    match TagService::merge_tags(tag_id, merge.target_id, &mut conn) {
+        Ok(tag) => HttpResponse::Ok().json(tag),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al fusionar etiquetas: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al fusionar etiquetas"))
+        }
+    }
+}
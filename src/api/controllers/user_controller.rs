@@ -1,21 +1,22 @@
 use actix_web::{web, HttpResponse, Responder, get, post, put, delete};
-use crate::config::database::DbPool;
-use crate::models::dto::{CreateUserDto, UpdateUserDto};
+use crate::auth::AuthenticatedUser;
+use crate::db::DbPool;
+use crate::models::dto::{CreateUserDto, SuspendUserDto, UpdateUserDto, UserSearchQuery};
 use crate::services::UserService;
-use crate::utils::error::AppError;
+use crate::utils::error::{error_body, AppError};
 
 #[get("")]
-pub async fn get_all_users(pool: web::Data<DbPool>) -> impl Responder {
+pub async fn get_all_users(query: web::Query<UserSearchQuery>, pool: web::Data<DbPool>) -> impl Responder {
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match UserService::get_all_users(&mut conn) {
+    match UserService::search_users(query.into_inner(), &mut conn) {
         Ok(users) => HttpResponse::Ok().json(users),
         Err(e) => {
             log::error!("Error al obtener usuarios: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener usuarios")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener usuarios"))
         }
     }
 }
@@ -25,15 +26,15 @@ pub async fn get_user_by_id(path: web::Path<i32>, pool: web::Data<DbPool>) -> im
     let user_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match UserService::get_user_by_id(user_id, &mut conn) {
         Ok(user) => HttpResponse::Ok().json(user),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al obtener usuario: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al obtener usuario")
+            HttpResponse::InternalServerError().json(error_body("Error al obtener usuario"))
         }
     }
 }
@@ -42,15 +43,15 @@ pub async fn get_user_by_id(path: web::Path<i32>, pool: web::Data<DbPool>) -> im
 pub async fn create_user(user: web::Json<CreateUserDto>, pool: web::Data<DbPool>) -> impl Responder {
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
     match UserService::create_user(user.into_inner(), &mut conn) {
         Ok(user) => HttpResponse::Created().json(user),
-        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(msg),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al crear usuario: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al crear usuario")
+            HttpResponse::InternalServerError().json(error_body("Error al crear usuario"))
         }
     }
 }
@@ -59,39 +60,107 @@ pub async fn create_user(user: web::Json<CreateUserDto>, pool: web::Data<DbPool>
 pub async fn update_user(
     path: web::Path<i32>,
     user: web::Json<UpdateUserDto>,
+    admin: AuthenticatedUser,
     pool: web::Data<DbPool>
 ) -> impl Responder {
     let user_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match UserService::update_user(user_id, user.into_inner(), &mut conn) {
+    let admin_id = admin.0.sub.parse::<i32>().unwrap_or(0);
+    if admin_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match UserService::update_user(user_id, user.into_inner(), admin_id, &mut conn) {
         Ok(user) => HttpResponse::Ok().json(user),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
-        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al actualizar usuario: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al actualizar usuario")
+            HttpResponse::InternalServerError().json(error_body("Error al actualizar usuario"))
+        }
+    }
+}
+
+#[post("/{id}/suspension")]
+pub async fn suspend_user(
+    path: web::Path<i32>,
+    suspension: web::Json<SuspendUserDto>,
+    admin: AuthenticatedUser,
+    pool: web::Data<DbPool>
+) -> impl Responder {
+    let user_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let admin_id = admin.0.sub.parse::<i32>().unwrap_or(0);
+    if admin_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match UserService::suspend_user(user_id, suspension.into_inner(), admin_id, &mut conn) {
+        Ok(user) => HttpResponse::Ok().json(user),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al suspender usuario: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al suspender usuario"))
+        }
+    }
+}
+
+#[delete("/{id}/suspension")]
+pub async fn unsuspend_user(path: web::Path<i32>, admin: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
+    let user_id = path.into_inner();
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
+    };
+
+    let admin_id = admin.0.sub.parse::<i32>().unwrap_or(0);
+    if admin_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match UserService::unsuspend_user(user_id, admin_id, &mut conn) {
+        Ok(user) => HttpResponse::Ok().json(user),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
+        Err(e) => {
+            log::error!("Error al levantar la suspensión: {:?}", e);
+            HttpResponse::InternalServerError().json(error_body("Error al levantar la suspensión"))
         }
     }
 }
 
 #[delete("/{id}")]
-pub async fn delete_user(path: web::Path<i32>, pool: web::Data<DbPool>) -> impl Responder {
+pub async fn delete_user(path: web::Path<i32>, admin: AuthenticatedUser, pool: web::Data<DbPool>) -> impl Responder {
     let user_id = path.into_inner();
     let mut conn = match pool.get() {
         Ok(conn) => conn,
-        Err(_) => return HttpResponse::InternalServerError().json("Error de conexión a la base de datos"),
+        Err(_) => return HttpResponse::InternalServerError().json(error_body("Error de conexión a la base de datos")),
     };
 
-    match UserService::delete_user(user_id, &mut conn) {
+    let admin_id = admin.0.sub.parse::<i32>().unwrap_or(0);
+    if admin_id == 0 {
+        return HttpResponse::BadRequest().json(error_body("ID de usuario inválido"));
+    }
+
+    match UserService::delete_user(user_id, admin_id, &mut conn) {
         Ok(_) => HttpResponse::NoContent().finish(),
-        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(msg),
+        Err(AppError::NotFound(msg)) => HttpResponse::NotFound().json(error_body(msg)),
+        Err(AppError::ValidationError(msg)) => HttpResponse::BadRequest().json(error_body(msg)),
+        Err(AppError::AuthorizationError(msg)) => HttpResponse::Forbidden().json(error_body(msg)),
         Err(e) => {
             log::error!("Error al eliminar usuario: {:?}", e);
-            HttpResponse::InternalServerError().json("Error al eliminar usuario")
+            HttpResponse::InternalServerError().json(error_body("Error al eliminar usuario"))
         }
     }
-} 
\ No newline at end of file
+}
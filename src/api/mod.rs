@@ -6,9 +6,43 @@ use crate::api::controllers::{
     category_controller,
     post_controller,
     comment_controller,
-    auth_controller
+    auth_controller,
+    health_controller,
+    tag_controller,
+    media_controller,
+    series_controller,
+    redirect_controller,
+    page_controller,
+    menu_controller,
+    settings_controller,
+    me_controller,
+    author_controller,
+    ban_controller,
+    invitation_controller
 };
 use crate::auth::{JwtAuth, OptionalJwtAuth};
+use crate::models::entities::{API_TOKEN_SCOPE_COMMENTS, API_TOKEN_SCOPE_MEDIA, API_TOKEN_SCOPE_POSTS};
+
+/// Configura las rutas de sondeo para balanceadores y orquestadores (fuera de `/api`)
+pub fn configure_system_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/health")
+            .service(health_controller::live)
+            .service(health_controller::ready)
+    )
+    .service(health_controller::version)
+    .service(health_controller::prometheus_metrics);
+}
+
+/// Configura la ruta que sirve los ficheros de la biblioteca de medios (fuera de `/api`)
+pub fn configure_media_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/media").service(media_controller::serve_file));
+}
+
+/// Servicio por defecto: aplica las redirecciones configuradas o responde 404
+pub fn configure_default_service(cfg: &mut web::ServiceConfig) {
+    cfg.default_service(web::to(redirect_controller::resolve_redirect));
+}
 
 /// Configura todas las rutas de la API
 pub fn configure_routes() -> Scope {
@@ -19,6 +53,7 @@ pub fn configure_routes() -> Scope {
                 // Rutas públicas
                 .service(auth_controller::login)
                 .service(auth_controller::register)
+                .service(auth_controller::confirm_email)
                 // Rutas protegidas
                 .service(
                     web::scope("/protected")
@@ -27,52 +62,173 @@ pub fn configure_routes() -> Scope {
                         .service(auth_controller::get_current_user)
                 )
         )
-        // Rutas protegidas para usuarios
+        // Datos y cuenta del usuario autenticado
         .service(
-            web::scope("/users")
+            web::scope("/me")
                 .wrap(JwtAuth::new()) // Requiere autenticación
-                .service(user_controller::get_all_users)
-                .service(user_controller::get_user_by_id)
-                .service(user_controller::create_user)
-                .service(user_controller::update_user)
-                .service(user_controller::delete_user)
+                .service(me_controller::get_my_profile)
+                .service(me_controller::update_my_profile)
+                .service(me_controller::change_my_password)
+                .service(me_controller::change_my_email)
+                .service(me_controller::export_my_data)
+                .service(me_controller::delete_my_account)
+                .service(me_controller::get_my_tokens)
+                .service(me_controller::create_my_token)
+                .service(me_controller::revoke_my_token)
+        )
+        .service(
+            web::scope("/authors")
+                .service(author_controller::get_author) // Público
         )
         .service(
             web::scope("/categories")
                 .service(category_controller::get_all_categories) // Público
+                .service(category_controller::get_category_tree) // Público
+                .service(category_controller::get_category_by_slug) // Público
+                .service(category_controller::get_category_posts) // Público
                 .service(category_controller::get_category_by_id) // Público
                 .service(
                     web::scope("")
-                        .wrap(JwtAuth::new()) // Requiere autenticación
+                        .wrap(JwtAuth::new().scope(API_TOKEN_SCOPE_POSTS)) // Requiere autenticación
                         .service(category_controller::create_category)
                         .service(category_controller::update_category)
                         .service(category_controller::delete_category)
+                        .service(category_controller::merge_category)
                 )
         )
         .service(
             web::scope("/posts")
+                .wrap(OptionalJwtAuth::new().scope(API_TOKEN_SCOPE_POSTS)) // Los borradores solo son visibles con sesión
                 .service(post_controller::get_all_posts) // Público
                 .service(post_controller::get_post_by_id) // Público
                 .service(post_controller::get_post_detail) // Público
                 .service(post_controller::get_post_by_slug) // Público
+                .service(media_controller::get_post_media) // Público
                 .service(
                     web::scope("")
-                        .wrap(JwtAuth::new()) // Requiere autenticación
+                        .wrap(JwtAuth::new().scope(API_TOKEN_SCOPE_POSTS)) // Requiere autenticación
                         .service(post_controller::create_post)
                         .service(post_controller::update_post)
                         .service(post_controller::delete_post)
+                        .service(media_controller::attach_media)
+                        .service(media_controller::detach_media)
+                )
+        )
+        .service(
+            web::scope("/series")
+                .service(series_controller::get_all_series) // Público
+                .service(series_controller::get_series_by_slug) // Público
+                .service(
+                    web::scope("")
+                        .wrap(JwtAuth::new().scope(API_TOKEN_SCOPE_POSTS)) // Requiere autenticación
+                        .service(series_controller::create_series)
+                        .service(series_controller::update_series)
+                        .service(series_controller::set_series_posts)
+                        .service(series_controller::delete_series)
                 )
         )
+        .service(
+            web::scope("/pages")
+                .service(page_controller::get_all_pages) // Público
+                .service(page_controller::get_page_tree) // Público
+                .service(page_controller::get_page_by_path) // Público
+                .service(page_controller::get_page_by_id) // Público
+                .service(
+                    web::scope("")
+                        .wrap(JwtAuth::new().roles(&["editor", "admin"]).scope(API_TOKEN_SCOPE_POSTS)) // Editores y administradores
+                        .service(page_controller::create_page)
+                        .service(page_controller::update_page)
+                        .service(page_controller::delete_page)
+                )
+        )
+        .service(
+            web::scope("/menus")
+                .service(menu_controller::get_menu_by_location) // Público
+                .service(
+                    web::scope("")
+                        .wrap(JwtAuth::new().role("admin")) // Solo administradores
+                        .service(menu_controller::get_all_menus)
+                        .service(menu_controller::create_menu)
+                        .service(menu_controller::update_menu)
+                        .service(menu_controller::set_menu_items)
+                        .service(menu_controller::delete_menu)
+                )
+        )
+        .service(
+            web::scope("/media")
+                .service(media_controller::get_media_by_id) // Público
+                .service(
+                    web::scope("")
+                        .wrap(JwtAuth::new().scope(API_TOKEN_SCOPE_MEDIA)) // Requiere autenticación
+                        .service(media_controller::get_all_media)
+                        .service(media_controller::upload_media)
+                        .service(media_controller::update_media)
+                        .service(media_controller::delete_media)
+                )
+        )
+        .service(
+            web::scope("/tags")
+                .service(tag_controller::get_all_tags) // Público
+                .service(tag_controller::get_tag_cloud) // Público
+                .service(tag_controller::get_posts_by_tag) // Público
+                .service(
+                    web::scope("")
+                        .wrap(JwtAuth::new().role("admin")) // Solo administradores
+                        .service(tag_controller::update_tag)
+                        .service(tag_controller::merge_tag)
+                )
+        )
+        .service(
+            web::scope("/redirects")
+                .wrap(JwtAuth::new().role("admin")) // Solo administradores
+                .service(redirect_controller::get_all_redirects)
+                .service(redirect_controller::create_redirect)
+                .service(redirect_controller::update_redirect)
+                .service(redirect_controller::delete_redirect)
+        )
+        .service(
+            web::scope("/settings")
+                .wrap(JwtAuth::new().role("admin")) // Solo administradores
+                .service(settings_controller::get_settings)
+                .service(settings_controller::update_settings)
+        )
+        .service(
+            web::scope("/admin")
+                .wrap(JwtAuth::new().role("admin")) // Solo administradores
+                .service(
+                    web::scope("/users")
+                        .service(user_controller::get_all_users)
+                        .service(user_controller::get_user_by_id)
+                        .service(user_controller::create_user)
+                        .service(user_controller::update_user)
+                        .service(user_controller::suspend_user)
+                        .service(user_controller::unsuspend_user)
+                        .service(user_controller::delete_user)
+                )
+                .service(
+                    web::scope("/bans")
+                        .service(ban_controller::get_all_bans)
+                        .service(ban_controller::create_ban)
+                        .service(ban_controller::delete_ban)
+                )
+                .service(
+                    web::scope("/invitations")
+                        .service(invitation_controller::get_all_invitations)
+                        .service(invitation_controller::create_invitation)
+                        .service(invitation_controller::delete_invitation)
+                )
+                .service(comment_controller::purge_commenter_email)
+        )
         .service(
             web::scope("/comments")
-                .wrap(OptionalJwtAuth::new()) // Middleware JWT opcional
+                .wrap(OptionalJwtAuth::new().scope(API_TOKEN_SCOPE_COMMENTS)) // Middleware JWT opcional
                 .service(comment_controller::get_all_comments) // Público
                 .service(comment_controller::get_comment_by_id) // Público
                 .service(comment_controller::get_comments_by_post) // Público
                 .service(comment_controller::create_comment) // Público - permitir comentarios anónimos
                 .service(
                     web::scope("")
-                        .wrap(JwtAuth::new()) // Requiere autenticación
+                        .wrap(JwtAuth::new().scope(API_TOKEN_SCOPE_COMMENTS)) // Requiere autenticación
                         .service(comment_controller::update_comment)
                         .service(comment_controller::delete_comment)
                 )
@@ -1,8 +1,11 @@
-use actix_web::{error::ErrorUnauthorized, Error as ActixError};
+use actix_web::Error as ActixError;
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
+use jsonwebtoken::errors::ErrorKind;
 use serde::{Deserialize, Serialize};
 use std::env;
+use crate::metrics;
+use crate::utils::error::AppError;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
@@ -11,6 +14,9 @@ pub struct Claims {
     pub iat: i64,           // Tiempo de emisión
     pub role: String,       // Rol del usuario (admin, user, etc.)
     pub username: String,   // Nombre de usuario
+    /// Permisos de un token personal; `None` en las sesiones, que no tienen límites
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<String>>,
 }
 
 impl Claims {
@@ -22,6 +28,7 @@ impl Claims {
             iat: now.timestamp(),
             role,
             username,
+            scopes: None,
         }
     }
 }
@@ -35,7 +42,7 @@ pub fn create_token(claims: Claims) -> Result<String, ActixError> {
     )
     .map_err(|e| {
         log::error!("Error al crear token JWT: {}", e);
-        ErrorUnauthorized("Error al crear token")
+        ActixError::from(AppError::AuthenticationError("Error al crear token".to_string()))
     })
 }
 
@@ -48,10 +55,21 @@ pub fn validate_token(token: &str) -> Result<TokenData<Claims>, ActixError> {
     )
     .map_err(|e| {
         log::error!("Error al validar token JWT: {}", e);
-        ErrorUnauthorized("Token inválido o expirado")
+        metrics::record_jwt_failure(failure_reason(e.kind()));
+        ActixError::from(AppError::AuthenticationError("Token inválido o expirado".to_string()))
     })
 }
 
+/// Clasifica el error de validación para la métrica de tokens rechazados
+fn failure_reason(kind: &ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::ExpiredSignature => "expired",
+        ErrorKind::InvalidSignature => "invalid_signature",
+        ErrorKind::InvalidToken | ErrorKind::Base64(_) | ErrorKind::Json(_) | ErrorKind::Utf8(_) => "malformed",
+        _ => "other",
+    }
+}
+
 fn get_secret() -> String {
     env::var("JWT_SECRET").unwrap_or_else(|_| {
         log::warn!("JWT_SECRET no está configurada, usando valor por defecto (inseguro)");
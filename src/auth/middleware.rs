@@ -1,7 +1,7 @@
 use actix_web::{
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
-    error::ErrorUnauthorized,
     http::header::{self},
+    web,
     Error as ActixError,
     HttpMessage,
 };
@@ -9,18 +9,37 @@ use futures::future::{ready, LocalBoxFuture, Ready};
 use std::rc::Rc;
 
 use crate::auth::jwt::{validate_token, Claims};
+use crate::db::DbPool;
+use crate::services::{ApiTokenService, UserService};
+use crate::utils::error::{AppError, AppResult};
 
+/// Exige una sesión (`Authorization: Bearer {jwt}`) o un token personal
+/// (`Authorization: Token {token}`). Los tokens personales solo se aceptan si el
+/// ámbito declara con `scope` el permiso que necesitan.
 pub struct JwtAuth {
-    pub required_role: Option<String>,
+    /// Roles admitidos; vacío si basta con estar autenticado
+    pub required_roles: Vec<String>,
+    pub required_scope: Option<String>,
 }
 
 impl JwtAuth {
     pub fn new() -> Self {
-        JwtAuth { required_role: None }
+        JwtAuth { required_roles: Vec::new(), required_scope: None }
     }
 
-    pub fn role(mut self, role: &str) -> Self {
-        self.required_role = Some(role.to_string());
+    pub fn role(self, role: &str) -> Self {
+        self.roles(&[role])
+    }
+
+    /// Admite a los usuarios con cualquiera de los roles indicados
+    pub fn roles(mut self, roles: &[&str]) -> Self {
+        self.required_roles = roles.iter().map(|role| role.to_string()).collect();
+        self
+    }
+
+    /// Permiso con el que un token personal puede usar estas rutas
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.required_scope = Some(scope.to_string());
         self
     }
 }
@@ -40,14 +59,16 @@ where
     fn new_transform(&self, service: S) -> Self::Future {
         ready(Ok(JwtAuthMiddleware {
             service: Rc::new(service),
-            required_role: self.required_role.clone(),
+            required_roles: self.required_roles.clone(),
+            required_scope: self.required_scope.clone(),
         }))
     }
 }
 
 pub struct JwtAuthMiddleware<S> {
     service: Rc<S>,
-    required_role: Option<String>,
+    required_roles: Vec<String>,
+    required_scope: Option<String>,
 }
 
 impl<S, B> Service<ServiceRequest> for JwtAuthMiddleware<S>
@@ -64,36 +85,31 @@ where
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let service = Rc::clone(&self.service);
-        let required_role = self.required_role.clone();
+        let required_roles = self.required_roles.clone();
+        let required_scope = self.required_scope.clone();
 
         Box::pin(async move {
-            // Extraer el token del encabezado Authorization
-            let auth_header = req
-                .headers()
-                .get(header::AUTHORIZATION)
-                .and_then(|h| h.to_str().ok());
-
-            let token = match auth_header {
-                Some(auth) if auth.starts_with("Bearer ") => {
-                    let token = auth.trim_start_matches("Bearer ").trim();
-                    token
-                }
-                _ => {
-                    return Err(ErrorUnauthorized("Token no proporcionado"));
+            // Extraer el token del encabezado Authorization y validarlo. La cuenta
+            // se comprueba en cada petición: un token sin caducar no sirve si se ha
+            // suspendido, y el rol es el actual y no el de cuando se emitió.
+            let claims = match credentials(&req) {
+                Some(Credentials::Session(token)) => session_claims(&req, validate_token(&token)?.claims).await?,
+                Some(Credentials::ApiToken(token)) => api_token_claims(&req, token).await?,
+                None => {
+                    return Err(AppError::AuthenticationError("Token no proporcionado".to_string()).into());
                 }
             };
 
-            // Validar el token
-            let token_data = validate_token(token)?;
-            let claims = token_data.claims;
+            check_scope(&claims, required_scope.as_deref())?;
 
             // Verificar el rol si es necesario
-            if let Some(role) = required_role {
-                if claims.role != role {
-                    return Err(ErrorUnauthorized("Rol insuficiente"));
-                }
+            if !required_roles.is_empty() && !required_roles.contains(&claims.role) {
+                return Err(AppError::AuthorizationError("Rol insuficiente".to_string()).into());
             }
 
+            // Asociar el usuario al span de la petición
+            tracing::Span::current().record("user_id", claims.sub.as_str());
+
             // Añadir los claims al request para que estén disponibles en los handlers
             req.extensions_mut().insert(claims);
 
@@ -115,7 +131,7 @@ impl FromRequest for AuthenticatedUser {
         let claims = req.extensions().get::<Claims>().cloned();
         match claims {
             Some(claims) => ready(Ok(AuthenticatedUser(claims))),
-            None => ready(Err(ErrorUnauthorized("Usuario no autenticado"))),
+            None => ready(Err(AppError::AuthenticationError("Usuario no autenticado".to_string()).into())),
         }
     }
 }
@@ -139,11 +155,19 @@ use actix_web::{FromRequest, HttpRequest};
 use actix_web::dev::Payload;
 
 // Middleware para autenticación JWT opcional
-pub struct OptionalJwtAuth;
+pub struct OptionalJwtAuth {
+    pub required_scope: Option<String>,
+}
 
 impl OptionalJwtAuth {
     pub fn new() -> Self {
-        OptionalJwtAuth
+        OptionalJwtAuth { required_scope: None }
+    }
+
+    /// Permiso con el que un token personal puede usar estas rutas
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.required_scope = Some(scope.to_string());
+        self
     }
 }
 
@@ -162,12 +186,14 @@ where
     fn new_transform(&self, service: S) -> Self::Future {
         ready(Ok(OptionalJwtAuthMiddleware {
             service: Rc::new(service),
+            required_scope: self.required_scope.clone(),
         }))
     }
 }
 
 pub struct OptionalJwtAuthMiddleware<S> {
     service: Rc<S>,
+    required_scope: Option<String>,
 }
 
 impl<S, B> Service<ServiceRequest> for OptionalJwtAuthMiddleware<S>
@@ -184,29 +210,98 @@ where
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let service = Rc::clone(&self.service);
+        let required_scope = self.required_scope.clone();
 
         Box::pin(async move {
-            // Extraer el token del encabezado Authorization
-            let auth_header = req
-                .headers()
-                .get(header::AUTHORIZATION)
-                .and_then(|h| h.to_str().ok());
-
-            // Si hay un token, intentar validarlo
-            if let Some(auth) = auth_header {
-                if auth.starts_with("Bearer ") {
-                    let token = auth.trim_start_matches("Bearer ").trim();
-                    
-                    // Intentar validar el token
-                    if let Ok(token_data) = validate_token(token) {
-                        // Añadir los claims al request para que estén disponibles en los handlers
-                        req.extensions_mut().insert(token_data.claims);
-                    }
-                }
+            // Si hay un token, intentar validarlo. Un JWT inválido o caducado se
+            // ignora, pero una cuenta suspendida no puede seguir como anónima con
+            // su propio token, ni un token personal revocado pasar desapercibido.
+            let claims = match credentials(&req) {
+                Some(Credentials::Session(token)) => match validate_token(&token) {
+                    Ok(token_data) => Some(session_claims(&req, token_data.claims).await?),
+                    Err(_) => None,
+                },
+                Some(Credentials::ApiToken(token)) => Some(api_token_claims(&req, token).await?),
+                None => None,
+            };
+
+            if let Some(claims) = claims {
+                check_scope(&claims, required_scope.as_deref())?;
+                tracing::Span::current().record("user_id", claims.sub.as_str());
+                // Añadir los claims al request para que estén disponibles en los handlers
+                req.extensions_mut().insert(claims);
             }
 
             // Continuar con la cadena de middleware, independientemente de si hay token o no
             service.call(req).await
         })
     }
-} 
\ No newline at end of file
+} 
+/// Credenciales de la cabecera `Authorization`
+enum Credentials {
+    /// JWT de una sesión iniciada con usuario y contraseña
+    Session(String),
+    /// Token personal de acceso
+    ApiToken(String),
+}
+
+fn credentials(req: &ServiceRequest) -> Option<Credentials> {
+    let auth = req.headers().get(header::AUTHORIZATION)?.to_str().ok()?;
+    if let Some(token) = auth.strip_prefix("Bearer ") {
+        Some(Credentials::Session(token.trim().to_string()))
+    } else {
+        auth.strip_prefix("Token ").map(|token| Credentials::ApiToken(token.trim().to_string()))
+    }
+}
+
+/// Claims del JWT con el rol y el nombre actuales, si la cuenta sigue existiendo y
+/// no está suspendida
+async fn session_claims(req: &ServiceRequest, mut claims: Claims) -> AppResult<Claims> {
+    let user_id = claims
+        .sub
+        .parse::<i32>()
+        .map_err(|_| AppError::AuthenticationError("Token inválido".to_string()))?;
+    let user = with_connection(req, move |conn| UserService::check_active(user_id, conn)).await?;
+    claims.role = user.role;
+    claims.username = user.username;
+    Ok(claims)
+}
+
+async fn api_token_claims(req: &ServiceRequest, token: String) -> AppResult<Claims> {
+    with_connection(req, move |conn| ApiTokenService::authenticate(&token, conn)).await
+}
+
+/// Las sesiones no tienen límites; un token personal necesita el permiso que
+/// declara el ámbito, y sin él no puede usar las rutas
+fn check_scope(claims: &Claims, required_scope: Option<&str>) -> Result<(), ActixError> {
+    let scopes = match claims.scopes {
+        Some(ref scopes) => scopes,
+        None => return Ok(()),
+    };
+    match required_scope {
+        Some(scope) if scopes.iter().any(|s| s == scope) => Ok(()),
+        Some(scope) => Err(AppError::AuthorizationError(format!("El token no tiene el permiso '{}'", scope)).into()),
+        None => Err(AppError::AuthorizationError("Estas rutas no admiten tokens personales".to_string()).into()),
+    }
+}
+
+/// Ejecuta `f` con una conexión del pool, fuera del hilo de la petición
+async fn with_connection<T, F>(req: &ServiceRequest, f: F) -> AppResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut crate::db::DbConnection) -> AppResult<T> + Send + 'static,
+{
+    let pool = req
+        .app_data::<web::Data<DbPool>>()
+        .cloned()
+        .ok_or_else(|| AppError::InternalServerError("Pool de base de datos no configurado".to_string()))?;
+
+    web::block(move || {
+        let mut conn = pool
+            .get()
+            .map_err(|e| AppError::InternalServerError(format!("Error de conexión a la base de datos: {}", e)))?;
+        f(&mut conn)
+    })
+    .await
+    .map_err(|e| AppError::InternalServerError(e.to_string()))?
+}
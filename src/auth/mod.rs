@@ -4,4 +4,4 @@ pub mod password;
 
 pub use jwt::{Claims, create_token};
 pub use middleware::{JwtAuth, AuthenticatedUser, OptionalAuthenticatedUser, OptionalJwtAuth};
-pub use password::{hash_password, verify_password}; 
\ No newline at end of file
+pub use password::{hash_password, hash_token, verify_password}; 
\ No newline at end of file
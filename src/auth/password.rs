@@ -1,4 +1,5 @@
 use bcrypt::{hash, verify, DEFAULT_COST};
+use sha2::{Digest, Sha256};
 use crate::utils::error::{AppError, AppResult};
 
 pub fn hash_password(password: &str) -> AppResult<String> {
@@ -13,4 +14,10 @@ pub fn verify_password(password: &str, hash: &str) -> AppResult<bool> {
         log::error!("Error al verificar contraseña: {}", e);
         AppError::InternalServerError("Error al verificar la contraseña".to_string())
     })
-} 
\ No newline at end of file
+}
+
+/// SHA-256 de un código de un solo uso generado por el servidor. Al ser aleatorio
+/// y largo no necesita bcrypt, y así puede buscarse directamente en la base de datos.
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,166 @@
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use crate::db::DbPool;
+use crate::services::BackupService;
+use crate::utils::backup::{self, ArchiveFormat};
+
+const BACKUP_USAGE: &str = "Uso: blog-rust backup <fichero> [--format json|ndjson] [--include-password-hashes]";
+const RESTORE_USAGE: &str = "Uso: blog-rust restore <fichero> [--dry-run]";
+
+/// `backup`: escribe una copia del sitio. Sin `--format`, un fichero `.ndjson`
+/// se escribe en NDJSON y cualquier otro en JSON.
+pub fn backup(args: &[String], pool: &DbPool) -> i32 {
+    let mut path = None;
+    let mut format = None;
+    let mut include_password_hashes = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next().map(String::as_str) {
+                    Some("json") => Some(ArchiveFormat::Json),
+                    Some("ndjson") => Some(ArchiveFormat::Ndjson),
+                    _ => {
+                        eprintln!("Formato no válido; usa json o ndjson\n{}", BACKUP_USAGE);
+                        return 2;
+                    }
+                }
+            }
+            "--include-password-hashes" => include_password_hashes = true,
+            flag if flag.starts_with("--") => {
+                eprintln!("Opción desconocida: '{}'\n{}", flag, BACKUP_USAGE);
+                return 2;
+            }
+            file if path.is_none() => path = Some(file),
+            _ => {
+                eprintln!("{}", BACKUP_USAGE);
+                return 2;
+            }
+        }
+    }
+    let path = match path {
+        Some(path) => path,
+        None => {
+            eprintln!("{}", BACKUP_USAGE);
+            return 2;
+        }
+    };
+    let format = format.unwrap_or(if path.ends_with(".ndjson") { ArchiveFormat::Ndjson } else { ArchiveFormat::Json });
+
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("No se pudo conectar a la base de datos: {}", e);
+            return 2;
+        }
+    };
+    let archive = match BackupService::export(include_password_hashes, &mut conn) {
+        Ok(archive) => archive,
+        Err(e) => {
+            eprintln!("No se pudo leer el contenido del sitio: {}", e);
+            return 2;
+        }
+    };
+
+    let written = File::create(path).map_err(|e| e.to_string()).and_then(|file| {
+        let mut out = BufWriter::new(file);
+        backup::write(&archive, format, &mut out)?;
+        out.flush().map_err(|e| e.to_string())
+    });
+    if let Err(e) = written {
+        eprintln!("No se pudo escribir '{}': {}", path, e);
+        return 2;
+    }
+
+    println!(
+        "Copia guardada en {}: {} usuarios, {} categorías, {} posts, {} comentarios{}",
+        path,
+        archive.users.len(),
+        archive.categories.len(),
+        archive.posts.len(),
+        archive.comments.len(),
+        if include_password_hashes { " (con contraseñas)" } else { "" }
+    );
+    0
+}
+
+/// `restore`: restaura una copia en una única transacción; con `--dry-run` solo
+/// comprueba que podría restaurarse
+pub fn restore(args: &[String], pool: &DbPool) -> i32 {
+    let mut path = None;
+    let mut dry_run = false;
+    for arg in args {
+        match arg.as_str() {
+            "--dry-run" => dry_run = true,
+            flag if flag.starts_with("--") => {
+                eprintln!("Opción desconocida: '{}'\n{}", flag, RESTORE_USAGE);
+                return 2;
+            }
+            file if path.is_none() => path = Some(file),
+            _ => {
+                eprintln!("{}", RESTORE_USAGE);
+                return 2;
+            }
+        }
+    }
+    let path = match path {
+        Some(path) => path,
+        None => {
+            eprintln!("{}", RESTORE_USAGE);
+            return 2;
+        }
+    };
+
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("No se pudo leer '{}': {}", path, e);
+            return 2;
+        }
+    };
+    let archive = match backup::read(&content) {
+        Ok(archive) => archive,
+        Err(e) => {
+            eprintln!("No se pudo analizar '{}': {}", path, e);
+            return 2;
+        }
+    };
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("No se pudo conectar a la base de datos: {}", e);
+            return 2;
+        }
+    };
+
+    println!(
+        "{} la copia del {} (versión {} del formato)",
+        if dry_run { "Comprobando" } else { "Restaurando" },
+        archive.header.created_at.format("%Y-%m-%d %H:%M:%S"),
+        archive.header.version
+    );
+
+    let report = match BackupService::restore(&archive, dry_run, &mut conn) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("ERROR: {}", e);
+            eprintln!("No se ha restaurado nada");
+            return 1;
+        }
+    };
+
+    println!("Usuarios: {} nuevos, {} ya existentes", report.users, report.existing_users);
+    println!("Categorías: {}", report.categories);
+    println!("Posts: {} ({} asignaciones de categoría)", report.posts, report.post_categories);
+    println!("Comentarios: {}", report.comments);
+    if report.users_without_password > 0 {
+        println!(
+            "{} usuarios se han creado con una contraseña aleatoria; un administrador debe cambiarla para que puedan entrar",
+            report.users_without_password
+        );
+    }
+    if dry_run {
+        println!("Simulación: la copia es válida y no se ha modificado la base de datos");
+    }
+    0
+}
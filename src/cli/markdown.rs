@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::db::DbPool;
+use crate::services::MarkdownService;
+use crate::utils::front_matter::FrontMatterFormat;
+
+const IMPORT_USAGE: &str = "Uso: blog-rust import-markdown <directorio>";
+const EXPORT_USAGE: &str = "Uso: blog-rust export-markdown <directorio> [--format yaml|toml]";
+
+/// `import-markdown`: crea un post por cada fichero `.md` del directorio. Un
+/// fichero con errores se notifica y la importación continúa con el siguiente.
+pub fn import(args: &[String], pool: &DbPool) -> i32 {
+    let dir = match args {
+        [dir] if !dir.starts_with("--") => Path::new(dir),
+        _ => {
+            eprintln!("{}", IMPORT_USAGE);
+            return 2;
+        }
+    };
+
+    let mut files: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && matches!(path.extension().and_then(|e| e.to_str()), Some("md" | "markdown")))
+            .collect(),
+        Err(e) => {
+            eprintln!("No se pudo leer el directorio '{}': {}", dir.display(), e);
+            return 2;
+        }
+    };
+    files.sort();
+
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("No se pudo conectar a la base de datos: {}", e);
+            return 2;
+        }
+    };
+
+    println!("Importando {} ficheros de {}", files.len(), dir.display());
+
+    let mut imported = 0;
+    let mut failed = 0;
+    for (n, path) in files.iter().enumerate() {
+        let file_name = path.file_name().and_then(|f| f.to_str()).unwrap_or_default();
+        let result = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| MarkdownService::import_file(file_name, &content, &mut conn).map_err(|e| e.to_string()));
+        match result {
+            Ok(post) => {
+                imported += 1;
+                println!("[{}/{}] {} → /posts/{} ({})", n + 1, files.len(), file_name, post.slug, post.status);
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("ERROR: {}: {}", file_name, e);
+            }
+        }
+    }
+
+    println!();
+    println!("Posts importados: {}, errores: {}", imported, failed);
+
+    if failed > 0 {
+        1
+    } else {
+        0
+    }
+}
+
+/// `export-markdown`: escribe cada post en `<directorio>/AAAA-MM-DD-slug.md`,
+/// sobrescribiendo los ficheros que ya existan
+pub fn export(args: &[String], pool: &DbPool) -> i32 {
+    let mut dir = None;
+    let mut format = FrontMatterFormat::Yaml;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next().map(String::as_str) {
+                    Some("yaml") => FrontMatterFormat::Yaml,
+                    Some("toml") => FrontMatterFormat::Toml,
+                    _ => {
+                        eprintln!("Formato no válido; usa yaml o toml\n{}", EXPORT_USAGE);
+                        return 2;
+                    }
+                }
+            }
+            flag if flag.starts_with("--") => {
+                eprintln!("Opción desconocida: '{}'\n{}", flag, EXPORT_USAGE);
+                return 2;
+            }
+            path if dir.is_none() => dir = Some(Path::new(path)),
+            _ => {
+                eprintln!("{}", EXPORT_USAGE);
+                return 2;
+            }
+        }
+    }
+    let dir = match dir {
+        Some(dir) => dir,
+        None => {
+            eprintln!("{}", EXPORT_USAGE);
+            return 2;
+        }
+    };
+
+    if let Err(e) = fs::create_dir_all(dir) {
+        eprintln!("No se pudo crear el directorio '{}': {}", dir.display(), e);
+        return 2;
+    }
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("No se pudo conectar a la base de datos: {}", e);
+            return 2;
+        }
+    };
+    let files = match MarkdownService::export_all(format, &mut conn) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("No se pudieron exportar los posts: {}", e);
+            return 2;
+        }
+    };
+
+    let mut failed = 0;
+    for file in &files {
+        if let Err(e) = fs::write(dir.join(&file.file_name), &file.content) {
+            failed += 1;
+            eprintln!("ERROR: {}: {}", file.file_name, e);
+        }
+    }
+    println!("Posts exportados a {}: {}, errores: {}", dir.display(), files.len() - failed, failed);
+
+    if failed > 0 {
+        1
+    } else {
+        0
+    }
+}
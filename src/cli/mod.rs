@@ -0,0 +1,41 @@
+//! Subcomandos de administración: `blog-rust <comando> [argumentos]`.
+//!
+//! Se ejecutan en lugar del servidor, después de aplicar las migraciones (si
+//! están habilitadas) y de preparar el almacenamiento. El proceso termina con
+//! 0 si todo fue bien, 1 si hubo errores en alguna entidad y 2 si el comando no
+//! pudo ejecutarse.
+
+mod backup;
+mod markdown;
+mod wordpress;
+
+use std::process;
+use crate::db::DbPool;
+
+const USAGE: &str = "Comandos disponibles:
+  import-wordpress <fichero.xml> [--skip-attachments]   Importa una exportación WXR de WordPress
+  import-markdown <directorio>                          Importa los ficheros .md con cabecera YAML o TOML
+  export-markdown <directorio> [--format yaml|toml]     Exporta todos los posts como ficheros .md
+  backup <fichero> [--format json|ndjson] [--include-password-hashes]
+                                                        Guarda una copia de usuarios, categorías, posts y comentarios
+  restore <fichero> [--dry-run]                         Restaura una copia; con --dry-run solo la comprueba";
+
+/// Ejecuta el subcomando y termina el proceso
+pub fn run(command: &str, args: &[String], pool: &DbPool) -> ! {
+    let code = match command {
+        "import-wordpress" => wordpress::import(args, pool),
+        "import-markdown" => markdown::import(args, pool),
+        "export-markdown" => markdown::export(args, pool),
+        "backup" => backup::backup(args, pool),
+        "restore" => backup::restore(args, pool),
+        "help" | "--help" => {
+            println!("{}", USAGE);
+            0
+        }
+        _ => {
+            eprintln!("Comando desconocido: '{}'\n{}", command, USAGE);
+            2
+        }
+    };
+    process::exit(code)
+}
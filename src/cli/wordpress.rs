@@ -0,0 +1,90 @@
+use std::fs;
+use crate::db::DbPool;
+use crate::services::WordPressImportService;
+use crate::services::wordpress_import_service::{ImportCount, ImportEvent, WordPressImportOptions};
+use crate::utils::wxr;
+
+const USAGE: &str = "Uso: blog-rust import-wordpress <fichero.xml> [--skip-attachments]";
+
+/// `import-wordpress`: importa un fichero WXR mostrando el progreso en la salida
+/// estándar y los errores en la de errores
+pub fn import(args: &[String], pool: &DbPool) -> i32 {
+    let mut path = None;
+    let mut options = WordPressImportOptions::default();
+    for arg in args {
+        match arg.as_str() {
+            "--skip-attachments" => options.skip_attachments = true,
+            flag if flag.starts_with("--") => {
+                eprintln!("Opción desconocida: '{}'\n{}", flag, USAGE);
+                return 2;
+            }
+            file if path.is_none() => path = Some(file),
+            _ => {
+                eprintln!("{}", USAGE);
+                return 2;
+            }
+        }
+    }
+    let path = match path {
+        Some(path) => path,
+        None => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    };
+
+    let xml = match fs::read_to_string(path) {
+        Ok(xml) => xml,
+        Err(e) => {
+            eprintln!("No se pudo leer '{}': {}", path, e);
+            return 2;
+        }
+    };
+    let document = match wxr::parse(&xml) {
+        Ok(document) => document,
+        Err(e) => {
+            eprintln!("No se pudo analizar '{}': {}", path, e);
+            return 2;
+        }
+    };
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("No se pudo conectar a la base de datos: {}", e);
+            return 2;
+        }
+    };
+
+    println!(
+        "Importando {} ({} autores, {} categorías, {} entradas)",
+        document.site_url,
+        document.authors.len(),
+        document.categories.len(),
+        document.items.len()
+    );
+
+    let report = WordPressImportService::import(&document, &options, &mut conn, &mut |event| match event {
+        ImportEvent::Progress(message) => println!("{}", message),
+        ImportEvent::Error(message) => eprintln!("ERROR: {}", message),
+    });
+
+    println!();
+    println!("{:<12} {:>10} {:>10} {:>10}", "", "nuevos", "existentes", "errores");
+    let rows: [(&str, &ImportCount); 5] = [
+        ("Autores", &report.authors),
+        ("Categorías", &report.categories),
+        ("Adjuntos", &report.attachments),
+        ("Posts", &report.posts),
+        ("Comentarios", &report.comments),
+    ];
+    for (label, count) in rows {
+        println!("{:<12} {:>10} {:>10} {:>10}", label, count.imported, count.existing, count.failed);
+    }
+    println!("Omitidos (páginas, papelera, spam...): {}", report.ignored);
+
+    if report.failed() > 0 {
+        1
+    } else {
+        0
+    }
+}
@@ -1,22 +0,0 @@
-use diesel::pg::PgConnection;
-use diesel::r2d2::{self, ConnectionManager};
-use dotenv::dotenv;
-use std::env;
-
-pub type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
-pub type DbConnection = r2d2::PooledConnection<ConnectionManager<PgConnection>>;
-
-pub fn establish_connection_pool() -> DbPool {
-    dotenv().ok();
-
-    let database_url = env::var("DATABASE_URL")
-        .expect("DATABASE_URL debe estar configurada en el archivo .env");
-    
-    let manager = ConnectionManager::<PgConnection>::new(database_url);
-    r2d2::Pool::builder()
-        .build(manager)
-        .expect("No se pudo crear el pool de conexiones")
-}
-
-// Eliminamos esta función ya que no es necesaria y causa problemas de tipo
-// La conexión se puede obtener directamente con pool.get() 
\ No newline at end of file
@@ -1,15 +1,70 @@
-pub mod database;
-
 use dotenv::dotenv;
 use std::env;
+use std::net::IpAddr;
+use crate::telemetry;
 
 pub fn init() {
     dotenv().ok();
     
-    // Inicializar el logger
-    if env::var("RUST_LOG").is_err() {
-        env::set_var("RUST_LOG", "info");
-    }
-    env_logger::init();
+    // Inicializar el logging estructurado (RUST_LOG=info por defecto)
+    telemetry::init();
+}
+
+/// Indica si las migraciones pendientes deben aplicarse al arrancar (`RUN_MIGRATIONS=true`)
+pub fn run_migrations_on_startup() -> bool {
+    env_flag("RUN_MIGRATIONS")
+}
+
+/// Lee una variable de entorno booleana; cualquier valor distinto de true/1/yes se considera falso
+fn env_flag(name: &str) -> bool {
+    env::var(name)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1" | "yes"))
+        .unwrap_or(false)
+}
+
+/// Tamaño máximo de un fichero subido (`MEDIA_MAX_UPLOAD_BYTES`, 10 MiB por defecto)
+pub fn media_max_upload_bytes() -> usize {
+    env_number("MEDIA_MAX_UPLOAD_BYTES").unwrap_or(10 * 1024 * 1024)
+}
+
+/// Espacio máximo que puede ocupar la biblioteca de medios de cada usuario
+/// (`MEDIA_USER_QUOTA_BYTES`, 100 MiB por defecto; 0 desactiva la cuota)
+pub fn media_user_quota_bytes() -> i64 {
+    env_number("MEDIA_USER_QUOTA_BYTES").unwrap_or(100 * 1024 * 1024)
 }
 
+/// Lee una variable de entorno numérica; se ignora si no se puede interpretar
+fn env_number<T: std::str::FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok().and_then(|v| v.trim().parse().ok())
+}
+
+/// Longitud máxima de los slugs generados (`SLUG_MAX_LENGTH`, 80 por defecto; 0 sin límite)
+pub fn slug_max_length() -> usize {
+    env_number("SLUG_MAX_LENGTH").unwrap_or(80)
+}
+
+/// Palabras que se omiten al generar slugs (`SLUG_STOP_WORDS`, separadas por comas;
+/// ninguna por defecto), p. ej. `el,la,de,the,a,of`
+pub fn slug_stop_words() -> Vec<String> {
+    env::var("SLUG_STOP_WORDS")
+        .map(|v| v.split(',').map(|w| w.trim().to_string()).filter(|w| !w.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Nombre de usuario de la cuenta que recibe los posts de las cuentas eliminadas
+/// (`DELETED_ACCOUNT_POSTS_OWNER`); sin ella, los posts se quedan sin autor
+pub fn deleted_account_posts_owner() -> Option<String> {
+    env::var("DELETED_ACCOUNT_POSTS_OWNER")
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Proxies inversos de confianza (`TRUSTED_PROXIES`, IPs separadas por comas;
+/// ninguno por defecto). Solo a las peticiones que llegan desde ellos se les
+/// toma la IP del cliente de `X-Forwarded-For`.
+pub fn trusted_proxies() -> Vec<IpAddr> {
+    env::var("TRUSTED_PROXIES")
+        .map(|v| v.split(',').filter_map(|ip| ip.trim().parse().ok()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,40 @@
+use diesel::pg::PgConnection;
+use diesel::sql_types::BigInt;
+use diesel::RunQueryDsl;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use crate::utils::error::{AppError, AppResult};
+
+/// Migraciones SQL de `migrations/` embebidas en el binario
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// Clave del advisory lock que serializa las migraciones entre instancias
+const MIGRATIONS_LOCK_KEY: i64 = 0x626c_6f67_5f6d_6967; // "blog_mig"
+
+/// Devuelve las versiones de las migraciones que aún no se han aplicado
+pub fn pending_migrations(conn: &mut PgConnection) -> AppResult<Vec<String>> {
+    let pending = conn.pending_migrations(MIGRATIONS).map_err(|e| {
+        AppError::InternalServerError(format!("No se pudieron obtener las migraciones pendientes: {}", e))
+    })?;
+
+    Ok(pending.iter().map(|m| m.name().to_string()).collect())
+}
+
+/// Aplica las migraciones pendientes bajo un advisory lock de PostgreSQL,
+/// de modo que varias instancias arrancando a la vez no compitan entre sí
+pub fn run_pending_migrations(conn: &mut PgConnection) -> AppResult<Vec<String>> {
+    diesel::sql_query("SELECT pg_advisory_lock($1)")
+        .bind::<BigInt, _>(MIGRATIONS_LOCK_KEY)
+        .execute(conn)?;
+
+    let result = conn
+        .run_pending_migrations(MIGRATIONS)
+        .map(|applied| applied.iter().map(|v| v.to_string()).collect())
+        .map_err(|e| AppError::InternalServerError(format!("Error al aplicar las migraciones: {}", e)));
+
+    // Liberar el lock aunque la migración haya fallado
+    diesel::sql_query("SELECT pg_advisory_unlock($1)")
+        .bind::<BigInt, _>(MIGRATIONS_LOCK_KEY)
+        .execute(conn)?;
+
+    result
+}
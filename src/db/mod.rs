@@ -1,16 +1,94 @@
 pub mod schema;
+pub mod migrations;
 
 use diesel::pg::PgConnection;
-use diesel::r2d2::{self, ConnectionManager};
-use crate::config::database::DbPool;
+use diesel::r2d2::{self, ConnectionManager, CustomizeConnection};
+use diesel::define_sql_function;
+use diesel::sql_types::{Nullable, Text};
+use diesel::RunQueryDsl;
+use std::env;
+use std::time::Duration;
+use crate::utils::error::{AppError, AppResult};
+
+pub type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
+pub type DbConnection = r2d2::PooledConnection<ConnectionManager<PgConnection>>;
+
+// `lower()` de PostgreSQL, para comparar emails sin distinguir mayúsculas
+define_sql_function!(fn lower(x: Nullable<Text>) -> Nullable<Text>);
+
+/// Ajustes de sesión que se aplican a cada conexión nueva del pool
+#[derive(Debug, Clone)]
+pub struct ConnectionOptions {
+    /// Tiempo máximo por sentencia (`statement_timeout`), en milisegundos
+    pub statement_timeout_ms: Option<u64>,
+    /// Nombre con el que la conexión aparece en `pg_stat_activity`
+    pub application_name: String,
+    /// `search_path` de la sesión; si es `None` se usa el del servidor
+    pub search_path: Option<String>,
+}
+
+impl ConnectionOptions {
+    /// Lee las opciones desde `DB_STATEMENT_TIMEOUT_MS`, `DB_APPLICATION_NAME` y `DB_SEARCH_PATH`
+    pub fn from_env() -> Self {
+        ConnectionOptions {
+            statement_timeout_ms: env::var("DB_STATEMENT_TIMEOUT_MS").ok().and_then(|v| v.parse().ok()),
+            application_name: env::var("DB_APPLICATION_NAME").unwrap_or_else(|_| env!("CARGO_PKG_NAME").to_string()),
+            search_path: env::var("DB_SEARCH_PATH").ok().filter(|v| !v.trim().is_empty()),
+        }
+    }
+}
+
+impl CustomizeConnection<PgConnection, r2d2::Error> for ConnectionOptions {
+    fn on_acquire(&self, conn: &mut PgConnection) -> Result<(), r2d2::Error> {
+        let mut settings = vec![("application_name", self.application_name.clone())];
+        if let Some(timeout) = self.statement_timeout_ms {
+            settings.push(("statement_timeout", timeout.to_string()));
+        }
+        if let Some(ref search_path) = self.search_path {
+            settings.push(("search_path", search_path.clone()));
+        }
+
+        // set_config permite enlazar los valores como parámetros en lugar de interpolarlos
+        for (name, value) in settings {
+            diesel::sql_query("SELECT set_config($1, $2, false)")
+                .bind::<Text, _>(name)
+                .bind::<Text, _>(value)
+                .execute(conn)
+                .map_err(r2d2::Error::QueryError)?;
+        }
+
+        Ok(())
+    }
+}
 
 /// Inicializa el pool de conexiones a la base de datos
 pub fn init_pool(database_url: &str) -> DbPool {
     let manager = ConnectionManager::<PgConnection>::new(database_url);
-    r2d2::Pool::builder()
-        .build(manager)
-        .expect("No se pudo crear el pool de conexiones")
+    let mut builder = r2d2::Pool::builder()
+        .connection_customizer(Box::new(ConnectionOptions::from_env()));
+
+    if let Some(max_size) = env::var("DB_POOL_MAX_SIZE").ok().and_then(|v| v.parse().ok()) {
+        builder = builder.max_size(max_size);
+    }
+
+    // No se validan conexiones al construir el pool; eso lo hace check_connection
+    builder
+        .connection_timeout(Duration::from_secs(5))
+        .build_unchecked(manager)
+}
+
+/// Verifica que se puede obtener una conexión y ejecutar una consulta trivial
+pub fn check_connection(pool: &DbPool) -> AppResult<()> {
+    let mut conn = pool
+        .get()
+        .map_err(|e| AppError::InternalServerError(format!("No se pudo obtener una conexión: {}", e)))?;
+
+    ping(&mut conn)
 }
 
-// Eliminamos esta función ya que no es necesaria y causa problemas de tipo
-// La conexión se puede obtener directamente con pool.get() 
\ No newline at end of file
+/// Ejecuta una consulta trivial sobre una conexión ya obtenida
+pub fn ping(conn: &mut PgConnection) -> AppResult<()> {
+    diesel::sql_query("SELECT 1").execute(conn)?;
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+//! Envío de correo electrónico.
+//!
+//! Todavía no hay un transporte SMTP: los mensajes se escriben en el log con el
+//! target `mail`, de donde pueden recogerse en desarrollo o reenviarse con otra
+//! herramienta. Quien llama a [`send`] no depende de cómo se entregan.
+
+/// Mensaje de texto plano
+pub struct Message {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Entrega un mensaje
+pub fn send(message: &Message) {
+    tracing::info!(
+        target: "mail",
+        to = %message.to,
+        subject = %message.subject,
+        body = %message.body,
+        "Correo pendiente de entrega (no hay transporte SMTP configurado)"
+    );
+}
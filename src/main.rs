@@ -6,12 +6,18 @@ mod services;
 mod utils;
 mod api;
 mod auth;
+mod metrics;
+mod telemetry;
+mod storage;
+mod mail;
+mod cli;
 
-use log::info;
+use log::{error, info};
 use std::env;
-use actix_web::{web, App, HttpServer, middleware::Logger};
+use std::process;
+use actix_web::{web, App, HttpServer};
 use actix_cors::Cors;
-use crate::api::configure_routes;
+use crate::api::{configure_default_service, configure_media_routes, configure_routes, configure_system_routes};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -24,6 +30,42 @@ async fn main() -> std::io::Result<()> {
     // Crear pool de conexiones
     let pool = db::init_pool(&database_url);
     
+    // Verificar la conectividad antes de aceptar tráfico
+    if let Err(e) = db::check_connection(&pool) {
+        error!("No se pudo conectar a la base de datos: {}", e);
+        process::exit(2);
+    }
+    
+    // Modo de verificación: falla si el esquema no está al día con las migraciones embebidas
+    if env::args().any(|arg| arg == "--check-migrations") {
+        check_migrations(&pool);
+    }
+    
+    // Aplicar migraciones pendientes si está habilitado
+    if config::run_migrations_on_startup() {
+        let mut conn = pool.get().expect("No se pudo obtener una conexión para las migraciones");
+        match db::migrations::run_pending_migrations(&mut conn) {
+            Ok(applied) if applied.is_empty() => info!("El esquema ya está al día"),
+            Ok(applied) => info!("Migraciones aplicadas: {}", applied.join(", ")),
+            Err(e) => {
+                error!("{}", e);
+                process::exit(1);
+            }
+        }
+    }
+    
+    // Preparar el almacenamiento de la biblioteca de medios
+    if let Err(e) = storage::init() {
+        error!("No se pudo configurar el almacenamiento: {}", e);
+        process::exit(2);
+    }
+    
+    // Subcomandos de administración (p. ej. `blog-rust import-wordpress export.xml`)
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some((command, args)) = args.split_first() {
+        cli::run(command, args, &pool);
+    }
+    
     info!("Iniciando servidor en http://127.0.0.1:8080");
     
     // Iniciar servidor HTTP
@@ -36,13 +78,42 @@ async fn main() -> std::io::Result<()> {
             .max_age(3600);
             
         App::new()
-            .wrap(Logger::default())
             .wrap(cors)
+            .wrap(metrics::RequestMetrics)
+            .wrap(telemetry::RequestTracing)
             .app_data(web::Data::new(pool.clone()))
+            .configure(configure_system_routes)
+            .configure(configure_media_routes)
             .service(configure_routes())
+            .configure(configure_default_service)
     })
     .bind("127.0.0.1:8080")?
     .run()
     .await
 }
 
+/// Comprueba si hay migraciones pendientes y termina el proceso con el código correspondiente
+fn check_migrations(pool: &db::DbPool) -> ! {
+    let mut conn = match pool.get() {
+        Ok(conn) => conn,
+        Err(e) => {
+            error!("No se pudo conectar a la base de datos: {}", e);
+            process::exit(2);
+        }
+    };
+
+    match db::migrations::pending_migrations(&mut conn) {
+        Ok(pending) if pending.is_empty() => {
+            info!("El esquema está al día");
+            process::exit(0);
+        }
+        Ok(pending) => {
+            error!("Hay {} migraciones pendientes: {}", pending.len(), pending.join(", "));
+            process::exit(1);
+        }
+        Err(e) => {
+            error!("{}", e);
+            process::exit(2);
+        }
+    }
+}
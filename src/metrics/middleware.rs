@@ -0,0 +1,77 @@
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error as ActixError,
+};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use std::rc::Rc;
+use std::time::Instant;
+
+use crate::metrics::HTTP_REQUEST_DURATION;
+
+/// Middleware que mide la duración de cada petición, etiquetada por método,
+/// patrón de ruta (p. ej. `/api/posts/{id}`) y código de estado
+pub struct RequestMetrics;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Transform = RequestMetricsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestMetricsMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let start = Instant::now();
+        let method = req.method().to_string();
+        // Usar el patrón de ruta y no la URL concreta para no disparar la cardinalidad.
+        // Se resuelve antes de llamar al servicio para conservarlo también cuando
+        // un middleware interno (p. ej. JwtAuth) devuelve un error
+        let route = req
+            .request()
+            .match_pattern()
+            .unwrap_or_else(|| "unmatched".to_string());
+
+        Box::pin(async move {
+            let result = service.call(req).await;
+
+            let status = match &result {
+                Ok(res) => res.status(),
+                Err(e) => e.as_response_error().status_code(),
+            };
+
+            HTTP_REQUEST_DURATION
+                .with_label_values(&[&method, &route, status.as_str()])
+                .observe(start.elapsed().as_secs_f64());
+
+            result
+        })
+    }
+}
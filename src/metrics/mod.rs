@@ -0,0 +1,95 @@
+pub mod middleware;
+
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+use std::sync::LazyLock;
+use crate::db::DbPool;
+
+pub use middleware::RequestMetrics;
+
+/// Registro propio de la aplicación (no se usa el registro global de prometheus)
+pub static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+pub static HTTP_REQUEST_DURATION: LazyLock<HistogramVec> = LazyLock::new(|| {
+    register(HistogramVec::new(
+        HistogramOpts::new("http_request_duration_seconds", "Duración de las peticiones HTTP"),
+        &["method", "route", "status"],
+    ))
+});
+
+pub static LOGINS_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register(IntCounterVec::new(
+        Opts::new("logins_total", "Intentos de inicio de sesión por resultado"),
+        &["result"],
+    ))
+});
+
+pub static COMMENTS_CREATED_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register(IntCounterVec::new(
+        Opts::new("comments_created_total", "Comentarios creados por estado de moderación"),
+        &["status"],
+    ))
+});
+
+pub static POSTS_PUBLISHED_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
+    register(IntCounter::new("posts_published_total", "Posts publicados"))
+});
+
+pub static JWT_VALIDATION_FAILURES_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    register(IntCounterVec::new(
+        Opts::new("jwt_validation_failures_total", "Tokens JWT rechazados por motivo"),
+        &["reason"],
+    ))
+});
+
+static DB_POOL_CONNECTIONS: LazyLock<IntGauge> = LazyLock::new(|| {
+    register(IntGauge::new("db_pool_connections", "Conexiones abiertas en el pool"))
+});
+
+static DB_POOL_IDLE_CONNECTIONS: LazyLock<IntGauge> = LazyLock::new(|| {
+    register(IntGauge::new("db_pool_idle_connections", "Conexiones inactivas en el pool"))
+});
+
+static DB_POOL_MAX_SIZE: LazyLock<IntGauge> = LazyLock::new(|| {
+    register(IntGauge::new("db_pool_max_size", "Tamaño máximo del pool"))
+});
+
+fn register<M>(metric: prometheus::Result<M>) -> M
+where
+    M: prometheus::core::Collector + Clone + 'static,
+{
+    let metric = metric.expect("Definición de métrica inválida");
+    REGISTRY
+        .register(Box::new(metric.clone()))
+        .expect("Métrica registrada dos veces");
+    metric
+}
+
+/// Registra un intento de inicio de sesión
+pub fn record_login(success: bool) {
+    LOGINS_TOTAL
+        .with_label_values(&[if success { "success" } else { "failure" }])
+        .inc();
+}
+
+/// Registra un token JWT rechazado
+pub fn record_jwt_failure(reason: &str) {
+    JWT_VALIDATION_FAILURES_TOTAL.with_label_values(&[reason]).inc();
+}
+
+/// Serializa todas las métricas en el formato de texto de Prometheus,
+/// actualizando antes las del pool de conexiones
+pub fn render(pool: &DbPool) -> String {
+    let state = pool.state();
+    DB_POOL_CONNECTIONS.set(i64::from(state.connections));
+    DB_POOL_IDLE_CONNECTIONS.set(i64::from(state.idle_connections));
+    DB_POOL_MAX_SIZE.set(i64::from(pool.max_size()));
+
+    let mut buffer = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&REGISTRY.gather(), &mut buffer) {
+        log::error!("Error al serializar las métricas: {}", e);
+    }
+
+    String::from_utf8(buffer).unwrap_or_default()
+}
@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct ApiTokenDto {
+    pub id: i32,
+    pub name: String,
+    /// Solo en la respuesta de creación: después no puede recuperarse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    /// `active`, `expired` o `revoked`
+    pub status: String,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateApiTokenDto {
+    pub name: String,
+    /// `posts`, `media` y/o `comments`
+    pub scopes: Vec<String>,
+    /// Sin fecha, el token no caduca
+    pub expires_at: Option<NaiveDateTime>,
+}
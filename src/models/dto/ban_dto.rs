@@ -0,0 +1,23 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct BanDto {
+    pub id: i32,
+    /// `email` o `ip`
+    pub kind: String,
+    pub value: String,
+    pub reason: Option<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_by: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateBanDto {
+    pub kind: String,
+    pub value: String,
+    pub reason: Option<String>,
+    /// Sin fecha, el bloqueo es permanente
+    pub expires_at: Option<NaiveDateTime>,
+}
@@ -1,5 +1,6 @@
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use crate::utils::serde_helpers::double_option;
 
 #[derive(Serialize, Deserialize)]
 pub struct CategoryDto {
@@ -9,6 +10,29 @@ pub struct CategoryDto {
     pub description: Option<String>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub parent_id: Option<i32>,
+    pub position: i32,
+    /// Número de posts asociados directamente a la categoría
+    pub post_count: i64,
+    /// Ruta desde la categoría raíz hasta esta categoría (incluida)
+    pub breadcrumbs: Vec<CategoryBreadcrumbDto>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CategoryBreadcrumbDto {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Serialize)]
+pub struct CategoryTreeDto {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub position: i32,
+    pub children: Vec<CategoryTreeDto>,
 }
 
 #[derive(Deserialize)]
@@ -16,11 +40,38 @@ pub struct CreateCategoryDto {
     pub name: String,
     pub slug: Option<String>,
     pub description: Option<String>,
+    pub parent_id: Option<i32>,
+    pub position: Option<i32>,
 }
 
 #[derive(Deserialize)]
 pub struct UpdateCategoryDto {
     pub name: Option<String>,
     pub slug: Option<String>,
+    /// Sin `slug`, genera uno nuevo a partir del nombre; por defecto el slug no cambia
+    pub regenerate_slug: Option<bool>,
     pub description: Option<String>,
-} 
\ No newline at end of file
+    /// Ausente: no se modifica; `null`: la categoría pasa a ser raíz
+    #[serde(default, deserialize_with = "double_option")]
+    pub parent_id: Option<Option<i32>>,
+    pub position: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct MergeCategoryDto {
+    pub target_id: i32,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteCategoryQuery {
+    /// Categoría a la que se mueven los posts antes de eliminar
+    pub reassign_to: Option<i32>,
+    /// Eliminar aunque queden posts sin ninguna categoría
+    pub force: Option<bool>,
+}
+
+#[derive(Deserialize)]
+pub struct CategoryPostsQuery {
+    /// Incluir los posts de las subcategorías (por defecto, sí)
+    pub include_descendants: Option<bool>,
+}
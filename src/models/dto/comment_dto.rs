@@ -6,6 +6,8 @@ pub struct CommentDto {
     pub id: i32,
     pub post_id: i32,
     pub user_id: Option<i32>,
+    /// Comentario al que responde, si es una respuesta
+    pub parent_id: Option<i32>,
     pub author_name: Option<String>,
     pub author_email: Option<String>,
     pub content: String,
@@ -17,12 +19,27 @@ pub struct CommentDto {
 pub struct CreateCommentDto {
     pub post_id: i32,
     pub user_id: Option<i32>,
+    /// Comentario del mismo post al que se responde
+    pub parent_id: Option<i32>,
     pub author_name: Option<String>,
     pub author_email: Option<String>,
     pub content: String,
+    /// IP del cliente; la pone el controlador, nunca viene en el cuerpo
+    #[serde(skip)]
+    pub author_ip: Option<String>,
 }
 
 #[derive(Deserialize)]
 pub struct UpdateCommentDto {
     pub content: Option<String>,
-} 
\ No newline at end of file
+} 
+#[derive(Deserialize)]
+pub struct PurgeCommenterEmailDto {
+    pub email: String,
+}
+
+#[derive(Serialize)]
+pub struct PurgeCommenterEmailResultDto {
+    /// Comentarios anónimos de los que se ha borrado el email
+    pub comments: usize,
+}
@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct LivenessDto {
+    pub status: String,
+}
+
+#[derive(Serialize)]
+pub struct PoolStateDto {
+    pub connections: u32,
+    pub idle_connections: u32,
+    pub max_size: u32,
+}
+
+#[derive(Serialize)]
+pub struct ReadinessDto {
+    pub status: String,
+    pub database: bool,
+    pub pending_migrations: Vec<String>,
+    pub pool: PoolStateDto,
+    pub errors: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct VersionDto {
+    pub version: String,
+    pub git_commit: String,
+    pub build_time: Option<String>,
+}
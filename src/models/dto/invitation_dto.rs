@@ -0,0 +1,30 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct InvitationDto {
+    pub id: i32,
+    /// Solo en la respuesta de creación: después no puede recuperarse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub role: String,
+    pub max_uses: i32,
+    pub uses: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    pub note: Option<String>,
+    /// `active`, `used_up` o `expired`
+    pub status: String,
+    pub created_by: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateInvitationDto {
+    /// Rol de las cuentas que se registren con ella; por defecto `user`
+    pub role: Option<String>,
+    /// Por defecto 1
+    pub max_uses: Option<i32>,
+    /// Sin fecha, la invitación no caduca
+    pub expires_at: Option<NaiveDateTime>,
+    pub note: Option<String>,
+}
@@ -0,0 +1,49 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::utils::serde_helpers::double_option;
+
+#[derive(Serialize, Deserialize)]
+pub struct MediaVariantDto {
+    /// `thumbnail` o `responsive`
+    pub kind: String,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+    pub url: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MediaDto {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub url: String,
+    pub original_filename: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub alt_text: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    /// URL de la miniatura en JPEG o PNG (solo imágenes)
+    pub thumbnail_url: Option<String>,
+    /// Valor listo para el atributo `srcset` de `<img>` (solo imágenes)
+    pub srcset: Option<String>,
+    /// Igual que `srcset`, con las variantes WebP, para `<source type="image/webp">`
+    pub srcset_webp: Option<String>,
+    pub variants: Vec<MediaVariantDto>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateMediaDto {
+    #[serde(default, deserialize_with = "double_option")]
+    pub alt_text: Option<Option<String>>,
+}
+
+#[derive(Deserialize)]
+pub struct AttachMediaDto {
+    pub media_id: i32,
+    /// Posición dentro de la galería del post (por defecto, al final)
+    pub position: Option<i32>,
+}
@@ -0,0 +1,66 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct MenuSummaryDto {
+    pub id: i32,
+    pub name: String,
+    pub location: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Menú con sus elementos resueltos en árbol
+#[derive(Serialize, Deserialize)]
+pub struct MenuDto {
+    pub id: i32,
+    pub name: String,
+    pub location: String,
+    pub items: Vec<MenuItemDto>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MenuItemDto {
+    pub id: i32,
+    /// Etiqueta propia o, si no tiene, el título actual del destino
+    pub label: String,
+    /// `page`, `post`, `category` o `url`
+    pub item_type: String,
+    /// ID de la página, el post o la categoría; `null` en las URLs
+    pub target_id: Option<i32>,
+    /// Slug actual del destino (la ruta completa en las páginas); `null` en las URLs
+    pub slug: Option<String>,
+    /// Solo en los elementos de tipo `url`
+    pub url: Option<String>,
+    pub children: Vec<MenuItemDto>,
+}
+
+#[derive(Deserialize)]
+pub struct MenuItemInputDto {
+    pub label: Option<String>,
+    pub item_type: String,
+    /// Obligatorio salvo en los elementos de tipo `url`
+    pub target_id: Option<i32>,
+    /// Obligatorio en los elementos de tipo `url`
+    pub url: Option<String>,
+    #[serde(default)]
+    pub children: Vec<MenuItemInputDto>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateMenuDto {
+    pub name: String,
+    pub location: String,
+    pub items: Option<Vec<MenuItemInputDto>>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateMenuDto {
+    pub name: Option<String>,
+    pub location: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SetMenuItemsDto {
+    pub items: Vec<MenuItemInputDto>,
+}
@@ -2,8 +2,30 @@ pub mod user_dto;
 pub mod post_dto;
 pub mod category_dto;
 pub mod comment_dto;
+pub mod health_dto;
+pub mod tag_dto;
+pub mod media_dto;
+pub mod series_dto;
+pub mod redirect_dto;
+pub mod page_dto;
+pub mod menu_dto;
+pub mod settings_dto;
+pub mod ban_dto;
+pub mod invitation_dto;
+pub mod api_token_dto;
 
 pub use user_dto::*;
 pub use post_dto::*;
 pub use category_dto::*;
-pub use comment_dto::*; 
\ No newline at end of file
+pub use comment_dto::*;
+pub use health_dto::*;
+pub use tag_dto::*;
+pub use media_dto::*;
+pub use series_dto::*;
+pub use redirect_dto::*;
+pub use page_dto::*;
+pub use menu_dto::*;
+pub use settings_dto::*;
+pub use ban_dto::*;
+pub use invitation_dto::*;
+pub use api_token_dto::*;
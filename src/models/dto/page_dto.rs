@@ -0,0 +1,75 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::utils::serde_helpers::double_option;
+
+#[derive(Serialize, Deserialize)]
+pub struct PageDto {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    /// Ruta completa desde la página raíz, p. ej. `legal/privacidad`
+    pub path: String,
+    pub body: String,
+    pub template: String,
+    pub parent_id: Option<i32>,
+    pub menu_order: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    /// Páginas desde la raíz hasta esta (incluida)
+    pub breadcrumbs: Vec<PageBreadcrumbDto>,
+}
+
+/// Página sin cuerpo, para los listados
+#[derive(Serialize, Deserialize)]
+pub struct PageSummaryDto {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub path: String,
+    pub template: String,
+    pub parent_id: Option<i32>,
+    pub menu_order: i32,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PageBreadcrumbDto {
+    pub id: i32,
+    pub title: String,
+    pub path: String,
+}
+
+#[derive(Serialize)]
+pub struct PageTreeDto {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub path: String,
+    pub template: String,
+    pub menu_order: i32,
+    pub children: Vec<PageTreeDto>,
+}
+
+#[derive(Deserialize)]
+pub struct CreatePageDto {
+    pub title: String,
+    pub slug: Option<String>,
+    pub body: String,
+    pub template: Option<String>,
+    pub parent_id: Option<i32>,
+    pub menu_order: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdatePageDto {
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    /// Sin `slug`, genera uno nuevo a partir del título; por defecto el slug no cambia
+    pub regenerate_slug: Option<bool>,
+    pub body: Option<String>,
+    pub template: Option<String>,
+    /// Ausente: no se modifica; `null`: la página pasa a ser raíz
+    #[serde(default, deserialize_with = "double_option")]
+    pub parent_id: Option<Option<i32>>,
+    pub menu_order: Option<i32>,
+}
@@ -2,6 +2,24 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use crate::models::dto::category_dto::CategoryDto;
 use crate::models::dto::comment_dto::CommentDto;
+use crate::models::dto::tag_dto::TagDto;
+use crate::models::dto::media_dto::MediaDto;
+use crate::models::dto::series_dto::SeriesNavigationDto;
+use crate::utils::serde_helpers::double_option;
+
+/// Imagen destacada de un post, ya sea un fichero de la biblioteca o una URL externa
+#[derive(Serialize, Deserialize)]
+pub struct FeaturedImageDto {
+    /// Fichero de la biblioteca de medios; `None` si es una URL externa
+    pub media_id: Option<i32>,
+    pub url: String,
+    pub thumbnail_url: Option<String>,
+    pub srcset: Option<String>,
+    pub srcset_webp: Option<String>,
+    pub alt_text: Option<String>,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+}
 
 #[derive(Serialize, Deserialize)]
 pub struct PostDto {
@@ -9,19 +27,81 @@ pub struct PostDto {
     pub title: String,
     pub slug: String,
     pub body: String,
+    /// Extracto explícito o, si no lo hay, generado a partir del cuerpo
+    pub excerpt: String,
+    pub featured_image: Option<FeaturedImageDto>,
+    pub word_count: usize,
+    pub reading_time_minutes: usize,
+    pub author_id: Option<i32>,
+    /// `published`, `draft` o `private`
+    pub status: String,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
 }
 
+/// Versión ligera de `PostDto` para listados: no incluye el cuerpo
+#[derive(Serialize, Deserialize)]
+pub struct PostSummaryDto {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub excerpt: String,
+    pub featured_image: Option<FeaturedImageDto>,
+    pub word_count: usize,
+    pub reading_time_minutes: usize,
+    pub author_id: Option<i32>,
+    pub status: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+impl From<PostDto> for PostSummaryDto {
+    fn from(post: PostDto) -> Self {
+        PostSummaryDto {
+            id: post.id,
+            title: post.title,
+            slug: post.slug,
+            excerpt: post.excerpt,
+            featured_image: post.featured_image,
+            word_count: post.word_count,
+            reading_time_minutes: post.reading_time_minutes,
+            author_id: post.author_id,
+            status: post.status,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct PostListQuery {
+    /// `summary` devuelve `PostSummaryDto` (sin cuerpo); por defecto, `full`
+    pub view: Option<String>,
+    /// Página a devolver, desde 1, con el tamaño del ajuste `posts_per_page`; sin
+    /// ella se devuelven todos los posts
+    pub page: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct PostDetailDto {
     pub id: i32,
     pub title: String,
     pub slug: String,
     pub body: String,
+    pub excerpt: String,
+    pub featured_image: Option<FeaturedImageDto>,
+    pub word_count: usize,
+    pub reading_time_minutes: usize,
+    pub author_id: Option<i32>,
+    pub status: String,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
     pub categories: Vec<CategoryDto>,
+    pub tags: Vec<TagDto>,
+    /// Ficheros asociados al post, en el orden de su galería
+    pub media: Vec<MediaDto>,
+    /// Navegación de la serie a la que pertenece el post, si pertenece a alguna
+    pub series: Option<SeriesNavigationDto>,
     pub comments: Vec<CommentDto>,
 }
 
@@ -30,7 +110,15 @@ pub struct CreatePostDto {
     pub title: String,
     pub body: String,
     pub slug: Option<String>,
+    pub excerpt: Option<String>,
+    /// Imagen destacada de la biblioteca de medios (excluyente con `featured_image_url`)
+    pub featured_media_id: Option<i32>,
+    /// Imagen destacada externa (excluyente con `featured_media_id`)
+    pub featured_image_url: Option<String>,
+    /// `published` (por defecto), `draft` o `private`
+    pub status: Option<String>,
     pub category_ids: Option<Vec<i32>>,
+    pub tags: Option<Vec<String>>,
 }
 
 #[derive(Deserialize)]
@@ -38,5 +126,17 @@ pub struct UpdatePostDto {
     pub title: Option<String>,
     pub body: Option<String>,
     pub slug: Option<String>,
+    /// Sin `slug`, genera uno nuevo a partir del título; por defecto el slug no cambia
+    pub regenerate_slug: Option<bool>,
+    /// `null` elimina el extracto explícito y vuelve al generado
+    #[serde(default, deserialize_with = "double_option")]
+    pub excerpt: Option<Option<String>>,
+    /// Asignar una imagen destacada sustituye a la otra; `null` la quita
+    #[serde(default, deserialize_with = "double_option")]
+    pub featured_media_id: Option<Option<i32>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub featured_image_url: Option<Option<String>>,
+    pub status: Option<String>,
     pub category_ids: Option<Vec<i32>>,
-} 
\ No newline at end of file
+    pub tags: Option<Vec<String>>,
+}
@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct RedirectDto {
+    pub id: i32,
+    pub source_path: String,
+    pub target_path: String,
+    pub status_code: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateRedirectDto {
+    pub source_path: String,
+    pub target_path: String,
+    /// 301 (por defecto), 302, 307 o 308
+    pub status_code: Option<i32>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateRedirectDto {
+    pub source_path: Option<String>,
+    pub target_path: Option<String>,
+    pub status_code: Option<i32>,
+}
@@ -0,0 +1,72 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use crate::models::dto::post_dto::PostSummaryDto;
+use crate::utils::serde_helpers::double_option;
+
+#[derive(Serialize, Deserialize)]
+pub struct SeriesDto {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub post_count: i64,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Página de presentación de una serie: sus datos y los posts en orden de lectura
+#[derive(Serialize, Deserialize)]
+pub struct SeriesDetailDto {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    pub posts: Vec<PostSummaryDto>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SeriesPostLinkDto {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+}
+
+/// Navegación dentro de la serie a la que pertenece un post
+#[derive(Serialize, Deserialize)]
+pub struct SeriesNavigationDto {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    /// Posición del post dentro de la serie, empezando en 1
+    pub part: usize,
+    pub total: usize,
+    pub previous: Option<SeriesPostLinkDto>,
+    pub next: Option<SeriesPostLinkDto>,
+    /// Índice completo de la serie, en orden de lectura
+    pub index: Vec<SeriesPostLinkDto>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateSeriesDto {
+    pub title: String,
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    /// Posts de la serie en orden de lectura
+    pub post_ids: Option<Vec<i32>>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateSeriesDto {
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub description: Option<Option<String>>,
+}
+
+/// Lista completa y ordenada de posts de la serie; sustituye a la actual
+#[derive(Deserialize)]
+pub struct SetSeriesPostsDto {
+    pub post_ids: Vec<i32>,
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+pub const REGISTRATION_OPEN: &str = "open";
+pub const REGISTRATION_INVITE_ONLY: &str = "invite_only";
+pub const REGISTRATION_CLOSED: &str = "closed";
+pub const REGISTRATION_POLICIES: [&str; 3] = [REGISTRATION_OPEN, REGISTRATION_INVITE_ONLY, REGISTRATION_CLOSED];
+
+/// Ajustes del sitio con sus tipos. Los que no están guardados en `site_settings`
+/// toman el valor de `Default`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SiteSettings {
+    pub title: String,
+    pub description: String,
+    /// Etiqueta de idioma, p. ej. `es` o `es-ES`
+    pub default_locale: String,
+    /// Tamaño de página de `GET /api/posts?page=N`
+    pub posts_per_page: u32,
+    pub comments_enabled: bool,
+    /// Permitir comentarios de usuarios no autenticados
+    pub comments_allow_anonymous: bool,
+    /// Días tras la publicación en que se cierran los comentarios de un post (0: nunca)
+    pub comments_close_after_days: u32,
+    pub comments_max_length: u32,
+    /// Quién puede registrarse: `open`, `invite_only` o `closed`
+    pub registration_policy: String,
+}
+
+impl Default for SiteSettings {
+    fn default() -> Self {
+        SiteSettings {
+            title: "Blog-Rust".to_string(),
+            description: String::new(),
+            default_locale: "es".to_string(),
+            posts_per_page: 10,
+            comments_enabled: true,
+            comments_allow_anonymous: true,
+            comments_close_after_days: 0,
+            comments_max_length: 5000,
+            registration_policy: REGISTRATION_OPEN.to_string(),
+        }
+    }
+}
+
+/// Ajustes a modificar: solo las claves presentes cambian
+pub type UpdateSiteSettingsDto = Map<String, Value>;
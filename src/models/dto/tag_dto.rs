@@ -0,0 +1,39 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct TagDto {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize)]
+pub struct TagWithCountDto {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub post_count: i64,
+}
+
+#[derive(Serialize)]
+pub struct TagCloudEntryDto {
+    pub name: String,
+    pub slug: String,
+    pub post_count: i64,
+    /// Peso relativo de 1 (menos usada) a 5 (más usada)
+    pub weight: u8,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateTagDto {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct MergeTagDto {
+    pub target_id: i32,
+}
@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use crate::models::dto::{CommentDto, PostDto, PostSummaryDto};
+use crate::utils::serde_helpers::double_option;
 
 #[derive(Serialize, Deserialize)]
 pub struct UserDto {
@@ -9,6 +12,21 @@ pub struct UserDto {
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
     pub role: String,
+    /// Nuevo email a la espera de que se confirme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_email: Option<String>,
+    /// `active`, `suspended` (hasta `suspended_until`) o `banned` (sin fecha de fin)
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub suspended_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub suspended_until: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub suspension_reason: Option<String>,
+    /// Administrador que suspendió la cuenta
+    #[serde(default)]
+    pub suspended_by: Option<i32>,
 }
 
 #[derive(Deserialize)]
@@ -17,6 +35,8 @@ pub struct CreateUserDto {
     pub email: String,
     pub password: String,
     pub role: Option<String>,
+    /// Código de invitación; en el registro público, obligatorio si la política es `invite_only`
+    pub invite_code: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -27,6 +47,44 @@ pub struct UpdateUserDto {
     pub role: Option<String>,
 }
 
+/// Filtros de la búsqueda de usuarios de la administración
+#[derive(Deserialize)]
+pub struct UserSearchQuery {
+    /// Texto contenido en el nombre de usuario o el email, sin distinguir mayúsculas
+    pub q: Option<String>,
+    pub role: Option<String>,
+    pub suspended: Option<bool>,
+}
+
+/// Suspensión de una cuenta; sin `until` es un bloqueo permanente
+#[derive(Deserialize)]
+pub struct SuspendUserDto {
+    pub reason: Option<String>,
+    pub until: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize)]
+pub struct ChangePasswordDto {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+#[derive(Deserialize)]
+pub struct ChangeEmailDto {
+    pub email: String,
+    pub current_password: String,
+}
+
+#[derive(Deserialize)]
+pub struct DeleteAccountDto {
+    pub current_password: String,
+}
+
+#[derive(Deserialize)]
+pub struct ConfirmEmailDto {
+    pub token: String,
+}
+
 #[derive(Deserialize)]
 pub struct LoginDto {
     pub email: String,
@@ -42,4 +100,56 @@ pub struct AuthResponseDto {
 #[derive(Serialize)]
 pub struct TokenResponseDto {
     pub token: String,
-} 
\ No newline at end of file
+} 
+/// Datos personales de un usuario: su perfil, sus posts y sus comentarios
+#[derive(Serialize)]
+pub struct PersonalDataExportDto {
+    pub generated_at: NaiveDateTime,
+    pub profile: UserDto,
+    pub public_profile: ProfileDto,
+    pub posts: Vec<PostDto>,
+    /// Los escritos con la cuenta
+    pub comments: Vec<CommentDto>,
+}
+
+/// Perfil público de un usuario; no incluye el email
+#[derive(Serialize)]
+pub struct ProfileDto {
+    pub id: i32,
+    pub username: String,
+    /// Nombre para mostrar; si no lo hay, el cliente puede usar `username`
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub website: Option<String>,
+    /// Red → URL
+    pub social_links: BTreeMap<String, String>,
+    /// Imagen subida, si la hay
+    pub avatar_media_id: Option<i32>,
+    /// Miniatura de la imagen subida o, si no hay, la de Gravatar
+    pub avatar_url: String,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+/// Página pública de un autor
+#[derive(Serialize)]
+pub struct AuthorPageDto {
+    pub profile: ProfileDto,
+    /// Posts publicados, del más reciente al más antiguo
+    pub posts: Vec<PostSummaryDto>,
+}
+
+/// Cambios en el perfil propio: solo cambian los campos presentes y `null` los vacía
+#[derive(Deserialize)]
+pub struct UpdateProfileDto {
+    #[serde(default, deserialize_with = "double_option")]
+    pub display_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub bio: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub website: Option<Option<String>>,
+    /// Sustituye todos los enlaces; `{}` los quita
+    pub social_links: Option<BTreeMap<String, String>>,
+    /// Imagen de la biblioteca de medios subida por el propio usuario; `null` vuelve a Gravatar
+    #[serde(default, deserialize_with = "double_option")]
+    pub avatar_media_id: Option<Option<i32>>,
+}
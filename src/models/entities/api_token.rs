@@ -0,0 +1,43 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::api_tokens;
+
+/// Crear, editar y borrar posts, categorías, series y páginas
+pub const API_TOKEN_SCOPE_POSTS: &str = "posts";
+/// Subir y gestionar ficheros de la biblioteca de medios
+pub const API_TOKEN_SCOPE_MEDIA: &str = "media";
+/// Comentar con la identidad del usuario y editar o borrar comentarios
+pub const API_TOKEN_SCOPE_COMMENTS: &str = "comments";
+pub const API_TOKEN_SCOPES: [&str; 3] = [API_TOKEN_SCOPE_POSTS, API_TOKEN_SCOPE_MEDIA, API_TOKEN_SCOPE_COMMENTS];
+
+/// Token personal de acceso a la API
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = api_tokens)]
+pub struct ApiToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    /// Comienzo del token, para reconocerlo en los listados
+    pub token_prefix: String,
+    /// SHA-256 del token; el token solo se muestra al crearlo
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    /// Sin fecha, el token no caduca
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = api_tokens)]
+pub struct NewApiToken {
+    pub user_id: i32,
+    pub name: String,
+    pub token_prefix: String,
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_at: Option<NaiveDateTime>,
+}
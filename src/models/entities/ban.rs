@@ -0,0 +1,33 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::bans;
+
+pub const BAN_KIND_EMAIL: &str = "email";
+pub const BAN_KIND_IP: &str = "ip";
+
+/// Email o IP que no puede comentar
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = bans)]
+pub struct Ban {
+    pub id: i32,
+    pub kind: String,
+    /// Email en minúsculas o IP normalizada
+    pub value: String,
+    pub reason: Option<String>,
+    /// Sin fecha, el bloqueo es permanente
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_by: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = bans)]
+pub struct NewBan {
+    pub kind: String,
+    pub value: String,
+    pub reason: Option<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_by: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
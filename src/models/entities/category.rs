@@ -3,7 +3,7 @@ use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::db::schema::categories;
 
-#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, AsChangeset)]
+#[derive(Queryable, QueryableByName, Identifiable, Serialize, Deserialize, Debug, AsChangeset)]
 #[diesel(table_name = categories)]
 pub struct Category {
     pub id: i32,
@@ -12,6 +12,8 @@ pub struct Category {
     pub description: Option<String>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub parent_id: Option<i32>,
+    pub position: i32,
 }
 
 #[derive(Insertable, Deserialize)]
@@ -22,6 +24,8 @@ pub struct NewCategory {
     pub description: Option<String>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub parent_id: Option<i32>,
+    pub position: i32,
 }
 
 #[derive(AsChangeset, Deserialize)]
@@ -31,4 +35,7 @@ pub struct UpdateCategory {
     pub slug: Option<String>,
     pub description: Option<String>,
     pub updated_at: Option<NaiveDateTime>,
-} 
\ No newline at end of file
+    /// `Some(None)` quita la categoría padre
+    pub parent_id: Option<Option<i32>>,
+    pub position: Option<i32>,
+}
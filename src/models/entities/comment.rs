@@ -14,6 +14,7 @@ pub struct Comment {
     pub content: String,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Insertable, Deserialize)]
@@ -26,6 +27,7 @@ pub struct NewComment {
     pub content: String,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(AsChangeset, Deserialize)]
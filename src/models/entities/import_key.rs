@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::import_keys;
+
+/// Valores de `entity_type`
+pub const IMPORT_ENTITY_AUTHOR: &str = "author";
+pub const IMPORT_ENTITY_CATEGORY: &str = "category";
+pub const IMPORT_ENTITY_POST: &str = "post";
+pub const IMPORT_ENTITY_COMMENT: &str = "comment";
+pub const IMPORT_ENTITY_ATTACHMENT: &str = "attachment";
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = import_keys)]
+pub struct ImportKey {
+    pub id: i32,
+    /// Origen de la importación, p. ej. la URL del sitio WordPress
+    pub source: String,
+    pub entity_type: String,
+    /// Identificador en el origen (GUID del post, login del autor...)
+    pub external_id: String,
+    pub entity_id: i32,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = import_keys)]
+pub struct NewImportKey {
+    pub source: String,
+    pub entity_type: String,
+    pub external_id: String,
+    pub entity_id: i32,
+    pub created_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,33 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::invitations;
+
+/// Código de invitación para registrarse con un rol asignado de antemano
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = invitations)]
+pub struct Invitation {
+    pub id: i32,
+    /// SHA-256 del código; el código solo se muestra al crearla
+    pub code_hash: String,
+    pub role: String,
+    pub max_uses: i32,
+    pub uses: i32,
+    /// Sin fecha, la invitación no caduca
+    pub expires_at: Option<NaiveDateTime>,
+    pub note: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = invitations)]
+pub struct NewInvitation {
+    pub code_hash: String,
+    pub role: String,
+    pub max_uses: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    pub note: Option<String>,
+    pub created_by: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
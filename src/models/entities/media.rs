@@ -0,0 +1,42 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::media;
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, Clone)]
+#[diesel(table_name = media)]
+pub struct Media {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub storage_key: String,
+    pub original_filename: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub alt_text: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = media)]
+pub struct NewMedia {
+    pub user_id: Option<i32>,
+    pub storage_key: String,
+    pub original_filename: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub alt_text: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(AsChangeset, Deserialize)]
+#[diesel(table_name = media)]
+pub struct UpdateMedia {
+    pub alt_text: Option<Option<String>>,
+    pub updated_at: Option<NaiveDateTime>,
+}
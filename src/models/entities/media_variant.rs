@@ -0,0 +1,29 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::media_variants;
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, Clone, Associations)]
+#[diesel(table_name = media_variants)]
+#[diesel(belongs_to(crate::models::entities::media::Media))]
+pub struct MediaVariant {
+    pub id: i32,
+    pub media_id: i32,
+    pub kind: String,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+    pub storage_key: String,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = media_variants)]
+pub struct NewMediaVariant {
+    pub media_id: i32,
+    pub kind: String,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+    pub storage_key: String,
+}
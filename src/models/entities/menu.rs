@@ -0,0 +1,31 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::menus;
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = menus)]
+pub struct Menu {
+    pub id: i32,
+    pub name: String,
+    pub location: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = menus)]
+pub struct NewMenu {
+    pub name: String,
+    pub location: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(AsChangeset, Deserialize)]
+#[diesel(table_name = menus)]
+pub struct UpdateMenu {
+    pub name: Option<String>,
+    pub location: Option<String>,
+    pub updated_at: Option<NaiveDateTime>,
+}
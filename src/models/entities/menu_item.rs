@@ -0,0 +1,40 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::menu_items;
+
+/// Tipos de destino de un elemento de menú
+pub const MENU_ITEM_PAGE: &str = "page";
+pub const MENU_ITEM_POST: &str = "post";
+pub const MENU_ITEM_CATEGORY: &str = "category";
+pub const MENU_ITEM_URL: &str = "url";
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, Associations)]
+#[diesel(table_name = menu_items)]
+#[diesel(belongs_to(crate::models::entities::menu::Menu))]
+pub struct MenuItem {
+    pub id: i32,
+    pub menu_id: i32,
+    pub parent_id: Option<i32>,
+    pub position: i32,
+    /// Sin etiqueta se usa el título del destino
+    pub label: Option<String>,
+    pub item_type: String,
+    pub page_id: Option<i32>,
+    pub post_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub url: Option<String>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = menu_items)]
+pub struct NewMenuItem {
+    pub menu_id: i32,
+    pub parent_id: Option<i32>,
+    pub position: i32,
+    pub label: Option<String>,
+    pub item_type: String,
+    pub page_id: Option<i32>,
+    pub post_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub url: Option<String>,
+}
@@ -3,9 +3,43 @@ pub mod user;
 pub mod category;
 pub mod comment;
 pub mod post_category;
+pub mod tag;
+pub mod post_tag;
+pub mod media;
+pub mod media_variant;
+pub mod post_media;
+pub mod series;
+pub mod series_post;
+pub mod slug_history;
+pub mod redirect;
+pub mod page;
+pub mod menu;
+pub mod menu_item;
+pub mod site_setting;
+pub mod import_key;
+pub mod ban;
+pub mod invitation;
+pub mod api_token;
 
-pub use post::{Post, NewPost, UpdatePost};
-pub use user::{User, NewUser, UpdateUser};
+pub use post::{Post, NewPost, UpdatePost, POST_STATUS_PUBLISHED, POST_STATUS_DRAFT, POST_STATUS_PRIVATE, POST_STATUSES};
+pub use user::{User, NewUser, UpdateUser, UpdateProfile};
 pub use category::{Category, NewCategory, UpdateCategory};
 pub use comment::{Comment, NewComment, UpdateComment};
-pub use post_category::{NewPostCategory}; 
\ No newline at end of file
+pub use post_category::{NewPostCategory};
+pub use tag::{Tag, NewTag, UpdateTag};
+pub use post_tag::{NewPostTag};
+pub use media::{Media, NewMedia, UpdateMedia};
+pub use media_variant::{MediaVariant, NewMediaVariant};
+pub use post_media::{NewPostMedia};
+pub use series::{Series, NewSeries, UpdateSeries};
+pub use series_post::{NewSeriesPost};
+pub use slug_history::{SlugHistory, NewSlugHistory, SLUG_ENTITY_POST, SLUG_ENTITY_CATEGORY};
+pub use redirect::{Redirect, NewRedirect, UpdateRedirect};
+pub use page::{Page, NewPage, UpdatePage};
+pub use menu::{Menu, NewMenu, UpdateMenu};
+pub use menu_item::{MenuItem, NewMenuItem, MENU_ITEM_PAGE, MENU_ITEM_POST, MENU_ITEM_CATEGORY, MENU_ITEM_URL};
+pub use site_setting::{SiteSetting};
+pub use import_key::{NewImportKey, IMPORT_ENTITY_AUTHOR, IMPORT_ENTITY_CATEGORY, IMPORT_ENTITY_POST, IMPORT_ENTITY_COMMENT, IMPORT_ENTITY_ATTACHMENT};
+pub use ban::{Ban, NewBan, BAN_KIND_EMAIL, BAN_KIND_IP};
+pub use invitation::{Invitation, NewInvitation};
+pub use api_token::{ApiToken, NewApiToken, API_TOKEN_SCOPE_POSTS, API_TOKEN_SCOPE_MEDIA, API_TOKEN_SCOPE_COMMENTS, API_TOKEN_SCOPES};
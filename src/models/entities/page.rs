@@ -0,0 +1,47 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::pages;
+
+#[derive(Queryable, QueryableByName, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = pages)]
+pub struct Page {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub path: String,
+    pub body: String,
+    pub template: String,
+    pub parent_id: Option<i32>,
+    pub menu_order: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = pages)]
+pub struct NewPage {
+    pub title: String,
+    pub slug: String,
+    pub path: String,
+    pub body: String,
+    pub template: String,
+    pub parent_id: Option<i32>,
+    pub menu_order: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(AsChangeset, Deserialize)]
+#[diesel(table_name = pages)]
+pub struct UpdatePage {
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub path: Option<String>,
+    pub body: Option<String>,
+    pub template: Option<String>,
+    /// `Some(None)` convierte la página en raíz
+    pub parent_id: Option<Option<i32>>,
+    pub menu_order: Option<i32>,
+    pub updated_at: Option<NaiveDateTime>,
+}
@@ -3,7 +3,13 @@ use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::db::schema::posts;
 
-#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, AsChangeset)]
+/// Valores de `status`; solo los publicados aparecen en los listados públicos
+pub const POST_STATUS_PUBLISHED: &str = "published";
+pub const POST_STATUS_DRAFT: &str = "draft";
+pub const POST_STATUS_PRIVATE: &str = "private";
+pub const POST_STATUSES: [&str; 3] = [POST_STATUS_PUBLISHED, POST_STATUS_DRAFT, POST_STATUS_PRIVATE];
+
+#[derive(Queryable, QueryableByName, Identifiable, Serialize, Deserialize, Debug, AsChangeset)]
 #[diesel(table_name = posts)]
 pub struct Post {
     pub id: i32,
@@ -12,6 +18,11 @@ pub struct Post {
     pub body: String,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub excerpt: Option<String>,
+    pub featured_media_id: Option<i32>,
+    pub featured_image_url: Option<String>,
+    pub author_id: Option<i32>,
+    pub status: String,
 }
 
 #[derive(Insertable, Deserialize)]
@@ -22,6 +33,11 @@ pub struct NewPost {
     pub body: String,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub excerpt: Option<String>,
+    pub featured_media_id: Option<i32>,
+    pub featured_image_url: Option<String>,
+    pub author_id: Option<i32>,
+    pub status: String,
 }
 
 #[derive(AsChangeset, Deserialize, Clone)]
@@ -31,4 +47,8 @@ pub struct UpdatePost {
     pub slug: Option<String>,
     pub body: Option<String>,
     pub updated_at: Option<NaiveDateTime>,
+    pub excerpt: Option<Option<String>>,
+    pub featured_media_id: Option<Option<i32>>,
+    pub featured_image_url: Option<Option<String>>,
+    pub status: Option<String>,
 } 
\ No newline at end of file
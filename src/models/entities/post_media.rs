@@ -0,0 +1,22 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::post_media;
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, Associations)]
+#[diesel(table_name = post_media)]
+#[diesel(belongs_to(crate::models::entities::post::Post))]
+#[diesel(belongs_to(crate::models::entities::media::Media))]
+pub struct PostMedia {
+    pub id: i32,
+    pub post_id: i32,
+    pub media_id: i32,
+    pub position: i32,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = post_media)]
+pub struct NewPostMedia {
+    pub post_id: i32,
+    pub media_id: i32,
+    pub position: i32,
+}
@@ -0,0 +1,20 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::post_tags;
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, Associations)]
+#[diesel(table_name = post_tags)]
+#[diesel(belongs_to(crate::models::entities::post::Post))]
+#[diesel(belongs_to(crate::models::entities::tag::Tag))]
+pub struct PostTag {
+    pub id: i32,
+    pub post_id: i32,
+    pub tag_id: i32,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = post_tags)]
+pub struct NewPostTag {
+    pub post_id: i32,
+    pub tag_id: i32,
+}
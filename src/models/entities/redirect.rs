@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::redirects;
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = redirects)]
+pub struct Redirect {
+    pub id: i32,
+    pub source_path: String,
+    pub target_path: String,
+    pub status_code: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = redirects)]
+pub struct NewRedirect {
+    pub source_path: String,
+    pub target_path: String,
+    pub status_code: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(AsChangeset, Deserialize)]
+#[diesel(table_name = redirects)]
+pub struct UpdateRedirect {
+    pub source_path: Option<String>,
+    pub target_path: Option<String>,
+    pub status_code: Option<i32>,
+    pub updated_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::series;
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = series)]
+pub struct Series {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = series)]
+pub struct NewSeries {
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(AsChangeset, Deserialize)]
+#[diesel(table_name = series)]
+pub struct UpdateSeries {
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub description: Option<Option<String>>,
+    pub updated_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,22 @@
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::series_posts;
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, Associations)]
+#[diesel(table_name = series_posts)]
+#[diesel(belongs_to(crate::models::entities::series::Series))]
+#[diesel(belongs_to(crate::models::entities::post::Post))]
+pub struct SeriesPost {
+    pub id: i32,
+    pub series_id: i32,
+    pub post_id: i32,
+    pub position: i32,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = series_posts)]
+pub struct NewSeriesPost {
+    pub series_id: i32,
+    pub post_id: i32,
+    pub position: i32,
+}
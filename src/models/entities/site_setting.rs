@@ -0,0 +1,13 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::db::schema::site_settings;
+
+#[derive(Queryable, Insertable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = site_settings)]
+pub struct SiteSetting {
+    pub key: String,
+    pub value: Value,
+    pub updated_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,27 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::slug_history;
+
+/// Valores de `entity_type`
+pub const SLUG_ENTITY_POST: &str = "post";
+pub const SLUG_ENTITY_CATEGORY: &str = "category";
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug)]
+#[diesel(table_name = slug_history)]
+pub struct SlugHistory {
+    pub id: i32,
+    pub entity_type: String,
+    pub entity_id: i32,
+    pub slug: String,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = slug_history)]
+pub struct NewSlugHistory {
+    pub entity_type: String,
+    pub entity_id: i32,
+    pub slug: String,
+    pub created_at: Option<NaiveDateTime>,
+}
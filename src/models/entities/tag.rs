@@ -0,0 +1,31 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::db::schema::tags;
+
+#[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, AsChangeset)]
+#[diesel(table_name = tags)]
+pub struct Tag {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Deserialize)]
+#[diesel(table_name = tags)]
+pub struct NewTag {
+    pub name: String,
+    pub slug: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(AsChangeset, Deserialize)]
+#[diesel(table_name = tags)]
+pub struct UpdateTag {
+    pub name: Option<String>,
+    pub slug: Option<String>,
+    pub updated_at: Option<NaiveDateTime>,
+}
@@ -1,6 +1,7 @@
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use crate::db::schema::users;
 
 #[derive(Queryable, Identifiable, Serialize, Deserialize, Debug, AsChangeset)]
@@ -14,6 +15,23 @@ pub struct User {
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
     pub role: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub website: Option<String>,
+    /// Objeto red → URL, p. ej. `{"mastodon": "https://..."}`
+    pub social_links: Value,
+    pub avatar_media_id: Option<i32>,
+    /// Nuevo email pendiente de confirmar
+    pub pending_email: Option<String>,
+    #[serde(skip_serializing)]
+    pub email_change_token_hash: Option<String>,
+    pub email_change_expires_at: Option<NaiveDateTime>,
+    pub suspended_at: Option<NaiveDateTime>,
+    /// Fin de la suspensión; sin él, la cuenta está bloqueada de forma permanente
+    pub suspended_until: Option<NaiveDateTime>,
+    pub suspension_reason: Option<String>,
+    /// Administrador que suspendió la cuenta
+    pub suspended_by: Option<i32>,
 }
 
 #[derive(Insertable, Deserialize)]
@@ -35,4 +53,16 @@ pub struct UpdateUser {
     pub password_hash: Option<String>,
     pub updated_at: Option<NaiveDateTime>,
     pub role: Option<String>,
-} 
\ No newline at end of file
+}
+
+/// Cambios en el perfil público; `Some(None)` vacía el campo
+#[derive(AsChangeset, Default)]
+#[diesel(table_name = users)]
+pub struct UpdateProfile {
+    pub display_name: Option<Option<String>>,
+    pub bio: Option<Option<String>>,
+    pub website: Option<Option<String>>,
+    pub social_links: Option<Value>,
+    pub avatar_media_id: Option<Option<i32>>,
+    pub updated_at: Option<NaiveDateTime>,
+}
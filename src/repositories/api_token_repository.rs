@@ -0,0 +1,63 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::api_tokens;
+use crate::models::entities::{ApiToken, NewApiToken};
+
+pub struct ApiTokenRepository;
+
+impl ApiTokenRepository {
+    #[instrument(skip(conn))]
+    pub fn find_by_user(user_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<ApiToken>> {
+        api_tokens::table
+            .filter(api_tokens::user_id.eq(user_id))
+            .order(api_tokens::id.desc())
+            .load::<ApiToken>(conn)
+    }
+
+    /// Tokens del usuario que no se han revocado, caducados o no
+    #[instrument(skip(conn))]
+    pub fn count_unrevoked_by_user(user_id: i32, conn: &mut DbConnection) -> QueryResult<i64> {
+        api_tokens::table
+            .filter(api_tokens::user_id.eq(user_id))
+            .filter(api_tokens::revoked_at.is_null())
+            .count()
+            .get_result(conn)
+    }
+
+    #[instrument(skip_all)]
+    pub fn create(new_token: &NewApiToken, conn: &mut DbConnection) -> QueryResult<ApiToken> {
+        diesel::insert_into(api_tokens::table)
+            .values(new_token)
+            .get_result::<ApiToken>(conn)
+    }
+
+    /// Anota el uso del token con ese hash, si no está revocado ni caducado en
+    /// `now`, y lo devuelve
+    #[instrument(skip_all)]
+    pub fn touch(token_hash: &str, now: NaiveDateTime, conn: &mut DbConnection) -> QueryResult<Option<ApiToken>> {
+        diesel::update(
+            api_tokens::table
+                .filter(api_tokens::token_hash.eq(token_hash))
+                .filter(api_tokens::revoked_at.is_null())
+                .filter(api_tokens::expires_at.is_null().or(api_tokens::expires_at.gt(now))),
+        )
+        .set(api_tokens::last_used_at.eq(Some(now)))
+        .get_result::<ApiToken>(conn)
+        .optional()
+    }
+
+    /// Revoca un token del usuario; devuelve las filas modificadas
+    #[instrument(skip(conn))]
+    pub fn revoke(token_id: i32, user_id: i32, now: NaiveDateTime, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::update(
+            api_tokens::table
+                .filter(api_tokens::id.eq(token_id))
+                .filter(api_tokens::user_id.eq(user_id))
+                .filter(api_tokens::revoked_at.is_null()),
+        )
+        .set(api_tokens::revoked_at.eq(Some(now)))
+        .execute(conn)
+    }
+}
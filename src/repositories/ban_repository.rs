@@ -0,0 +1,50 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::bans;
+use crate::models::entities::{Ban, NewBan};
+
+pub struct BanRepository;
+
+impl BanRepository {
+    #[instrument(skip_all)]
+    pub fn find_all(conn: &mut DbConnection) -> QueryResult<Vec<Ban>> {
+        bans::table.order(bans::id.desc()).load::<Ban>(conn)
+    }
+
+    /// Bloqueo del valor que sigue en vigor en `now`
+    #[instrument(skip(conn))]
+    pub fn find_active(kind: &str, value: &str, now: NaiveDateTime, conn: &mut DbConnection) -> QueryResult<Option<Ban>> {
+        bans::table
+            .filter(bans::kind.eq(kind))
+            .filter(bans::value.eq(value))
+            .filter(bans::expires_at.is_null().or(bans::expires_at.gt(now)))
+            .first::<Ban>(conn)
+            .optional()
+    }
+
+    #[instrument(skip_all)]
+    pub fn create(new_ban: &NewBan, conn: &mut DbConnection) -> QueryResult<Ban> {
+        diesel::insert_into(bans::table)
+            .values(new_ban)
+            .get_result::<Ban>(conn)
+    }
+
+    /// Borra el bloqueo caducado del valor, para que uno nuevo pueda ocupar su lugar
+    #[instrument(skip(conn))]
+    pub fn delete_expired(kind: &str, value: &str, now: NaiveDateTime, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(
+            bans::table
+                .filter(bans::kind.eq(kind))
+                .filter(bans::value.eq(value))
+                .filter(bans::expires_at.le(now)),
+        )
+        .execute(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete(ban_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(bans::table.filter(bans::id.eq(ban_id))).execute(conn)
+    }
+}
@@ -0,0 +1,194 @@
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer};
+use std::collections::HashMap;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::{categories, post_categories, posts};
+use crate::models::entities::{Category, Post, POST_STATUS_PUBLISHED};
+
+/// Categorías del subárbol cuya raíz es `$1` (incluida). UNION, y no UNION ALL,
+/// garantiza que la recursión termina aunque existiera un ciclo en los datos.
+const CATEGORY_TREE_CTE: &str = "
+    WITH RECURSIVE category_tree AS (
+        SELECT id FROM categories WHERE id = $1
+        UNION
+        SELECT c.id FROM categories c JOIN category_tree t ON c.parent_id = t.id
+    )";
+
+#[derive(QueryableByName)]
+struct Count {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+pub struct CategoryRepository;
+
+impl CategoryRepository {
+    #[instrument(skip_all)]
+    pub fn find_all(conn: &mut DbConnection) -> QueryResult<Vec<Category>> {
+        categories::table
+            .order((categories::position.asc(), categories::name.asc()))
+            .load::<Category>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_id(category_id: i32, conn: &mut DbConnection) -> QueryResult<Category> {
+        categories::table.filter(categories::id.eq(category_id)).first::<Category>(conn)
+    }
+
+    /// Devuelve la cadena de categorías desde la raíz hasta `category_id` (incluida)
+    #[instrument(skip(conn))]
+    pub fn find_ancestors(category_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<Category>> {
+        diesel::sql_query(
+            "WITH RECURSIVE ancestors AS (
+                SELECT c.*, 0 AS depth FROM categories c WHERE c.id = $1
+                UNION ALL
+                SELECT p.*, a.depth + 1 FROM categories p
+                JOIN ancestors a ON p.id = a.parent_id
+                WHERE a.depth < 64
+            )
+            SELECT id, name, slug, description, created_at, updated_at, parent_id, position
+            FROM ancestors ORDER BY depth DESC",
+        )
+        .bind::<Integer, _>(category_id)
+        .load::<Category>(conn)
+    }
+
+    /// Lee la categoría bloqueándola con `FOR UPDATE`; mientras dure la transacción
+    /// no se le pueden asociar posts nuevos
+    #[instrument(skip(conn))]
+    pub fn lock_by_id(category_id: i32, conn: &mut DbConnection) -> QueryResult<Category> {
+        categories::table
+            .filter(categories::id.eq(category_id))
+            .for_update()
+            .first::<Category>(conn)
+    }
+
+    /// Bloquea con `FOR UPDATE` la cadena de categorías desde `category_id` hasta
+    /// la raíz y devuelve sus IDs. Cada fila se lee ya bloqueada, así que la cadena
+    /// no puede cambiar hasta que termine la transacción.
+    #[instrument(skip(conn))]
+    pub fn lock_ancestor_ids(category_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<i32>> {
+        let mut chain = Vec::new();
+        let mut current = Some(category_id);
+        while let Some(current_id) = current {
+            if chain.contains(&current_id) || chain.len() >= 64 {
+                break;
+            }
+            current = categories::table
+                .filter(categories::id.eq(current_id))
+                .select(categories::parent_id)
+                .for_update()
+                .first::<Option<i32>>(conn)?;
+            chain.push(current_id);
+        }
+
+        Ok(chain)
+    }
+
+    /// Devuelve los posts publicados de una categoría y de todas sus subcategorías, sin duplicados
+    #[instrument(skip(conn))]
+    pub fn find_posts_in_tree(category_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<Post>> {
+        diesel::sql_query(format!(
+            "{} SELECT p.* FROM posts p
+            WHERE p.status = 'published'
+            AND EXISTS (
+                SELECT 1 FROM post_categories pc
+                JOIN category_tree t ON t.id = pc.category_id
+                WHERE pc.post_id = p.id
+            )
+            ORDER BY p.id DESC",
+            CATEGORY_TREE_CTE
+        ))
+        .bind::<Integer, _>(category_id)
+        .load::<Post>(conn)
+    }
+
+    /// Devuelve los posts publicados asociados directamente a una categoría
+    #[instrument(skip(conn))]
+    pub fn find_posts(category_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<Post>> {
+        post_categories::table
+            .filter(post_categories::category_id.eq(category_id))
+            .inner_join(posts::table)
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .select(posts::all_columns)
+            .order(posts::id.desc())
+            .load::<Post>(conn)
+    }
+
+    /// Número de posts publicados asociados directamente a cada categoría; las
+    /// categorías sin posts no aparecen
+    #[instrument(skip_all)]
+    pub fn count_posts_by_category(conn: &mut DbConnection) -> QueryResult<HashMap<i32, i64>> {
+        let counts = post_categories::table
+            .inner_join(posts::table)
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .group_by(post_categories::category_id)
+            .select((post_categories::category_id, diesel::dsl::count_star()))
+            .load::<(i32, i64)>(conn)?;
+
+        Ok(counts.into_iter().collect())
+    }
+
+    /// Número de posts publicados asociados directamente a la categoría
+    #[instrument(skip(conn))]
+    pub fn count_posts(category_id: i32, conn: &mut DbConnection) -> QueryResult<i64> {
+        post_categories::table
+            .inner_join(posts::table)
+            .filter(post_categories::category_id.eq(category_id))
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .count()
+            .get_result(conn)
+    }
+
+    /// Cuenta los posts cuya única categoría es `category_id`
+    #[instrument(skip(conn))]
+    pub fn count_exclusive_posts(category_id: i32, conn: &mut DbConnection) -> QueryResult<i64> {
+        let row = diesel::sql_query(
+            "SELECT COUNT(*) AS count FROM post_categories pc
+            WHERE pc.category_id = $1
+            AND NOT EXISTS (
+                SELECT 1 FROM post_categories o
+                WHERE o.post_id = pc.post_id AND o.category_id <> $1
+            )",
+        )
+        .bind::<Integer, _>(category_id)
+        .get_result::<Count>(conn)?;
+
+        Ok(row.count)
+    }
+
+    /// Mueve las asociaciones con posts de `source_id` a `target_id`, sin duplicar
+    /// los posts que ya estaban en la categoría destino. Devuelve las filas movidas.
+    #[instrument(skip(conn))]
+    pub fn move_posts(source_id: i32, target_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        let already_in_target: Vec<i32> = post_categories::table
+            .filter(post_categories::category_id.eq(target_id))
+            .select(post_categories::post_id)
+            .load(conn)?;
+
+        diesel::delete(
+            post_categories::table
+                .filter(post_categories::category_id.eq(source_id))
+                .filter(post_categories::post_id.eq_any(already_in_target)),
+        )
+        .execute(conn)?;
+
+        diesel::update(post_categories::table.filter(post_categories::category_id.eq(source_id)))
+            .set(post_categories::category_id.eq(target_id))
+            .execute(conn)
+    }
+
+    /// Cuelga las subcategorías directas de `category_id` de `new_parent`
+    #[instrument(skip(conn))]
+    pub fn reparent_children(category_id: i32, new_parent: Option<i32>, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::update(categories::table.filter(categories::parent_id.eq(category_id)))
+            .set(categories::parent_id.eq(new_parent))
+            .execute(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete(category_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(categories::table.filter(categories::id.eq(category_id))).execute(conn)
+    }
+}
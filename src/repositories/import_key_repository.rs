@@ -0,0 +1,43 @@
+use chrono::Local;
+use diesel::prelude::*;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::import_keys;
+use crate::models::entities::NewImportKey;
+
+pub struct ImportKeyRepository;
+
+impl ImportKeyRepository {
+    /// Entidad creada a partir de un identificador externo, si ya se importó
+    #[instrument(skip(conn))]
+    pub fn find(source: &str, entity_type: &str, external_id: &str, conn: &mut DbConnection) -> QueryResult<Option<i32>> {
+        import_keys::table
+            .filter(import_keys::source.eq(source))
+            .filter(import_keys::entity_type.eq(entity_type))
+            .filter(import_keys::external_id.eq(external_id))
+            .select(import_keys::entity_id)
+            .first::<i32>(conn)
+            .optional()
+    }
+
+    /// Asocia un identificador externo a una entidad; si ya estaba asociado, lo
+    /// reasigna (la entidad anterior se borró después de importarla)
+    #[instrument(skip(conn))]
+    pub fn record(source: &str, entity_type: &str, external_id: &str, entity_id: i32, conn: &mut DbConnection) -> QueryResult<()> {
+        let now = Local::now().naive_local();
+        diesel::insert_into(import_keys::table)
+            .values(&NewImportKey {
+                source: source.to_string(),
+                entity_type: entity_type.to_string(),
+                external_id: external_id.to_string(),
+                entity_id,
+                created_at: Some(now),
+            })
+            .on_conflict((import_keys::source, import_keys::entity_type, import_keys::external_id))
+            .do_update()
+            .set((import_keys::entity_id.eq(entity_id), import_keys::created_at.eq(Some(now))))
+            .execute(conn)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::invitations;
+use crate::models::entities::{Invitation, NewInvitation};
+
+pub struct InvitationRepository;
+
+impl InvitationRepository {
+    #[instrument(skip_all)]
+    pub fn find_all(conn: &mut DbConnection) -> QueryResult<Vec<Invitation>> {
+        invitations::table.order(invitations::id.desc()).load::<Invitation>(conn)
+    }
+
+    #[instrument(skip_all)]
+    pub fn create(new_invitation: &NewInvitation, conn: &mut DbConnection) -> QueryResult<Invitation> {
+        diesel::insert_into(invitations::table)
+            .values(new_invitation)
+            .get_result::<Invitation>(conn)
+    }
+
+    /// Suma un uso a la invitación del código si sigue vigente en `now` y le quedan
+    /// usos. Es una única sentencia, así que dos registros simultáneos no pueden
+    /// gastar el mismo uso.
+    #[instrument(skip_all)]
+    pub fn consume(code_hash: &str, now: NaiveDateTime, conn: &mut DbConnection) -> QueryResult<Option<Invitation>> {
+        diesel::update(
+            invitations::table
+                .filter(invitations::code_hash.eq(code_hash))
+                .filter(invitations::uses.lt(invitations::max_uses))
+                .filter(invitations::expires_at.is_null().or(invitations::expires_at.gt(now))),
+        )
+        .set(invitations::uses.eq(invitations::uses + 1))
+        .get_result::<Invitation>(conn)
+        .optional()
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete(invitation_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(invitations::table.filter(invitations::id.eq(invitation_id))).execute(conn)
+    }
+}
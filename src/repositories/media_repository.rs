@@ -0,0 +1,158 @@
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer};
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::{media, media_variants, post_media, users};
+use crate::models::entities::{Media, MediaVariant, NewMedia, NewMediaVariant, NewPostMedia, UpdateMedia};
+
+#[derive(QueryableByName)]
+struct UsedBytes {
+    #[diesel(sql_type = BigInt)]
+    used: i64,
+}
+
+pub struct MediaRepository;
+
+impl MediaRepository {
+    #[instrument(skip(conn))]
+    pub fn find_by_id(media_id: i32, conn: &mut DbConnection) -> QueryResult<Media> {
+        media::table.filter(media::id.eq(media_id)).first::<Media>(conn)
+    }
+
+    /// Varios ficheros a la vez; los identificadores inexistentes se ignoran
+    #[instrument(skip(conn))]
+    pub fn find_by_ids(media_ids: &[i32], conn: &mut DbConnection) -> QueryResult<Vec<Media>> {
+        media::table.filter(media::id.eq_any(media_ids)).load::<Media>(conn)
+    }
+
+    /// Lista los ficheros, los más recientes primero; con `user_id` solo los de ese usuario
+    #[instrument(skip(conn))]
+    pub fn find_all(user_id: Option<i32>, conn: &mut DbConnection) -> QueryResult<Vec<Media>> {
+        let mut query = media::table.into_boxed();
+        if let Some(user_id) = user_id {
+            query = query.filter(media::user_id.eq(user_id));
+        }
+
+        query.order(media::id.desc()).load::<Media>(conn)
+    }
+
+    /// Variantes de varios ficheros a la vez, ordenadas por ancho
+    #[instrument(skip(conn))]
+    pub fn find_variants(media_ids: &[i32], conn: &mut DbConnection) -> QueryResult<Vec<MediaVariant>> {
+        media_variants::table
+            .filter(media_variants::media_id.eq_any(media_ids))
+            .order((media_variants::media_id.asc(), media_variants::width.asc()))
+            .load::<MediaVariant>(conn)
+    }
+
+    /// Bytes que ocupan los ficheros de un usuario, variantes incluidas
+    #[instrument(skip(conn))]
+    pub fn used_bytes(user_id: i32, conn: &mut DbConnection) -> QueryResult<i64> {
+        let row = diesel::sql_query(
+            "SELECT (
+                COALESCE((SELECT SUM(m.size_bytes) FROM media m WHERE m.user_id = $1), 0)
+                + COALESCE((SELECT SUM(v.size_bytes) FROM media_variants v
+                    JOIN media m ON m.id = v.media_id WHERE m.user_id = $1), 0)
+            )::BIGINT AS used",
+        )
+        .bind::<Integer, _>(user_id)
+        .get_result::<UsedBytes>(conn)?;
+
+        Ok(row.used)
+    }
+
+    /// Bloquea con `FOR UPDATE` la fila del usuario, para que las subidas simultáneas
+    /// de un mismo usuario comprueben su cuota de una en una
+    #[instrument(skip(conn))]
+    pub fn lock_owner(user_id: i32, conn: &mut DbConnection) -> QueryResult<i32> {
+        users::table
+            .filter(users::id.eq(user_id))
+            .select(users::id)
+            .for_update()
+            .first::<i32>(conn)
+    }
+
+    /// Inserta el fichero y sus variantes en una única transacción
+    #[instrument(skip_all)]
+    pub fn create(new_media: &NewMedia, variants: Vec<NewMediaVariant>, conn: &mut DbConnection) -> QueryResult<Media> {
+        conn.transaction(|conn| {
+            let created = diesel::insert_into(media::table)
+                .values(new_media)
+                .get_result::<Media>(conn)?;
+
+            let variants: Vec<NewMediaVariant> = variants
+                .into_iter()
+                .map(|v| NewMediaVariant { media_id: created.id, ..v })
+                .collect();
+            diesel::insert_into(media_variants::table)
+                .values(&variants)
+                .execute(conn)?;
+
+            Ok(created)
+        })
+    }
+
+    #[instrument(skip(update, conn))]
+    pub fn update(media_id: i32, update: &UpdateMedia, conn: &mut DbConnection) -> QueryResult<Media> {
+        diesel::update(media::table.filter(media::id.eq(media_id)))
+            .set(update)
+            .get_result::<Media>(conn)
+    }
+
+    /// Elimina el fichero; sus variantes y asociaciones con posts caen en cascada
+    #[instrument(skip(conn))]
+    pub fn delete(media_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(media::table.filter(media::id.eq(media_id))).execute(conn)
+    }
+
+    /// Elimina todos los ficheros de un usuario; sus variantes, asociaciones
+    /// con posts y su uso como avatar o imagen destacada caen en cascada
+    #[instrument(skip(conn))]
+    pub fn delete_by_user(user_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(media::table.filter(media::user_id.eq(user_id))).execute(conn)
+    }
+
+    /// Ficheros asociados a un post, en el orden de su galería
+    #[instrument(skip(conn))]
+    pub fn find_by_post(post_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<Media>> {
+        post_media::table
+            .filter(post_media::post_id.eq(post_id))
+            .inner_join(media::table)
+            .select(media::all_columns)
+            .order((post_media::position.asc(), post_media::id.asc()))
+            .load::<Media>(conn)
+    }
+
+    /// Asocia un fichero a un post; si ya lo estaba, solo cambia su posición.
+    /// Sin posición explícita se añade al final de la galería.
+    #[instrument(skip(conn))]
+    pub fn attach(post_id: i32, media_id: i32, position: Option<i32>, conn: &mut DbConnection) -> QueryResult<usize> {
+        let position = match position {
+            Some(position) => position,
+            None => {
+                let last: Option<i32> = post_media::table
+                    .filter(post_media::post_id.eq(post_id))
+                    .select(diesel::dsl::max(post_media::position))
+                    .first(conn)?;
+                last.map_or(0, |p| p + 1)
+            }
+        };
+
+        diesel::insert_into(post_media::table)
+            .values(&NewPostMedia { post_id, media_id, position })
+            .on_conflict((post_media::post_id, post_media::media_id))
+            .do_update()
+            .set(post_media::position.eq(position))
+            .execute(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn detach(post_id: i32, media_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(
+            post_media::table
+                .filter(post_media::post_id.eq(post_id))
+                .filter(post_media::media_id.eq(media_id)),
+        )
+        .execute(conn)
+    }
+}
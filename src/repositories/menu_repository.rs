@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+use diesel::prelude::*;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::{categories, menu_items, menus, pages, posts};
+use crate::models::entities::{Menu, MenuItem, NewMenu, NewMenuItem, UpdateMenu, POST_STATUS_PUBLISHED};
+
+pub struct MenuRepository;
+
+impl MenuRepository {
+    #[instrument(skip_all)]
+    pub fn find_all(conn: &mut DbConnection) -> QueryResult<Vec<Menu>> {
+        menus::table.order(menus::location.asc()).load::<Menu>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_id(menu_id: i32, conn: &mut DbConnection) -> QueryResult<Menu> {
+        menus::table.filter(menus::id.eq(menu_id)).first::<Menu>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_location(menu_location: &str, conn: &mut DbConnection) -> QueryResult<Menu> {
+        menus::table.filter(menus::location.eq(menu_location)).first::<Menu>(conn)
+    }
+
+    /// Elementos del menú, de todos los niveles, en orden de presentación
+    #[instrument(skip(conn))]
+    pub fn find_items(menu_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<MenuItem>> {
+        menu_items::table
+            .filter(menu_items::menu_id.eq(menu_id))
+            .order((menu_items::position.asc(), menu_items::id.asc()))
+            .load::<MenuItem>(conn)
+    }
+
+    /// Título y ruta actuales de las páginas indicadas
+    #[instrument(skip(conn))]
+    pub fn page_links(page_ids: &[i32], conn: &mut DbConnection) -> QueryResult<HashMap<i32, (String, String)>> {
+        let rows = pages::table
+            .filter(pages::id.eq_any(page_ids))
+            .select((pages::id, pages::title, pages::path))
+            .load::<(i32, String, String)>(conn)?;
+
+        Ok(rows.into_iter().map(|(id, title, path)| (id, (title, path))).collect())
+    }
+
+    /// Título y slug actuales de los posts indicados; los no publicados se
+    /// omiten para que el menú no los enlace
+    #[instrument(skip(conn))]
+    pub fn post_links(post_ids: &[i32], conn: &mut DbConnection) -> QueryResult<HashMap<i32, (String, String)>> {
+        let rows = posts::table
+            .filter(posts::id.eq_any(post_ids))
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .select((posts::id, posts::title, posts::slug))
+            .load::<(i32, String, String)>(conn)?;
+
+        Ok(rows.into_iter().map(|(id, title, slug)| (id, (title, slug))).collect())
+    }
+
+    /// Nombre y slug actuales de las categorías indicadas
+    #[instrument(skip(conn))]
+    pub fn category_links(category_ids: &[i32], conn: &mut DbConnection) -> QueryResult<HashMap<i32, (String, String)>> {
+        let rows = categories::table
+            .filter(categories::id.eq_any(category_ids))
+            .select((categories::id, categories::name, categories::slug))
+            .load::<(i32, String, String)>(conn)?;
+
+        Ok(rows.into_iter().map(|(id, name, slug)| (id, (name, slug))).collect())
+    }
+
+    #[instrument(skip_all)]
+    pub fn create(new_menu: &NewMenu, conn: &mut DbConnection) -> QueryResult<Menu> {
+        diesel::insert_into(menus::table)
+            .values(new_menu)
+            .get_result::<Menu>(conn)
+    }
+
+    #[instrument(skip(menu_data, conn))]
+    pub fn update(menu_id: i32, menu_data: &UpdateMenu, conn: &mut DbConnection) -> QueryResult<Menu> {
+        diesel::update(menus::table.filter(menus::id.eq(menu_id)))
+            .set(menu_data)
+            .get_result::<Menu>(conn)
+    }
+
+    #[instrument(skip_all)]
+    pub fn create_item(new_item: &NewMenuItem, conn: &mut DbConnection) -> QueryResult<MenuItem> {
+        diesel::insert_into(menu_items::table)
+            .values(new_item)
+            .get_result::<MenuItem>(conn)
+    }
+
+    /// Elimina todos los elementos del menú
+    #[instrument(skip(conn))]
+    pub fn delete_items(menu_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(menu_items::table.filter(menu_items::menu_id.eq(menu_id))).execute(conn)
+    }
+
+    /// Elimina el menú y, en cascada, sus elementos
+    #[instrument(skip(conn))]
+    pub fn delete(menu_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(menus::table.filter(menus::id.eq(menu_id))).execute(conn)
+    }
+}
@@ -1,3 +1,29 @@
 pub mod post_repository;
+pub mod category_repository;
+pub mod tag_repository;
+pub mod media_repository;
+pub mod series_repository;
+pub mod slug_history_repository;
+pub mod redirect_repository;
+pub mod page_repository;
+pub mod menu_repository;
+pub mod site_setting_repository;
+pub mod import_key_repository;
+pub mod ban_repository;
+pub mod invitation_repository;
+pub mod api_token_repository;
 
-pub use post_repository::PostRepository; 
\ No newline at end of file
+pub use post_repository::PostRepository;
+pub use category_repository::CategoryRepository;
+pub use tag_repository::TagRepository;
+pub use media_repository::MediaRepository;
+pub use series_repository::SeriesRepository;
+pub use slug_history_repository::SlugHistoryRepository;
+pub use redirect_repository::RedirectRepository;
+pub use page_repository::PageRepository;
+pub use menu_repository::MenuRepository;
+pub use site_setting_repository::SiteSettingRepository;
+pub use import_key_repository::ImportKeyRepository;
+pub use ban_repository::BanRepository;
+pub use invitation_repository::InvitationRepository;
+pub use api_token_repository::ApiTokenRepository;
@@ -0,0 +1,99 @@
+use diesel::prelude::*;
+use diesel::sql_types::Text;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::pages;
+use crate::models::entities::{NewPage, Page, UpdatePage};
+
+pub struct PageRepository;
+
+impl PageRepository {
+    #[instrument(skip_all)]
+    pub fn find_all(conn: &mut DbConnection) -> QueryResult<Vec<Page>> {
+        pages::table
+            .order((pages::menu_order.asc(), pages::title.asc()))
+            .load::<Page>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_id(page_id: i32, conn: &mut DbConnection) -> QueryResult<Page> {
+        pages::table.filter(pages::id.eq(page_id)).first::<Page>(conn)
+    }
+
+    /// Lee la página bloqueándola con `FOR UPDATE`, para que su ruta no cambie
+    /// hasta que termine la transacción
+    #[instrument(skip(conn))]
+    pub fn lock_by_id(page_id: i32, conn: &mut DbConnection) -> QueryResult<Page> {
+        pages::table.filter(pages::id.eq(page_id)).for_update().first::<Page>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_path(page_path: &str, conn: &mut DbConnection) -> QueryResult<Page> {
+        pages::table.filter(pages::path.eq(page_path)).first::<Page>(conn)
+    }
+
+    /// Página hija de `parent_id` (o raíz, si es `None`) con el slug indicado
+    #[instrument(skip(conn))]
+    pub fn find_sibling(parent_id: Option<i32>, page_slug: &str, conn: &mut DbConnection) -> QueryResult<Option<Page>> {
+        let query = pages::table.filter(pages::slug.eq(page_slug)).into_boxed();
+        let query = match parent_id {
+            Some(parent) => query.filter(pages::parent_id.eq(parent)),
+            None => query.filter(pages::parent_id.is_null()),
+        };
+        query.first::<Page>(conn).optional()
+    }
+
+    /// Páginas desde la raíz hasta la de ruta `page_path` (incluida): son las que
+    /// tienen por ruta un prefijo de la suya
+    #[instrument(skip(conn))]
+    pub fn find_ancestors(page_path: &str, conn: &mut DbConnection) -> QueryResult<Vec<Page>> {
+        let segments: Vec<&str> = page_path.split('/').collect();
+        let prefixes: Vec<String> = (1..=segments.len()).map(|n| segments[..n].join("/")).collect();
+
+        let mut ancestors = pages::table
+            .filter(pages::path.eq_any(&prefixes))
+            .load::<Page>(conn)?;
+        ancestors.sort_by_key(|p| p.path.len());
+        Ok(ancestors)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn count_children(page_id: i32, conn: &mut DbConnection) -> QueryResult<i64> {
+        pages::table
+            .filter(pages::parent_id.eq(page_id))
+            .count()
+            .get_result(conn)
+    }
+
+    #[instrument(skip_all)]
+    pub fn create(new_page: &NewPage, conn: &mut DbConnection) -> QueryResult<Page> {
+        diesel::insert_into(pages::table)
+            .values(new_page)
+            .get_result::<Page>(conn)
+    }
+
+    #[instrument(skip(page_data, conn))]
+    pub fn update(page_id: i32, page_data: &UpdatePage, conn: &mut DbConnection) -> QueryResult<Page> {
+        diesel::update(pages::table.filter(pages::id.eq(page_id)))
+            .set(page_data)
+            .get_result::<Page>(conn)
+    }
+
+    /// Cambia el prefijo `old_path/` por `new_path/` en las rutas de todas las subpáginas.
+    /// Los slugs solo contienen `[a-z0-9-]`, así que no hay comodines de LIKE que escapar.
+    #[instrument(skip(conn))]
+    pub fn move_descendants(old_path: &str, new_path: &str, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::sql_query(
+            "UPDATE pages SET path = $2 || substr(path, length($1) + 1)
+            WHERE path LIKE $1 || '/%'",
+        )
+        .bind::<Text, _>(old_path)
+        .bind::<Text, _>(new_path)
+        .execute(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete(page_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(pages::table.filter(pages::id.eq(page_id))).execute(conn)
+    }
+}
@@ -2,65 +2,90 @@ use diesel::prelude::*;
 use diesel::RunQueryDsl;
 use diesel::QueryDsl;
 use diesel::ExpressionMethods;
-use chrono::Local;
-use crate::config::database::DbConnection;
+use chrono::{Local, NaiveDateTime};
+use crate::db::DbConnection;
 use crate::models::entities::{Post, NewPost, UpdatePost, NewPostCategory};
-use crate::models::entities::post_category::PostCategory;
-use crate::db::schema::{posts, post_categories};
-use crate::utils::slug::slugify;
+use crate::db::schema::{posts, post_categories, slug_history};
+use crate::models::entities::{POST_STATUS_PUBLISHED, SLUG_ENTITY_POST};
+use tracing::instrument;
 
 pub struct PostRepository;
 
 impl PostRepository {
+    /// Posts publicados, del más reciente al más antiguo
+    #[instrument(skip_all)]
+    pub fn find_published(conn: &mut DbConnection) -> QueryResult<Vec<Post>> {
+        use crate::db::schema::posts::dsl::*;
+        posts.filter(status.eq(POST_STATUS_PUBLISHED)).order(id.desc()).load::<Post>(conn)
+    }
+    
+    /// Una página de los posts publicados, en el mismo orden que `find_published`
+    #[instrument(skip(conn))]
+    pub fn find_published_page(limit: i64, offset: i64, conn: &mut DbConnection) -> QueryResult<Vec<Post>> {
+        use crate::db::schema::posts::dsl::*;
+        posts
+            .filter(status.eq(POST_STATUS_PUBLISHED))
+            .order(id.desc())
+            .limit(limit)
+            .offset(offset)
+            .load::<Post>(conn)
+    }
+    
+    /// Todos los posts, sea cual sea su estado, por orden de creación
+    #[instrument(skip_all)]
     pub fn find_all(conn: &mut DbConnection) -> QueryResult<Vec<Post>> {
         use crate::db::schema::posts::dsl::*;
-        posts.order(id.desc()).load::<Post>(conn)
+        posts.order((created_at.asc(), id.asc())).load::<Post>(conn)
     }
     
+    /// Posts publicados de un autor, del más reciente al más antiguo
+    #[instrument(skip(conn))]
+    pub fn find_published_by_author(user_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<Post>> {
+        use crate::db::schema::posts::dsl::*;
+        posts
+            .filter(author_id.eq(user_id))
+            .filter(status.eq(POST_STATUS_PUBLISHED))
+            .order(id.desc())
+            .load::<Post>(conn)
+    }
+    
+    /// Posts de un autor, sea cual sea su estado
+    #[instrument(skip(conn))]
+    pub fn find_by_author(user_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<Post>> {
+        use crate::db::schema::posts::dsl::*;
+        posts.filter(author_id.eq(user_id)).order(id.desc()).load::<Post>(conn)
+    }
+    
+    #[instrument(skip(conn))]
     pub fn find_by_id(post_id: i32, conn: &mut DbConnection) -> QueryResult<Post> {
         use crate::db::schema::posts::dsl::*;
         posts.filter(id.eq(post_id)).first::<Post>(conn)
     }
     
+    #[instrument(skip(conn))]
     pub fn find_by_slug(post_slug: &str, conn: &mut DbConnection) -> QueryResult<Post> {
         use crate::db::schema::posts::dsl::*;
         posts.filter(slug.eq(post_slug)).first::<Post>(conn)
     }
     
+    #[instrument(skip_all)]
     pub fn create(new_post: &NewPost, conn: &mut DbConnection) -> QueryResult<Post> {
         diesel::insert_into(posts::table)
             .values(new_post)
             .get_result(conn)
     }
     
+    #[instrument(skip_all)]
     pub fn create_with_categories(
-        title: &str, 
-        body: &str, 
-        slug_str: Option<&str>, 
+        new_post: &NewPost, 
         category_ids: &[i32], 
         conn: &mut DbConnection
     ) -> QueryResult<Post> {
-        // Generar slug si no se proporciona
-        let slug_value = match slug_str {
-            Some(s) => s.to_string(),
-            None => slugify(title),
-        };
-        
-        // Crear el post
-        let now = Local::now().naive_local();
-        let new_post = NewPost {
-            title: title.to_string(),
-            body: body.to_string(),
-            slug: slug_value,
-            created_at: Some(now),
-            updated_at: Some(now),
-        };
-        
         // Iniciar transacción
         conn.transaction(|conn| {
             // Insertar post
             let post = diesel::insert_into(posts::table)
-                .values(&new_post)
+                .values(new_post)
                 .get_result::<Post>(conn)?;
             
             // Insertar relaciones con categorías
@@ -79,6 +104,7 @@ impl PostRepository {
         })
     }
     
+    #[instrument(skip(post_data, conn))]
     pub fn update(post_id: i32, post_data: &UpdatePost, conn: &mut DbConnection) -> QueryResult<Post> {
         use crate::db::schema::posts::dsl::*;
         
@@ -90,32 +116,32 @@ impl PostRepository {
             .get_result::<Post>(conn)
     }
     
+    /// Fija las fechas de creación y modificación, p. ej. al importar contenido
+    #[instrument(skip(conn))]
+    pub fn set_dates(
+        post_id: i32,
+        created: NaiveDateTime,
+        updated: NaiveDateTime,
+        conn: &mut DbConnection
+    ) -> QueryResult<Post> {
+        use crate::db::schema::posts::dsl::*;
+        
+        diesel::update(posts.filter(id.eq(post_id)))
+            .set((created_at.eq(created), updated_at.eq(updated)))
+            .get_result::<Post>(conn)
+    }
+    
+    #[instrument(skip(post_data, conn))]
     pub fn update_with_categories(
         post_id: i32, 
-        title_opt: Option<&str>, 
-        body_opt: Option<&str>, 
-        slug_opt: Option<&str>, 
+        post_data: &UpdatePost, 
         category_ids_opt: Option<&[i32]>, 
         conn: &mut DbConnection
     ) -> QueryResult<Post> {
         // Iniciar transacción
         conn.transaction(|conn| {
-            // Preparar datos de actualización
-            let mut update_data = UpdatePost {
-                title: title_opt.map(|s| s.to_string()),
-                body: body_opt.map(|s| s.to_string()),
-                slug: slug_opt.map(|s| s.to_string()),
-                updated_at: Some(Local::now().naive_local()),
-            };
-            
-            // Si se proporciona título pero no slug, generar slug
-            if update_data.title.is_some() && update_data.slug.is_none() {
-                let title = update_data.title.as_ref().unwrap();
-                update_data.slug = Some(slugify(title));
-            }
-            
             // Actualizar post
-            let updated_post = Self::update(post_id, &update_data, conn)?;
+            let updated_post = Self::update(post_id, post_data, conn)?;
             
             // Si se proporcionan categorías, actualizar relaciones
             if let Some(category_ids) = category_ids_opt {
@@ -140,6 +166,7 @@ impl PostRepository {
         })
     }
     
+    #[instrument(skip(conn))]
     pub fn delete(post_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
         use crate::db::schema::posts::dsl::*;
         
@@ -149,6 +176,14 @@ impl PostRepository {
             diesel::delete(post_categories::table.filter(post_categories::post_id.eq(post_id)))
                 .execute(conn)?;
             
+            // Eliminar los slugs antiguos, que ya no tienen a dónde redirigir
+            diesel::delete(
+                slug_history::table
+                    .filter(slug_history::entity_type.eq(SLUG_ENTITY_POST))
+                    .filter(slug_history::entity_id.eq(post_id)),
+            )
+            .execute(conn)?;
+            
             // Eliminar post
             let result = diesel::delete(posts.filter(id.eq(post_id))).execute(conn)?;
             
@@ -156,6 +191,7 @@ impl PostRepository {
         })
     }
     
+    #[instrument(skip(conn))]
     pub fn get_categories(post_id_param: i32, conn: &mut DbConnection) -> QueryResult<Vec<crate::models::entities::Category>> {
         use crate::db::schema::categories::dsl::*;
         use crate::db::schema::post_categories::dsl::{post_categories, post_id};
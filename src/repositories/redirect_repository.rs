@@ -0,0 +1,46 @@
+use diesel::prelude::*;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::redirects;
+use crate::models::entities::{NewRedirect, Redirect, UpdateRedirect};
+
+pub struct RedirectRepository;
+
+impl RedirectRepository {
+    #[instrument(skip_all)]
+    pub fn find_all(conn: &mut DbConnection) -> QueryResult<Vec<Redirect>> {
+        redirects::table.order(redirects::source_path.asc()).load::<Redirect>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_id(redirect_id: i32, conn: &mut DbConnection) -> QueryResult<Redirect> {
+        redirects::table.filter(redirects::id.eq(redirect_id)).first::<Redirect>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_source(path: &str, conn: &mut DbConnection) -> QueryResult<Option<Redirect>> {
+        redirects::table
+            .filter(redirects::source_path.eq(path))
+            .first::<Redirect>(conn)
+            .optional()
+    }
+
+    #[instrument(skip_all)]
+    pub fn create(new_redirect: &NewRedirect, conn: &mut DbConnection) -> QueryResult<Redirect> {
+        diesel::insert_into(redirects::table)
+            .values(new_redirect)
+            .get_result::<Redirect>(conn)
+    }
+
+    #[instrument(skip(update, conn))]
+    pub fn update(redirect_id: i32, update: &UpdateRedirect, conn: &mut DbConnection) -> QueryResult<Redirect> {
+        diesel::update(redirects::table.filter(redirects::id.eq(redirect_id)))
+            .set(update)
+            .get_result::<Redirect>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete(redirect_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(redirects::table.filter(redirects::id.eq(redirect_id))).execute(conn)
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use diesel::prelude::*;
+use diesel::dsl::count;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::{posts, series, series_posts};
+use crate::models::entities::{NewSeries, NewSeriesPost, Post, Series, UpdateSeries, POST_STATUS_PUBLISHED};
+
+pub struct SeriesRepository;
+
+impl SeriesRepository {
+    #[instrument(skip_all)]
+    pub fn find_all(conn: &mut DbConnection) -> QueryResult<Vec<Series>> {
+        series::table.order(series::title.asc()).load::<Series>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_id(series_id: i32, conn: &mut DbConnection) -> QueryResult<Series> {
+        series::table.filter(series::id.eq(series_id)).first::<Series>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_slug(series_slug: &str, conn: &mut DbConnection) -> QueryResult<Series> {
+        series::table.filter(series::slug.eq(series_slug)).first::<Series>(conn)
+    }
+
+    /// Serie a la que pertenece un post, si pertenece a alguna
+    #[instrument(skip(conn))]
+    pub fn find_by_post(post_id: i32, conn: &mut DbConnection) -> QueryResult<Option<Series>> {
+        series_posts::table
+            .filter(series_posts::post_id.eq(post_id))
+            .inner_join(series::table)
+            .select(series::all_columns)
+            .first::<Series>(conn)
+            .optional()
+    }
+
+    /// Posts publicados de una serie en orden de lectura
+    #[instrument(skip(conn))]
+    pub fn find_posts(series_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<Post>> {
+        series_posts::table
+            .filter(series_posts::series_id.eq(series_id))
+            .inner_join(posts::table)
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .select(posts::all_columns)
+            .order((series_posts::position.asc(), series_posts::id.asc()))
+            .load::<Post>(conn)
+    }
+
+    /// Número de posts publicados de cada serie; las series sin ninguno no aparecen
+    #[instrument(skip_all)]
+    pub fn count_posts_by_series(conn: &mut DbConnection) -> QueryResult<HashMap<i32, i64>> {
+        let counts = series_posts::table
+            .inner_join(posts::table)
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .group_by(series_posts::series_id)
+            .select((series_posts::series_id, count(series_posts::id)))
+            .load::<(i32, i64)>(conn)?;
+
+        Ok(counts.into_iter().collect())
+    }
+
+    /// Pares (post, serie) de los posts indicados que ya pertenecen a alguna serie
+    #[instrument(skip(conn))]
+    pub fn find_memberships(post_ids: &[i32], conn: &mut DbConnection) -> QueryResult<Vec<(i32, i32)>> {
+        series_posts::table
+            .filter(series_posts::post_id.eq_any(post_ids))
+            .select((series_posts::post_id, series_posts::series_id))
+            .load::<(i32, i32)>(conn)
+    }
+
+    #[instrument(skip_all)]
+    pub fn create(new_series: &NewSeries, conn: &mut DbConnection) -> QueryResult<Series> {
+        diesel::insert_into(series::table)
+            .values(new_series)
+            .get_result::<Series>(conn)
+    }
+
+    #[instrument(skip(series_data, conn))]
+    pub fn update(series_id: i32, series_data: &UpdateSeries, conn: &mut DbConnection) -> QueryResult<Series> {
+        diesel::update(series::table.filter(series::id.eq(series_id)))
+            .set(series_data)
+            .get_result::<Series>(conn)
+    }
+
+    /// Sustituye los posts de la serie por `post_ids`, en ese orden, en una única transacción
+    #[instrument(skip(conn))]
+    pub fn set_posts(series_id: i32, post_ids: &[i32], conn: &mut DbConnection) -> QueryResult<()> {
+        conn.transaction(|conn| {
+            diesel::delete(series_posts::table.filter(series_posts::series_id.eq(series_id)))
+                .execute(conn)?;
+
+            let rows: Vec<NewSeriesPost> = post_ids
+                .iter()
+                .enumerate()
+                .map(|(position, &post_id)| NewSeriesPost {
+                    series_id,
+                    post_id,
+                    position: position as i32,
+                })
+                .collect();
+            diesel::insert_into(series_posts::table)
+                .values(&rows)
+                .execute(conn)?;
+
+            Ok(())
+        })
+    }
+
+    /// Elimina la serie; sus posts se conservan y solo pierden la pertenencia
+    #[instrument(skip(conn))]
+    pub fn delete(series_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(series::table.filter(series::id.eq(series_id))).execute(conn)
+    }
+}
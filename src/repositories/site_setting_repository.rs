@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use serde_json::Value;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::site_settings;
+use crate::models::entities::SiteSetting;
+
+pub struct SiteSettingRepository;
+
+impl SiteSettingRepository {
+    #[instrument(skip_all)]
+    pub fn find_all(conn: &mut DbConnection) -> QueryResult<Vec<SiteSetting>> {
+        site_settings::table.load::<SiteSetting>(conn)
+    }
+
+    /// Guarda el valor de una clave, creándola si no existía
+    #[instrument(skip(value, conn))]
+    pub fn upsert(key: &str, value: &Value, now: NaiveDateTime, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::insert_into(site_settings::table)
+            .values((
+                site_settings::key.eq(key),
+                site_settings::value.eq(value),
+                site_settings::updated_at.eq(Some(now)),
+            ))
+            .on_conflict(site_settings::key)
+            .do_update()
+            .set((
+                site_settings::value.eq(excluded(site_settings::value)),
+                site_settings::updated_at.eq(excluded(site_settings::updated_at)),
+            ))
+            .execute(conn)
+    }
+}
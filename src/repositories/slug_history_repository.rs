@@ -0,0 +1,83 @@
+use chrono::Local;
+use diesel::prelude::*;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::slug_history;
+use crate::models::entities::{NewSlugHistory, SlugHistory};
+
+pub struct SlugHistoryRepository;
+
+impl SlugHistoryRepository {
+    /// Busca un slug antiguo de un tipo de entidad (`post`, `category`)
+    #[instrument(skip(conn))]
+    pub fn find(entity_type: &str, old_slug: &str, conn: &mut DbConnection) -> QueryResult<Option<SlugHistory>> {
+        slug_history::table
+            .filter(slug_history::entity_type.eq(entity_type))
+            .filter(slug_history::slug.eq(old_slug))
+            .first::<SlugHistory>(conn)
+            .optional()
+    }
+
+    /// Registra el cambio de slug de una entidad: `old_slug` pasa al historial
+    /// y `new_slug` deja de estarlo, porque vuelve a ser un slug en uso.
+    /// Si `old_slug` ya apuntaba a otra entidad, ahora apunta a esta.
+    #[instrument(skip(conn))]
+    pub fn record_rename(
+        entity_type: &str,
+        entity_id: i32,
+        old_slug: &str,
+        new_slug: &str,
+        conn: &mut DbConnection,
+    ) -> QueryResult<()> {
+        Self::release(entity_type, new_slug, conn)?;
+
+        let now = Local::now().naive_local();
+        diesel::insert_into(slug_history::table)
+            .values(&NewSlugHistory {
+                entity_type: entity_type.to_string(),
+                entity_id,
+                slug: old_slug.to_string(),
+                created_at: Some(now),
+            })
+            .on_conflict((slug_history::entity_type, slug_history::slug))
+            .do_update()
+            .set((slug_history::entity_id.eq(entity_id), slug_history::created_at.eq(Some(now))))
+            .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// Quita un slug del historial, p. ej. porque una entidad nueva lo usa
+    #[instrument(skip(conn))]
+    pub fn release(entity_type: &str, slug: &str, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(
+            slug_history::table
+                .filter(slug_history::entity_type.eq(entity_type))
+                .filter(slug_history::slug.eq(slug)),
+        )
+        .execute(conn)
+    }
+
+    /// Traspasa los slugs antiguos de una entidad a otra (al fusionar categorías)
+    #[instrument(skip(conn))]
+    pub fn reassign(entity_type: &str, from_id: i32, to_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::update(
+            slug_history::table
+                .filter(slug_history::entity_type.eq(entity_type))
+                .filter(slug_history::entity_id.eq(from_id)),
+        )
+        .set(slug_history::entity_id.eq(to_id))
+        .execute(conn)
+    }
+
+    /// Elimina el historial de una entidad que se borra
+    #[instrument(skip(conn))]
+    pub fn delete_for(entity_type: &str, entity_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        diesel::delete(
+            slug_history::table
+                .filter(slug_history::entity_type.eq(entity_type))
+                .filter(slug_history::entity_id.eq(entity_id)),
+        )
+        .execute(conn)
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use diesel::prelude::*;
+use diesel::dsl::count;
+use chrono::Local;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::{post_tags, posts, tags};
+use crate::models::entities::{NewPostTag, NewTag, Post, Tag, UpdateTag, POST_STATUS_PUBLISHED};
+use crate::utils::slug::slugify;
+
+pub struct TagRepository;
+
+impl TagRepository {
+    #[instrument(skip(conn))]
+    pub fn find_by_id(tag_id: i32, conn: &mut DbConnection) -> QueryResult<Tag> {
+        tags::table.filter(tags::id.eq(tag_id)).first::<Tag>(conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn find_by_slug(tag_slug: &str, conn: &mut DbConnection) -> QueryResult<Tag> {
+        tags::table.filter(tags::slug.eq(tag_slug)).first::<Tag>(conn)
+    }
+
+    /// Devuelve todas las etiquetas con el número de posts publicados que las usan
+    #[instrument(skip_all)]
+    pub fn find_all_with_counts(conn: &mut DbConnection) -> QueryResult<Vec<(Tag, i64)>> {
+        let counts: HashMap<i32, i64> = post_tags::table
+            .inner_join(posts::table)
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .group_by(post_tags::tag_id)
+            .select((post_tags::tag_id, count(post_tags::id)))
+            .load::<(i32, i64)>(conn)?
+            .into_iter()
+            .collect();
+
+        let all_tags = tags::table.order(tags::name.asc()).load::<Tag>(conn)?;
+        Ok(all_tags
+            .into_iter()
+            .map(|tag| {
+                let post_count = counts.get(&tag.id).copied().unwrap_or(0);
+                (tag, post_count)
+            })
+            .collect())
+    }
+
+    /// Devuelve las etiquetas de un post
+    #[instrument(skip(conn))]
+    pub fn find_by_post(post_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<Tag>> {
+        post_tags::table
+            .filter(post_tags::post_id.eq(post_id))
+            .inner_join(tags::table)
+            .select(tags::all_columns)
+            .order(tags::name.asc())
+            .load::<Tag>(conn)
+    }
+
+    /// Devuelve los posts publicados que tienen una etiqueta
+    #[instrument(skip(conn))]
+    pub fn find_posts(tag_id: i32, conn: &mut DbConnection) -> QueryResult<Vec<Post>> {
+        post_tags::table
+            .filter(post_tags::tag_id.eq(tag_id))
+            .inner_join(posts::table)
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .select(posts::all_columns)
+            .order(posts::id.desc())
+            .load::<Post>(conn)
+    }
+
+    /// Busca las etiquetas por nombre y crea las que no existen. Los nombres se
+    /// identifican por su slug, así que "Rust" y "rust" son la misma etiqueta.
+    #[instrument(skip(conn))]
+    pub fn find_or_create_by_names(names: &[String], conn: &mut DbConnection) -> QueryResult<Vec<Tag>> {
+        let mut result: Vec<Tag> = Vec::new();
+
+        for name in names {
+            let name = name.trim();
+            if name.is_empty() {
+                continue;
+            }
+
+            let tag_slug = slugify(name);
+            if result.iter().any(|t| t.slug == tag_slug) {
+                continue;
+            }
+
+            // ON CONFLICT evita el error si otra petición crea la misma etiqueta a la vez
+            let now = Local::now().naive_local();
+            diesel::insert_into(tags::table)
+                .values(&NewTag {
+                    name: name.to_string(),
+                    slug: tag_slug.clone(),
+                    created_at: Some(now),
+                    updated_at: Some(now),
+                })
+                .on_conflict(tags::slug)
+                .do_nothing()
+                .execute(conn)?;
+
+            result.push(Self::find_by_slug(&tag_slug, conn)?);
+        }
+
+        Ok(result)
+    }
+
+    /// Reemplaza las etiquetas de un post por las indicadas
+    #[instrument(skip(conn))]
+    pub fn set_post_tags(post_id: i32, names: &[String], conn: &mut DbConnection) -> QueryResult<Vec<Tag>> {
+        conn.transaction(|conn| {
+            let tags_found = Self::find_or_create_by_names(names, conn)?;
+
+            diesel::delete(post_tags::table.filter(post_tags::post_id.eq(post_id)))
+                .execute(conn)?;
+
+            let new_post_tags: Vec<NewPostTag> = tags_found
+                .iter()
+                .map(|tag| NewPostTag { post_id, tag_id: tag.id })
+                .collect();
+
+            diesel::insert_into(post_tags::table)
+                .values(&new_post_tags)
+                .execute(conn)?;
+
+            Ok(tags_found)
+        })
+    }
+
+    #[instrument(skip(update_data, conn))]
+    pub fn update(tag_id: i32, update_data: &UpdateTag, conn: &mut DbConnection) -> QueryResult<Tag> {
+        diesel::update(tags::table.filter(tags::id.eq(tag_id)))
+            .set(update_data)
+            .get_result::<Tag>(conn)
+    }
+
+    /// Mueve todas las asociaciones de `source_id` a `target_id` y elimina la etiqueta origen
+    #[instrument(skip(conn))]
+    pub fn merge(source_id: i32, target_id: i32, conn: &mut DbConnection) -> QueryResult<usize> {
+        conn.transaction(|conn| {
+            // Posts que ya tienen la etiqueta destino: basta con borrar la asociación origen
+            let already_tagged: Vec<i32> = post_tags::table
+                .filter(post_tags::tag_id.eq(target_id))
+                .select(post_tags::post_id)
+                .load(conn)?;
+
+            diesel::delete(
+                post_tags::table
+                    .filter(post_tags::tag_id.eq(source_id))
+                    .filter(post_tags::post_id.eq_any(already_tagged)),
+            )
+            .execute(conn)?;
+
+            let moved = diesel::update(post_tags::table.filter(post_tags::tag_id.eq(source_id)))
+                .set(post_tags::tag_id.eq(target_id))
+                .execute(conn)?;
+
+            diesel::delete(tags::table.filter(tags::id.eq(source_id))).execute(conn)?;
+
+            Ok(moved)
+        })
+    }
+}
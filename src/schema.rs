@@ -1,70 +0,0 @@
-// @generated automatically by Diesel CLI.
-
-diesel::table! {
-    categories (id) {
-        id -> Int4,
-        name -> Varchar,
-        slug -> Varchar,
-        description -> Nullable<Text>,
-        created_at -> Nullable<Timestamp>,
-        updated_at -> Nullable<Timestamp>,
-    }
-}
-
-diesel::table! {
-    comments (id) {
-        id -> Int4,
-        post_id -> Int4,
-        user_id -> Nullable<Int4>,
-        author_name -> Nullable<Varchar>,
-        author_email -> Nullable<Varchar>,
-        content -> Text,
-        created_at -> Nullable<Timestamp>,
-        updated_at -> Nullable<Timestamp>,
-    }
-}
-
-diesel::table! {
-    post_categories (id) {
-        id -> Int4,
-        post_id -> Int4,
-        category_id -> Int4,
-    }
-}
-
-diesel::table! {
-    posts (id) {
-        id -> Int4,
-        title -> Varchar,
-        slug -> Varchar,
-        body -> Text,
-        created_at -> Nullable<Timestamp>,
-        updated_at -> Nullable<Timestamp>,
-    }
-}
-
-diesel::table! {
-    users (id) {
-        id -> Int4,
-        username -> Varchar,
-        email -> Varchar,
-        password_hash -> Varchar,
-        created_at -> Nullable<Timestamp>,
-        updated_at -> Nullable<Timestamp>,
-        #[max_length = 50]
-        role -> Varchar,
-    }
-}
-
-diesel::joinable!(comments -> posts (post_id));
-diesel::joinable!(comments -> users (user_id));
-diesel::joinable!(post_categories -> categories (category_id));
-diesel::joinable!(post_categories -> posts (post_id));
-
-diesel::allow_tables_to_appear_in_same_query!(
-    categories,
-    comments,
-    post_categories,
-    posts,
-    users,
-);
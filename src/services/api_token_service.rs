@@ -0,0 +1,140 @@
+use chrono::{Local, NaiveDateTime};
+use tracing::instrument;
+use uuid::Uuid;
+use crate::auth::{hash_token, Claims};
+use crate::db::DbConnection;
+use crate::models::dto::{ApiTokenDto, CreateApiTokenDto};
+use crate::models::entities::{ApiToken, NewApiToken, API_TOKEN_SCOPES};
+use crate::repositories::ApiTokenRepository;
+use crate::services::UserService;
+use crate::utils::error::{AppError, AppResult};
+
+/// Prefijo de los tokens personales, para distinguirlos de un JWT a simple vista
+const TOKEN_PREFIX: &str = "brt_";
+/// Caracteres del token que se guardan en claro para reconocerlo
+const VISIBLE_PREFIX_LENGTH: usize = 12;
+const MAX_NAME_LENGTH: usize = 100;
+const MAX_TOKENS_PER_USER: i64 = 50;
+
+/// Tokens personales de acceso a la API, para scripts e integraciones
+pub struct ApiTokenService;
+
+impl ApiTokenService {
+    #[instrument(skip(conn))]
+    pub fn get_user_tokens(user_id: i32, conn: &mut DbConnection) -> AppResult<Vec<ApiTokenDto>> {
+        let tokens = ApiTokenRepository::find_by_user(user_id, conn)?;
+        let now = Local::now().naive_local();
+        Ok(tokens.into_iter().map(|token| Self::to_dto(token, None, now)).collect())
+    }
+
+    /// Crea un token y lo devuelve en claro; no vuelve a mostrarse
+    #[instrument(skip(dto, conn))]
+    pub fn create_token(user_id: i32, dto: CreateApiTokenDto, conn: &mut DbConnection) -> AppResult<ApiTokenDto> {
+        let name = dto.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "El nombre del token debe tener entre 1 y {} caracteres",
+                MAX_NAME_LENGTH
+            )));
+        }
+
+        let mut scopes = Vec::new();
+        for scope in dto.scopes {
+            let scope = scope.trim().to_lowercase();
+            if !API_TOKEN_SCOPES.contains(&scope.as_str()) {
+                return Err(AppError::ValidationError(format!(
+                    "Permiso '{}' no válido; usa {}",
+                    scope,
+                    API_TOKEN_SCOPES.join(", ")
+                )));
+            }
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        if scopes.is_empty() {
+            return Err(AppError::ValidationError("El token debe tener al menos un permiso".to_string()));
+        }
+        scopes.sort();
+
+        let now = Local::now().naive_local();
+        if dto.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(AppError::ValidationError("La fecha de caducidad debe ser futura".to_string()));
+        }
+        if ApiTokenRepository::count_unrevoked_by_user(user_id, conn)? >= MAX_TOKENS_PER_USER {
+            return Err(AppError::ValidationError(format!(
+                "No se pueden tener más de {} tokens; revoca alguno antes",
+                MAX_TOKENS_PER_USER
+            )));
+        }
+
+        let token = format!("{}{}", TOKEN_PREFIX, Uuid::new_v4().simple());
+        let api_token = ApiTokenRepository::create(
+            &NewApiToken {
+                user_id,
+                name,
+                token_prefix: token[..VISIBLE_PREFIX_LENGTH].to_string(),
+                token_hash: hash_token(&token),
+                scopes,
+                expires_at: dto.expires_at,
+                created_at: Some(now),
+            },
+            conn,
+        )?;
+
+        tracing::info!(token_id = api_token.id, user_id, "Token personal creado");
+        Ok(Self::to_dto(api_token, Some(token), now))
+    }
+
+    /// Revoca un token del usuario. El token se conserva en el listado.
+    #[instrument(skip(conn))]
+    pub fn revoke_token(token_id: i32, user_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        if ApiTokenRepository::revoke(token_id, user_id, Local::now().naive_local(), conn)? == 0 {
+            return Err(AppError::NotFound(format!("Token con ID {} no encontrado o ya revocado", token_id)));
+        }
+        tracing::info!(token_id, user_id, "Token personal revocado");
+        Ok(())
+    }
+
+    /// Claims de un token personal: los del usuario, con su rol actual, limitados
+    /// a los permisos del token. Anota el uso del token.
+    #[instrument(skip_all)]
+    pub fn authenticate(token: &str, conn: &mut DbConnection) -> AppResult<Claims> {
+        let now = Local::now().naive_local();
+        let api_token = ApiTokenRepository::touch(&hash_token(token), now, conn)?
+            .ok_or_else(|| AppError::AuthenticationError("Token inválido, caducado o revocado".to_string()))?;
+        let user = UserService::check_active(api_token.user_id, conn)?;
+
+        Ok(Claims {
+            sub: user.id.to_string(),
+            exp: api_token.expires_at.map_or(i64::MAX, |expires_at| expires_at.and_utc().timestamp()),
+            iat: api_token.created_at.unwrap_or(now).and_utc().timestamp(),
+            role: user.role,
+            username: user.username,
+            scopes: Some(api_token.scopes),
+        })
+    }
+
+    fn to_dto(api_token: ApiToken, token: Option<String>, now: NaiveDateTime) -> ApiTokenDto {
+        let status = if api_token.revoked_at.is_some() {
+            "revoked"
+        } else if api_token.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            "expired"
+        } else {
+            "active"
+        };
+
+        ApiTokenDto {
+            id: api_token.id,
+            name: api_token.name,
+            token,
+            token_prefix: api_token.token_prefix,
+            scopes: api_token.scopes,
+            expires_at: api_token.expires_at,
+            last_used_at: api_token.last_used_at,
+            revoked_at: api_token.revoked_at,
+            status: status.to_string(),
+            created_at: api_token.created_at,
+        }
+    }
+}
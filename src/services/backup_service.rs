@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+use chrono::Local;
+use diesel::prelude::*;
+use diesel::result::Error as DieselError;
+use tracing::instrument;
+use uuid::Uuid;
+use crate::auth::hash_password;
+use crate::db::{lower, DbConnection};
+use crate::db::schema::{categories, comments, post_categories, posts, users};
+use crate::models::entities::{
+    Category, Comment, NewCategory, NewComment, NewPost, NewPostCategory, NewUser, Post, UpdateProfile, User, SLUG_ENTITY_CATEGORY,
+    SLUG_ENTITY_POST,
+};
+use crate::repositories::{PostRepository, SlugHistoryRepository};
+use crate::utils::backup::{
+    self, Archive, BackupCategory, BackupComment, BackupHeader, BackupPost, BackupPostCategory, BackupUser,
+    BACKUP_FORMAT, BACKUP_VERSION,
+};
+use crate::utils::error::{AppError, AppResult};
+
+/// Entidades creadas (o que se crearían, en una simulación) al restaurar
+#[derive(Default, Debug)]
+pub struct RestoreReport {
+    pub users: usize,
+    /// Usuarios de la copia asociados a una cuenta existente con el mismo email
+    pub existing_users: usize,
+    /// Usuarios creados con una contraseña aleatoria, porque la copia no la incluía
+    pub users_without_password: usize,
+    pub categories: usize,
+    pub posts: usize,
+    pub post_categories: usize,
+    pub comments: usize,
+}
+
+/// Motivo por el que se deshace la transacción de la restauración
+enum Rollback {
+    Failed(AppError),
+    DryRun(RestoreReport),
+}
+
+impl From<DieselError> for Rollback {
+    fn from(e: DieselError) -> Self {
+        Rollback::Failed(AppError::DatabaseError(e))
+    }
+}
+
+/// Correspondencia entre los IDs de la copia y los nuevos
+#[derive(Default)]
+struct IdMap {
+    users: HashMap<i32, i32>,
+    categories: HashMap<i32, i32>,
+    posts: HashMap<i32, i32>,
+    comments: HashMap<i32, i32>,
+}
+
+pub struct BackupService;
+
+impl BackupService {
+    /// Copia de usuarios, categorías, posts, sus categorías y comentarios. Los
+    /// hashes de las contraseñas solo se incluyen con `include_password_hashes`.
+    #[instrument(skip(conn))]
+    pub fn export(include_password_hashes: bool, conn: &mut DbConnection) -> AppResult<Archive> {
+        let archive_users = users::table
+            .order(users::id.asc())
+            .load::<User>(conn)?
+            .into_iter()
+            .map(|user| BackupUser {
+                id: user.id,
+                username: user.username,
+                email: user.email,
+                password_hash: include_password_hashes.then_some(user.password_hash),
+                role: user.role,
+                display_name: user.display_name,
+                bio: user.bio,
+                website: user.website,
+                social_links: Some(user.social_links),
+                created_at: user.created_at,
+                updated_at: user.updated_at,
+            })
+            .collect();
+
+        let archive_categories = categories::table
+            .order(categories::id.asc())
+            .load::<Category>(conn)?
+            .into_iter()
+            .map(|category| BackupCategory {
+                id: category.id,
+                name: category.name,
+                slug: category.slug,
+                description: category.description,
+                parent_id: category.parent_id,
+                position: category.position,
+                created_at: category.created_at,
+                updated_at: category.updated_at,
+            })
+            .collect();
+
+        let archive_posts = posts::table
+            .order(posts::id.asc())
+            .load::<Post>(conn)?
+            .into_iter()
+            .map(|post| BackupPost {
+                id: post.id,
+                title: post.title,
+                slug: post.slug,
+                body: post.body,
+                excerpt: post.excerpt,
+                featured_image_url: post.featured_image_url,
+                author_id: post.author_id,
+                status: post.status,
+                created_at: post.created_at,
+                updated_at: post.updated_at,
+            })
+            .collect();
+
+        let archive_post_categories = post_categories::table
+            .select((post_categories::post_id, post_categories::category_id))
+            .order((post_categories::post_id.asc(), post_categories::category_id.asc()))
+            .load::<(i32, i32)>(conn)?
+            .into_iter()
+            .map(|(post_id, category_id)| BackupPostCategory { post_id, category_id })
+            .collect();
+
+        let archive_comments = comments::table
+            .order(comments::id.asc())
+            .load::<Comment>(conn)?
+            .into_iter()
+            .map(|comment| BackupComment {
+                id: comment.id,
+                post_id: comment.post_id,
+                user_id: comment.user_id,
+                parent_id: comment.parent_id,
+                author_name: comment.author_name,
+                author_email: comment.author_email,
+                content: comment.content,
+                created_at: comment.created_at,
+                updated_at: comment.updated_at,
+            })
+            .collect();
+
+        Ok(Archive {
+            header: BackupHeader {
+                format: BACKUP_FORMAT.to_string(),
+                version: BACKUP_VERSION,
+                created_at: Local::now().naive_local(),
+                includes_password_hashes: include_password_hashes,
+            },
+            users: archive_users,
+            categories: archive_categories,
+            posts: archive_posts,
+            post_categories: archive_post_categories,
+            comments: archive_comments,
+        })
+    }
+
+    /// Restaura una copia en una única transacción, asignando IDs nuevos. Los
+    /// usuarios cuyo email ya existe (sin distinguir mayúsculas, como en
+    /// `backup::validate`) se asocian a esa cuenta; una categoría o un
+    /// post cuyo slug ya existe deshace toda la restauración.
+    ///
+    /// Con `dry_run` se hace todo igual pero la transacción se deshace al final,
+    /// así que sirve para comprobar la copia contra la base de datos actual.
+    #[instrument(skip(archive, conn))]
+    pub fn restore(archive: &Archive, dry_run: bool, conn: &mut DbConnection) -> AppResult<RestoreReport> {
+        let problems = backup::validate(archive);
+        if !problems.is_empty() {
+            return Err(AppError::ValidationError(format!(
+                "La copia tiene {} problemas de integridad:\n- {}",
+                problems.len(),
+                problems.join("\n- ")
+            )));
+        }
+
+        let result = conn.transaction::<_, Rollback, _>(|conn| {
+            let report = Self::restore_all(archive, conn).map_err(Rollback::Failed)?;
+            if dry_run {
+                Err(Rollback::DryRun(report))
+            } else {
+                Ok(report)
+            }
+        });
+
+        match result {
+            Ok(report) | Err(Rollback::DryRun(report)) => Ok(report),
+            Err(Rollback::Failed(e)) => Err(e),
+        }
+    }
+
+    fn restore_all(archive: &Archive, conn: &mut DbConnection) -> AppResult<RestoreReport> {
+        let mut report = RestoreReport::default();
+        let mut ids = IdMap::default();
+
+        for user in &archive.users {
+            let existing = users::table
+                .filter(lower(users::email.nullable()).eq(user.email.to_lowercase()))
+                .select(users::id)
+                .first::<i32>(conn)
+                .optional()?;
+            if let Some(user_id) = existing {
+                ids.users.insert(user.id, user_id);
+                report.existing_users += 1;
+                continue;
+            }
+
+            let username_taken = users::table
+                .filter(users::username.eq(&user.username))
+                .select(users::id)
+                .first::<i32>(conn)
+                .optional()?
+                .is_some();
+            if username_taken {
+                return Err(AppError::ValidationError(format!(
+                    "Ya existe un usuario con el nombre '{}' y otro email",
+                    user.username
+                )));
+            }
+
+            let password_hash = match user.password_hash {
+                Some(ref hash) => hash.clone(),
+                None => {
+                    report.users_without_password += 1;
+                    hash_password(&Uuid::new_v4().to_string())?
+                }
+            };
+            let user_id = diesel::insert_into(users::table)
+                .values(&NewUser {
+                    username: user.username.clone(),
+                    email: user.email.clone(),
+                    password_hash,
+                    created_at: user.created_at,
+                    updated_at: user.updated_at,
+                    role: user.role.clone(),
+                })
+                .returning(users::id)
+                .get_result::<i32>(conn)?;
+            diesel::update(users::table.find(user_id))
+                .set(&UpdateProfile {
+                    display_name: Some(user.display_name.clone()),
+                    bio: Some(user.bio.clone()),
+                    website: Some(user.website.clone()),
+                    social_links: user.social_links.clone(),
+                    ..Default::default()
+                })
+                .execute(conn)?;
+            ids.users.insert(user.id, user_id);
+            report.users += 1;
+        }
+
+        // Las categorías se crean sin padre y la jerarquía se enlaza después,
+        // porque un padre puede aparecer en la copia detrás de sus hijas
+        for category in &archive.categories {
+            let slug_taken = categories::table
+                .filter(categories::slug.eq(&category.slug))
+                .select(categories::id)
+                .first::<i32>(conn)
+                .optional()?
+                .is_some();
+            if slug_taken {
+                return Err(AppError::ValidationError(format!("Ya existe una categoría con el slug '{}'", category.slug)));
+            }
+
+            let category_id = diesel::insert_into(categories::table)
+                .values(&NewCategory {
+                    name: category.name.clone(),
+                    slug: category.slug.clone(),
+                    description: category.description.clone(),
+                    created_at: category.created_at,
+                    updated_at: category.updated_at,
+                    parent_id: None,
+                    position: category.position,
+                })
+                .returning(categories::id)
+                .get_result::<i32>(conn)?;
+            SlugHistoryRepository::release(SLUG_ENTITY_CATEGORY, &category.slug, conn)?;
+            ids.categories.insert(category.id, category_id);
+            report.categories += 1;
+        }
+        for category in &archive.categories {
+            if let Some(parent) = category.parent_id {
+                diesel::update(categories::table.find(ids.categories[&category.id]))
+                    .set(categories::parent_id.eq(ids.categories[&parent]))
+                    .execute(conn)?;
+            }
+        }
+
+        for post in &archive.posts {
+            match PostRepository::find_by_slug(&post.slug, conn) {
+                Ok(_) => return Err(AppError::ValidationError(format!("Ya existe un post con el slug '{}'", post.slug))),
+                Err(DieselError::NotFound) => {}
+                Err(e) => return Err(AppError::DatabaseError(e)),
+            }
+
+            let new_post = NewPost {
+                title: post.title.clone(),
+                slug: post.slug.clone(),
+                body: post.body.clone(),
+                created_at: post.created_at,
+                updated_at: post.updated_at,
+                excerpt: post.excerpt.clone(),
+                featured_media_id: None,
+                featured_image_url: post.featured_image_url.clone(),
+                author_id: post.author_id.map(|id| ids.users[&id]),
+                status: post.status.clone(),
+            };
+            let created = PostRepository::create(&new_post, conn)?;
+            SlugHistoryRepository::release(SLUG_ENTITY_POST, &created.slug, conn)?;
+            ids.posts.insert(post.id, created.id);
+            report.posts += 1;
+        }
+
+        for link in &archive.post_categories {
+            diesel::insert_into(post_categories::table)
+                .values(&NewPostCategory {
+                    post_id: ids.posts[&link.post_id],
+                    category_id: ids.categories[&link.category_id],
+                })
+                .execute(conn)?;
+            report.post_categories += 1;
+        }
+
+        // Igual que las categorías: primero los comentarios y después las respuestas
+        for comment in &archive.comments {
+            let comment_id = diesel::insert_into(comments::table)
+                .values(&NewComment {
+                    post_id: ids.posts[&comment.post_id],
+                    user_id: comment.user_id.map(|id| ids.users[&id]),
+                    author_name: comment.author_name.clone(),
+                    author_email: comment.author_email.clone(),
+                    content: comment.content.clone(),
+                    created_at: comment.created_at,
+                    updated_at: comment.updated_at,
+                    parent_id: None,
+                })
+                .returning(comments::id)
+                .get_result::<i32>(conn)?;
+            ids.comments.insert(comment.id, comment_id);
+            report.comments += 1;
+        }
+        for comment in &archive.comments {
+            if let Some(parent) = comment.parent_id {
+                diesel::update(comments::table.find(ids.comments[&comment.id]))
+                    .set(comments::parent_id.eq(ids.comments[&parent]))
+                    .execute(conn)?;
+            }
+        }
+
+        Ok(report)
+    }
+}
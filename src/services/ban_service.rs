@@ -0,0 +1,138 @@
+use std::net::{IpAddr, SocketAddr};
+use chrono::Local;
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use tracing::instrument;
+use crate::db::{lower, DbConnection};
+use crate::db::schema::users;
+use crate::models::dto::{BanDto, CreateBanDto};
+use crate::models::entities::{Ban, NewBan, User, BAN_KIND_EMAIL, BAN_KIND_IP};
+use crate::repositories::BanRepository;
+use crate::services::UserService;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::validation::is_valid_email;
+
+/// Emails e IPs que no pueden comentar
+pub struct BanService;
+
+impl BanService {
+    #[instrument(skip_all)]
+    pub fn get_all_bans(conn: &mut DbConnection) -> AppResult<Vec<BanDto>> {
+        let bans = BanRepository::find_all(conn)?;
+        Ok(bans.into_iter().map(Self::to_dto).collect())
+    }
+
+    #[instrument(skip(dto, conn))]
+    pub fn create_ban(dto: CreateBanDto, acting_user_id: i32, conn: &mut DbConnection) -> AppResult<BanDto> {
+        let value = match dto.kind.as_str() {
+            BAN_KIND_EMAIL => Self::normalize_email(&dto.value)
+                .ok_or_else(|| AppError::ValidationError(format!("El email '{}' no es válido", dto.value.trim())))?,
+            BAN_KIND_IP => Self::normalize_ip(&dto.value)
+                .ok_or_else(|| AppError::ValidationError(format!("La IP '{}' no es válida", dto.value.trim())))?,
+            other => {
+                return Err(AppError::ValidationError(format!(
+                    "Tipo de bloqueo '{}' no válido; usa '{}' o '{}'",
+                    other, BAN_KIND_EMAIL, BAN_KIND_IP
+                )))
+            }
+        };
+
+        let now = Local::now().naive_local();
+        if dto.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(AppError::ValidationError("La fecha de fin del bloqueo debe ser futura".to_string()));
+        }
+        let already_banned = || AppError::ValidationError(format!("'{}' ya está bloqueado", value));
+
+        // Un bloqueo caducado del mismo valor se sustituye por el nuevo
+        let ban = conn.transaction::<_, AppError, _>(|conn| {
+            if BanRepository::find_active(&dto.kind, &value, now, conn)?.is_some() {
+                return Err(already_banned());
+            }
+            BanRepository::delete_expired(&dto.kind, &value, now, conn)?;
+
+            BanRepository::create(
+                &NewBan {
+                    kind: dto.kind.clone(),
+                    value: value.clone(),
+                    reason: dto.reason.as_deref().map(str::trim).filter(|r| !r.is_empty()).map(str::to_string),
+                    expires_at: dto.expires_at,
+                    created_by: Some(acting_user_id),
+                    created_at: Some(now),
+                },
+                conn,
+            )
+            .map_err(|e| match e {
+                // Otra petición acaba de bloquear el mismo valor
+                DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => already_banned(),
+                e => AppError::DatabaseError(e),
+            })
+        })?;
+
+        tracing::info!(ban_id = ban.id, kind = %ban.kind, acting_user_id, "Bloqueo creado");
+        Ok(Self::to_dto(ban))
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete_ban(ban_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        if BanRepository::delete(ban_id, conn)? == 0 {
+            return Err(AppError::NotFound(format!("Bloqueo con ID {} no encontrado", ban_id)));
+        }
+        Ok(())
+    }
+
+    /// Impide comentar a un email o una IP bloqueados, y con el email de una
+    /// cuenta suspendida, que de otro modo podría seguir comentando sin sesión
+    #[instrument(skip_all)]
+    pub fn check_commenter(email: Option<&str>, ip: Option<&str>, conn: &mut DbConnection) -> AppResult<()> {
+        let now = Local::now().naive_local();
+        let blocked = || AppError::AuthorizationError("No puedes comentar en este sitio".to_string());
+
+        if let Some(email) = email.and_then(Self::normalize_email) {
+            if BanRepository::find_active(BAN_KIND_EMAIL, &email, now, conn)?.is_some() {
+                return Err(blocked());
+            }
+
+            let accounts = users::table
+                .filter(lower(users::email.nullable()).eq(&email))
+                .load::<User>(conn)?;
+            if accounts.iter().any(UserService::is_suspended) {
+                return Err(blocked());
+            }
+        }
+
+        if let Some(ip) = ip.and_then(Self::normalize_ip) {
+            if BanRepository::find_active(BAN_KIND_IP, &ip, now, conn)?.is_some() {
+                return Err(blocked());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn normalize_email(email: &str) -> Option<String> {
+        let email = email.trim().to_lowercase();
+        is_valid_email(&email).then_some(email)
+    }
+
+    /// Forma canónica de la IP, para que `::1` y `0:0:0:0:0:0:0:1` coincidan; se
+    /// admite también con puerto, como la puede dar un proxy
+    fn normalize_ip(ip: &str) -> Option<String> {
+        let ip = ip.trim();
+        ip.parse::<IpAddr>()
+            .or_else(|_| ip.parse::<SocketAddr>().map(|addr| addr.ip()))
+            .ok()
+            .map(|ip| ip.to_string())
+    }
+
+    fn to_dto(ban: Ban) -> BanDto {
+        BanDto {
+            id: ban.id,
+            kind: ban.kind,
+            value: ban.value,
+            reason: ban.reason,
+            expires_at: ban.expires_at,
+            created_by: ban.created_by,
+            created_at: ban.created_at,
+        }
+    }
+}
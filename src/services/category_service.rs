@@ -1,88 +1,132 @@
 use chrono::Local;
 use diesel::result::Error as DieselError;
-use diesel::prelude::*;
 use diesel::RunQueryDsl;
 use diesel::QueryDsl;
 use diesel::ExpressionMethods;
 use diesel::Connection;
-use crate::config::database::DbConnection;
-use crate::models::entities::{Category, NewCategory, UpdateCategory};
-use crate::models::dto::{CategoryDto, CreateCategoryDto, UpdateCategoryDto};
+use diesel::OptionalExtension;
+use std::collections::{HashMap, HashSet};
+use crate::db::DbConnection;
+use crate::db::schema::post_categories;
+use crate::models::entities::{Category, NewCategory, UpdateCategory, SLUG_ENTITY_CATEGORY};
+use crate::models::dto::{
+    CategoryBreadcrumbDto, CategoryDto, CategoryTreeDto, CreateCategoryDto, PostDto, UpdateCategoryDto,
+};
+use crate::repositories::{CategoryRepository, SlugHistoryRepository};
+use crate::services::{PostService, SlugLookup};
 use crate::utils::error::{AppError, AppResult};
-use crate::utils::slug::slugify;
+use crate::utils::slug::{explicit_slug_error, slugify, with_unique_slug_in_history};
+use tracing::instrument;
 
 pub struct CategoryService;
 
 impl CategoryService {
     /// Obtiene todas las categorías
+    #[instrument(skip_all)]
     pub fn get_all_categories(conn: &mut DbConnection) -> AppResult<Vec<CategoryDto>> {
-        use crate::db::schema::categories::dsl::*;
+        let categories_result = CategoryRepository::find_all(conn)
+            .map_err(AppError::DatabaseError)?;
+        let post_counts = CategoryRepository::count_posts_by_category(conn)
+            .map_err(AppError::DatabaseError)?;
         
-        let categories_result = categories
-            .order(name.asc())
-            .load::<Category>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+        // Con todas las categorías en memoria, las rutas se calculan sin más consultas
+        let by_id: HashMap<i32, &Category> = categories_result.iter().map(|c| (c.id, c)).collect();
+        let breadcrumbs: Vec<Vec<CategoryBreadcrumbDto>> = categories_result.iter()
+            .map(|category| Self::breadcrumbs_from_map(category, &by_id))
+            .collect();
         
         // Convertir entidades a DTOs
         let category_dtos = categories_result.into_iter()
-            .map(|category| CategoryDto {
-                id: category.id,
-                name: category.name,
-                slug: category.slug,
-                description: category.description,
-                created_at: category.created_at,
-                updated_at: category.updated_at,
+            .zip(breadcrumbs)
+            .map(|(category, breadcrumbs)| {
+                let post_count = post_counts.get(&category.id).copied().unwrap_or(0);
+                Self::to_dto(category, breadcrumbs, post_count)
             })
             .collect();
         
         Ok(category_dtos)
     }
     
+    /// Obtiene el árbol completo de categorías
+    #[instrument(skip_all)]
+    pub fn get_category_tree(conn: &mut DbConnection) -> AppResult<Vec<CategoryTreeDto>> {
+        let categories_result = CategoryRepository::find_all(conn)
+            .map_err(AppError::DatabaseError)?;
+        
+        // Agrupar por padre; las categorías cuyo padre no existe se tratan como raíces
+        let ids: HashSet<i32> = categories_result.iter().map(|c| c.id).collect();
+        let mut children: HashMap<Option<i32>, Vec<Category>> = HashMap::new();
+        for category in categories_result {
+            let parent = category.parent_id.filter(|p| ids.contains(p));
+            children.entry(parent).or_default().push(category);
+        }
+        
+        Ok(Self::build_tree(None, &mut children))
+    }
+    
     /// Obtiene una categoría por su ID
+    #[instrument(skip(conn))]
     pub fn get_category_by_id(category_id: i32, conn: &mut DbConnection) -> AppResult<CategoryDto> {
+        let category = Self::find_category(category_id, conn)?;
+        
+        Self::load_dto(category, conn)
+    }
+    
+    /// Obtiene una categoría por su slug
+    #[instrument(skip(conn))]
+    pub fn get_category_by_slug(category_slug: &str, conn: &mut DbConnection) -> AppResult<SlugLookup<CategoryDto>> {
         use crate::db::schema::categories::dsl::*;
         
-        let category = categories
-            .filter(id.eq(category_id))
-            .first::<Category>(conn)
-            .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Categoría con ID {} no encontrada", category_id)),
-                _ => AppError::DatabaseError(e)
-            })?;
+        match categories.filter(slug.eq(category_slug)).first::<Category>(conn) {
+            Ok(category) => return Ok(SlugLookup::Found(Self::load_dto(category, conn)?)),
+            Err(DieselError::NotFound) => {}
+            Err(e) => return Err(AppError::DatabaseError(e)),
+        }
         
-        Ok(CategoryDto {
-            id: category.id,
-            name: category.name,
-            slug: category.slug,
-            description: category.description,
-            created_at: category.created_at,
-            updated_at: category.updated_at,
-        })
+        // Un slug antiguo redirige al actual
+        match SlugHistoryRepository::find(SLUG_ENTITY_CATEGORY, category_slug, conn)? {
+            Some(entry) => {
+                let category = Self::find_category(entry.entity_id, conn)?;
+                Ok(SlugLookup::Moved(category.slug))
+            }
+            None => Err(AppError::NotFound(format!("Categoría con slug '{}' no encontrada", category_slug))),
+        }
     }
     
-    /// Obtiene una categoría por su slug
-    pub fn get_category_by_slug(category_slug: &str, conn: &mut DbConnection) -> AppResult<CategoryDto> {
+    /// ID de la categoría con ese slug o que lo tuvo antes de renombrarse; las
+    /// importaciones lo usan para no duplicar una categoría renombrada
+    #[instrument(skip(conn))]
+    pub fn find_id_by_slug(category_slug: &str, conn: &mut DbConnection) -> AppResult<Option<i32>> {
         use crate::db::schema::categories::dsl::*;
         
-        let category = categories
+        let current = categories
             .filter(slug.eq(category_slug))
-            .first::<Category>(conn)
-            .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Categoría con slug '{}' no encontrada", category_slug)),
-                _ => AppError::DatabaseError(e)
-            })?;
+            .select(id)
+            .first::<i32>(conn)
+            .optional()?;
+        match current {
+            Some(category_id) => Ok(Some(category_id)),
+            None => Ok(SlugHistoryRepository::find(SLUG_ENTITY_CATEGORY, category_slug, conn)?.map(|entry| entry.entity_id)),
+        }
+    }
+    
+    /// Obtiene los posts de una categoría y, opcionalmente, de todas sus subcategorías
+    #[instrument(skip(conn))]
+    pub fn get_category_posts(category_id: i32, include_descendants: bool, conn: &mut DbConnection) -> AppResult<Vec<PostDto>> {
+        Self::find_category(category_id, conn)?;
         
-        Ok(CategoryDto {
-            id: category.id,
-            name: category.name,
-            slug: category.slug,
-            description: category.description,
-            created_at: category.created_at,
-            updated_at: category.updated_at,
-        })
+        let posts = if include_descendants {
+            CategoryRepository::find_posts_in_tree(category_id, conn)
+        } else {
+            CategoryRepository::find_posts(category_id, conn)
+        }
+        .map_err(AppError::DatabaseError)?;
+        
+        PostService::to_dtos(posts, conn)
     }
     
     /// Crea una nueva categoría
+    #[instrument(skip(dto, conn))]
     pub fn create_category(dto: CreateCategoryDto, conn: &mut DbConnection) -> AppResult<CategoryDto> {
         use crate::db::schema::categories;
         
@@ -91,131 +135,197 @@ impl CategoryService {
             return Err(AppError::ValidationError("El nombre no puede estar vacío".to_string()));
         }
         
-        // Generar slug si no se proporciona
-        let slug_str = match dto.slug {
-            Some(s) if !s.trim().is_empty() => s,
-            _ => slugify(&dto.name),
+        // Verificar que la categoría padre existe
+        if let Some(parent) = dto.parent_id {
+            Self::find_parent(parent, conn)?;
+        }
+        
+        // Generar slug si no se proporciona; si el generado ya existe se le añade un sufijo
+        let (slug_str, auto_slug) = match dto.slug {
+            Some(s) if !s.trim().is_empty() => (s, false),
+            _ => (slugify(&dto.name), true),
         };
         
-        // Verificar si ya existe una categoría con el mismo slug
+        // Verificar si ya existe una categoría con el slug indicado
         use crate::db::schema::categories::dsl::*;
-        let slug_exists = categories
-            .filter(slug.eq(&slug_str))
-            .first::<Category>(conn)
-            .is_ok();
-        
-        if slug_exists {
-            return Err(AppError::ValidationError(format!("Ya existe una categoría con el slug '{}'", slug_str)));
+        if !auto_slug {
+            let slug_exists = categories
+                .filter(slug.eq(&slug_str))
+                .first::<Category>(conn)
+                .is_ok();
+            
+            if slug_exists {
+                return Err(AppError::ValidationError(format!("Ya existe una categoría con el slug '{}'", slug_str)));
+            }
         }
         
         // Crear la categoría
         let now = Local::now().naive_local();
-        let new_category = NewCategory {
+        let mut new_category = NewCategory {
             name: dto.name,
-            slug: slug_str,
+            slug: slug_str.clone(),
             description: dto.description,
             created_at: Some(now),
             updated_at: Some(now),
+            parent_id: dto.parent_id,
+            position: dto.position.unwrap_or(0),
         };
         
-        // Insertar la categoría
-        let category = diesel::insert_into(categories::table)
-            .values(&new_category)
-            .get_result::<Category>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+        // Insertar la categoría; si el slug indicado era uno antiguo de otra categoría,
+        // deja de redirigir (los generados nunca lo son)
+        let category = conn.transaction::<_, AppError, _>(|conn| {
+            let mut insert = |candidate: &str, conn: &mut DbConnection| -> AppResult<Category> {
+                new_category.slug = candidate.to_string();
+                Ok(diesel::insert_into(categories::table)
+                    .values(&new_category)
+                    .get_result::<Category>(conn)?)
+            };
+            let category = if auto_slug {
+                with_unique_slug_in_history(SLUG_ENTITY_CATEGORY, None, &slug_str, conn, insert)?
+            } else {
+                insert(&slug_str, conn).map_err(|e| explicit_slug_error(e, &slug_str))?
+            };
+            SlugHistoryRepository::release(SLUG_ENTITY_CATEGORY, &category.slug, conn)?;
+            Ok(category)
+        })?;
         
-        Ok(CategoryDto {
-            id: category.id,
-            name: category.name,
-            slug: category.slug,
-            description: category.description,
-            created_at: category.created_at,
-            updated_at: category.updated_at,
-        })
+        Self::load_dto(category, conn)
     }
     
     /// Actualiza una categoría existente
+    #[instrument(skip(dto, conn))]
     pub fn update_category(category_id: i32, dto: UpdateCategoryDto, conn: &mut DbConnection) -> AppResult<CategoryDto> {
         use crate::db::schema::categories::dsl::*;
         
         // Verificar que la categoría existe
-        let category = categories
-            .filter(id.eq(category_id))
-            .first::<Category>(conn)
-            .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Categoría con ID {} no encontrada", category_id)),
-                _ => AppError::DatabaseError(e)
-            })?;
-        
-        // Verificar si se está cambiando el slug y si el nuevo slug ya existe
-        if let Some(ref new_slug) = dto.slug {
-            if new_slug != &category.slug {
-                let slug_exists = categories
-                    .filter(slug.eq(new_slug))
-                    .first::<Category>(conn)
-                    .is_ok();
-                
-                if slug_exists {
-                    return Err(AppError::ValidationError(format!("Ya existe una categoría con el slug '{}'", new_slug)));
-                }
-            }
-        }
+        let category = Self::find_category(category_id, conn)?;
         
-        // Generar slug si se proporciona un nuevo nombre pero no un nuevo slug
-        let slug_value = match &dto.name {
-            Some(new_name) if dto.slug.is_none() => Some(slugify(new_name)),
-            _ => dto.slug.clone()
+        // El slug solo cambia si se indica uno o se pide regenerarlo a partir del nombre,
+        // para no romper los enlaces existentes. El regenerado se desambigua con un sufijo.
+        let (new_slug, auto_slug) = match dto.slug {
+            Some(ref s) if !s.trim().is_empty() => (Some(s.clone()), false),
+            _ if dto.regenerate_slug == Some(true) => {
+                (Some(slugify(dto.name.as_deref().unwrap_or(&category.name))), true)
+            }
+            _ => (None, false),
         };
+        let new_slug = new_slug.filter(|s| s != &category.slug);
+        
+        // Verificar que el slug indicado no esté en uso
+        if let Some(new_slug) = new_slug.as_ref().filter(|_| !auto_slug) {
+            let slug_exists = categories
+                .filter(slug.eq(new_slug))
+                .first::<Category>(conn)
+                .is_ok();
+            
+            if slug_exists {
+                return Err(AppError::ValidationError(format!("Ya existe una categoría con el slug '{}'", new_slug)));
+            }
+        }
         
         // Preparar datos para actualización
-        let update_data = UpdateCategory {
+        let mut update_data = UpdateCategory {
             name: dto.name,
-            slug: slug_value,
+            slug: new_slug.clone(),
             description: dto.description,
             updated_at: Some(Local::now().naive_local()),
+            parent_id: dto.parent_id,
+            position: dto.position,
         };
         
-        // Actualizar la categoría
-        let updated_category = diesel::update(categories.filter(id.eq(category_id)))
-            .set(&update_data)
-            .get_result::<Category>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
-        
-        Ok(CategoryDto {
-            id: updated_category.id,
-            name: updated_category.name,
-            slug: updated_category.slug,
-            description: updated_category.description,
-            created_at: updated_category.created_at,
-            updated_at: updated_category.updated_at,
-        })
+        // Actualizar la categoría; el slug anterior queda en el historial para redirigir
+        let updated_category = conn.transaction::<_, AppError, _>(|conn| {
+            // Evitar ciclos: el nuevo padre no puede ser la propia categoría ni una de
+            // sus subcategorías. La cadena del nuevo padre queda bloqueada hasta el
+            // final, para que otra edición simultánea no cierre un ciclo.
+            if let Some(Some(new_parent)) = update_data.parent_id {
+                Self::find_parent(new_parent, conn)?;
+
+                let ancestors = CategoryRepository::lock_ancestor_ids(new_parent, conn)
+                    .map_err(AppError::DatabaseError)?;
+                if ancestors.contains(&category_id) {
+                    return Err(AppError::ValidationError(
+                        "Una categoría no puede ser hija de sí misma ni de una de sus subcategorías".to_string()
+                    ));
+                }
+            }
+
+            let save = |update_data: &UpdateCategory, conn: &mut DbConnection| -> AppResult<Category> {
+                Ok(diesel::update(categories.filter(id.eq(category_id)))
+                    .set(update_data)
+                    .get_result::<Category>(conn)?)
+            };
+            let updated_category = match new_slug {
+                Some(ref base) if auto_slug => {
+                    with_unique_slug_in_history(SLUG_ENTITY_CATEGORY, Some(category_id), base, conn, |candidate, conn| {
+                        update_data.slug = Some(candidate.to_string());
+                        save(&update_data, conn)
+                    })?
+                }
+                _ => save(&update_data, conn)
+                    .map_err(|e| explicit_slug_error(e, update_data.slug.as_deref().unwrap_or_default()))?,
+            };
+            if updated_category.slug != category.slug {
+                SlugHistoryRepository::record_rename(
+                    SLUG_ENTITY_CATEGORY, category_id, &category.slug, &updated_category.slug, conn,
+                )?;
+            }
+            Ok(updated_category)
+        })?;
+        
+        Self::load_dto(updated_category, conn)
     }
     
-    /// Elimina una categoría
-    pub fn delete_category(category_id: i32, conn: &mut DbConnection) -> AppResult<()> {
-        use crate::db::schema::categories::dsl::*;
-        use crate::db::schema::post_categories;
-        
-        // Verificar que la categoría existe
-        let _ = categories
-            .filter(id.eq(category_id))
-            .first::<Category>(conn)
-            .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Categoría con ID {} no encontrada", category_id)),
-                _ => AppError::DatabaseError(e)
-            })?;
+    /// Elimina una categoría.
+    ///
+    /// Con `reassign_to` sus posts pasan a la categoría indicada. Sin él, la operación
+    /// se rechaza si algún post quedaría sin categorías, salvo que se fuerce con `force`.
+    #[instrument(skip(conn))]
+    pub fn delete_category(category_id: i32, reassign_to: Option<i32>, force: bool, conn: &mut DbConnection) -> AppResult<()> {
+        if reassign_to == Some(category_id) {
+            return Err(AppError::ValidationError("No se pueden reasignar los posts a la categoría que se elimina".to_string()));
+        }
         
         // Iniciar transacción
         conn.transaction(|conn| {
-            // Eliminar relaciones con posts
-            diesel::delete(post_categories::table.filter(post_categories::category_id.eq(category_id)))
-                .execute(conn)
-                .map_err(|e| AppError::DatabaseError(e))?;
+            // Verificar que la categoría existe y bloquearla, para que no se le asocien
+            // posts entre la comprobación y el borrado
+            CategoryRepository::lock_by_id(category_id, conn)
+                .map_err(|e| match e {
+                    DieselError::NotFound => AppError::NotFound(format!("Categoría con ID {} no encontrada", category_id)),
+                    _ => AppError::DatabaseError(e)
+                })?;
             
-            // Eliminar la categoría
-            let deleted = diesel::delete(categories.filter(id.eq(category_id)))
-                .execute(conn)
-                .map_err(|e| AppError::DatabaseError(e))?;
+            match reassign_to {
+                Some(target_id) => {
+                    Self::find_target(target_id, conn)?;
+                    CategoryRepository::move_posts(category_id, target_id, conn)
+                        .map_err(AppError::DatabaseError)?;
+                }
+                None => {
+                    if !force {
+                        let orphaned = CategoryRepository::count_exclusive_posts(category_id, conn)
+                            .map_err(AppError::DatabaseError)?;
+                        if orphaned > 0 {
+                            return Err(AppError::ValidationError(format!(
+                                "{} post(s) quedarían sin categoría; indica reassign_to o usa force=true",
+                                orphaned
+                            )));
+                        }
+                    }
+                    
+                    // Eliminar relaciones con posts
+                    diesel::delete(post_categories::table.filter(post_categories::category_id.eq(category_id)))
+                        .execute(conn)
+                        .map_err(AppError::DatabaseError)?;
+                }
+            }
+            
+            SlugHistoryRepository::delete_for(SLUG_ENTITY_CATEGORY, category_id, conn)?;
+            
+            // Eliminar la categoría (las subcategorías pasan a ser raíces por ON DELETE SET NULL)
+            let deleted = CategoryRepository::delete(category_id, conn)
+                .map_err(AppError::DatabaseError)?;
             
             if deleted == 0 {
                 return Err(AppError::NotFound(format!("No se pudo eliminar la categoría con ID {}", category_id)));
@@ -224,4 +334,135 @@ impl CategoryService {
             Ok(())
         })
     }
-} 
\ No newline at end of file
+    
+    /// Fusiona una categoría en otra: sus posts y subcategorías pasan a la categoría
+    /// destino y la categoría origen se elimina
+    #[instrument(skip(conn))]
+    pub fn merge_categories(source_id: i32, target_id: i32, conn: &mut DbConnection) -> AppResult<CategoryDto> {
+        if source_id == target_id {
+            return Err(AppError::ValidationError("No se puede fusionar una categoría consigo misma".to_string()));
+        }
+        
+        conn.transaction::<_, AppError, _>(|conn| {
+            // El origen queda bloqueado, para que no se le asocien posts ni
+            // subcategorías entre el traspaso y el borrado
+            let source = CategoryRepository::lock_by_id(source_id, conn)
+                .map_err(|e| match e {
+                    DieselError::NotFound => AppError::NotFound(format!("Categoría con ID {} no encontrada", source_id)),
+                    _ => AppError::DatabaseError(e)
+                })?;
+            let target = Self::find_category(target_id, conn)?;
+            
+            // Las subcategorías del origen se cuelgan del destino, que por tanto no puede
+            // estar debajo del origen. La cadena del destino queda bloqueada hasta el final,
+            // para que otra edición simultánea no lo mueva debajo del origen.
+            let ancestors = CategoryRepository::lock_ancestor_ids(target_id, conn)
+                .map_err(AppError::DatabaseError)?;
+            if ancestors.contains(&source_id) {
+                return Err(AppError::ValidationError(
+                    "No se puede fusionar una categoría en una de sus subcategorías".to_string()
+                ));
+            }
+            
+            CategoryRepository::move_posts(source_id, target_id, conn)?;
+            CategoryRepository::reparent_children(source_id, Some(target_id), conn)?;
+            // Los enlaces a la categoría origen, actuales y antiguos, llevan ahora a la destino
+            SlugHistoryRepository::reassign(SLUG_ENTITY_CATEGORY, source_id, target_id, conn)?;
+            SlugHistoryRepository::record_rename(SLUG_ENTITY_CATEGORY, target_id, &source.slug, &target.slug, conn)?;
+            CategoryRepository::delete(source_id, conn)?;
+            Ok(())
+        })?;
+        
+        Self::get_category_by_id(target_id, conn)
+    }
+    
+    /// Ruta desde la raíz hasta la categoría, obtenida con una consulta recursiva
+    pub fn breadcrumbs(category_id: i32, conn: &mut DbConnection) -> AppResult<Vec<CategoryBreadcrumbDto>> {
+        let ancestors = CategoryRepository::find_ancestors(category_id, conn)
+            .map_err(AppError::DatabaseError)?;
+        
+        Ok(ancestors.into_iter()
+            .map(|c| CategoryBreadcrumbDto { id: c.id, name: c.name, slug: c.slug })
+            .collect())
+    }
+    
+    /// Construye el DTO completo de una categoría, con su ruta y su número de posts
+    pub fn load_dto(category: Category, conn: &mut DbConnection) -> AppResult<CategoryDto> {
+        let breadcrumbs = Self::breadcrumbs(category.id, conn)?;
+        let post_count = CategoryRepository::count_posts(category.id, conn)
+            .map_err(AppError::DatabaseError)?;
+        
+        Ok(Self::to_dto(category, breadcrumbs, post_count))
+    }
+    
+    pub fn to_dto(category: Category, breadcrumbs: Vec<CategoryBreadcrumbDto>, post_count: i64) -> CategoryDto {
+        CategoryDto {
+            id: category.id,
+            name: category.name,
+            slug: category.slug,
+            description: category.description,
+            created_at: category.created_at,
+            updated_at: category.updated_at,
+            parent_id: category.parent_id,
+            position: category.position,
+            post_count,
+            breadcrumbs,
+        }
+    }
+    
+    fn find_category(category_id: i32, conn: &mut DbConnection) -> AppResult<Category> {
+        CategoryRepository::find_by_id(category_id, conn)
+            .map_err(|e| match e {
+                DieselError::NotFound => AppError::NotFound(format!("Categoría con ID {} no encontrada", category_id)),
+                _ => AppError::DatabaseError(e)
+            })
+    }
+    
+    fn find_parent(parent_id: i32, conn: &mut DbConnection) -> AppResult<Category> {
+        CategoryRepository::find_by_id(parent_id, conn)
+            .map_err(|e| match e {
+                DieselError::NotFound => AppError::ValidationError(format!("La categoría padre con ID {} no existe", parent_id)),
+                _ => AppError::DatabaseError(e)
+            })
+    }
+    
+    fn find_target(target_id: i32, conn: &mut DbConnection) -> AppResult<Category> {
+        CategoryRepository::find_by_id(target_id, conn)
+            .map_err(|e| match e {
+                DieselError::NotFound => AppError::ValidationError(format!("La categoría destino con ID {} no existe", target_id)),
+                _ => AppError::DatabaseError(e)
+            })
+    }
+    
+    fn breadcrumbs_from_map(category: &Category, by_id: &HashMap<i32, &Category>) -> Vec<CategoryBreadcrumbDto> {
+        let mut path = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = Some(category);
+        
+        while let Some(c) = current {
+            if !visited.insert(c.id) {
+                break;
+            }
+            path.push(CategoryBreadcrumbDto { id: c.id, name: c.name.clone(), slug: c.slug.clone() });
+            current = c.parent_id.and_then(|p| by_id.get(&p).copied());
+        }
+        
+        path.reverse();
+        path
+    }
+    
+    fn build_tree(parent: Option<i32>, children: &mut HashMap<Option<i32>, Vec<Category>>) -> Vec<CategoryTreeDto> {
+        let nodes = children.remove(&parent).unwrap_or_default();
+        
+        nodes.into_iter()
+            .map(|category| CategoryTreeDto {
+                children: Self::build_tree(Some(category.id), children),
+                id: category.id,
+                name: category.name,
+                slug: category.slug,
+                description: category.description,
+                position: category.position,
+            })
+            .collect()
+    }
+}
@@ -1,25 +1,32 @@
-use chrono::Local;
+use chrono::{Duration, Local};
 use diesel::result::Error as DieselError;
-use diesel::prelude::*;
 use diesel::RunQueryDsl;
 use diesel::QueryDsl;
 use diesel::ExpressionMethods;
-use crate::config::database::DbConnection;
-use crate::models::entities::{Comment, NewComment, UpdateComment};
+use diesel::OptionalExtension;
+use crate::db::DbConnection;
+use crate::models::entities::{Comment, NewComment, UpdateComment, POST_STATUS_PUBLISHED};
 use crate::models::dto::{CommentDto, CreateCommentDto, UpdateCommentDto};
+use crate::services::{BanService, SiteSettingsService};
 use crate::utils::error::{AppError, AppResult};
+use crate::metrics;
+use tracing::instrument;
 
 pub struct CommentService;
 
 impl CommentService {
-    /// Obtiene todos los comentarios
+    /// Obtiene todos los comentarios de los posts publicados
+    #[instrument(skip_all)]
     pub fn get_all_comments(conn: &mut DbConnection) -> AppResult<Vec<CommentDto>> {
-        use crate::db::schema::comments::dsl::*;
+        use crate::db::schema::{comments, posts};
         
-        let comments_result = comments
-            .order(created_at.desc())
+        let comments_result = comments::table
+            .inner_join(posts::table)
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .order(comments::created_at.desc())
+            .select(comments::all_columns)
             .load::<Comment>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+            .map_err(AppError::DatabaseError)?;
         
         // Convertir entidades a DTOs
         let comment_dtos = comments_result.into_iter()
@@ -27,6 +34,7 @@ impl CommentService {
                 id: comment.id,
                 post_id: comment.post_id,
                 user_id: comment.user_id,
+                parent_id: comment.parent_id,
                 author_name: comment.author_name,
                 author_email: comment.author_email,
                 content: comment.content,
@@ -38,15 +46,35 @@ impl CommentService {
         Ok(comment_dtos)
     }
 
-    /// Obtiene todos los comentarios de un post
+    /// Los comentarios de un post solo son públicos si el post está publicado
+    fn check_post_published(post_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        use crate::db::schema::posts;
+        
+        let published = posts::table
+            .filter(posts::id.eq(post_id))
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .select(posts::id)
+            .first::<i32>(conn)
+            .optional()?;
+        match published {
+            Some(_) => Ok(()),
+            None => Err(AppError::NotFound(format!("Post con ID {} no encontrado", post_id))),
+        }
+    }
+    
+    /// Obtiene todos los comentarios de un post; los de los borradores y posts
+    /// privados no son públicos
+    #[instrument(skip(conn))]
     pub fn get_comments_by_post(post_id_param: i32, conn: &mut DbConnection) -> AppResult<Vec<CommentDto>> {
         use crate::db::schema::comments::dsl::*;
         
+        Self::check_post_published(post_id_param, conn)?;
+        
         let comments_result = comments
             .filter(post_id.eq(post_id_param))
             .order(created_at.desc())
             .load::<Comment>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+            .map_err(AppError::DatabaseError)?;
         
         // Convertir entidades a DTOs
         let comment_dtos = comments_result.into_iter()
@@ -54,6 +82,7 @@ impl CommentService {
                 id: comment.id,
                 post_id: comment.post_id,
                 user_id: comment.user_id,
+                parent_id: comment.parent_id,
                 author_name: comment.author_name,
                 author_email: comment.author_email,
                 content: comment.content,
@@ -66,16 +95,21 @@ impl CommentService {
     }
     
     /// Alias para get_comments_by_post para mantener compatibilidad
+    #[instrument(skip(conn))]
     pub fn get_comments_by_post_id(post_id: i32, conn: &mut DbConnection) -> AppResult<Vec<CommentDto>> {
         Self::get_comments_by_post(post_id, conn)
     }
     
-    /// Obtiene un comentario por su ID
+    /// Obtiene un comentario por su ID, si es de un post publicado
+    #[instrument(skip(conn))]
     pub fn get_comment_by_id(comment_id: i32, conn: &mut DbConnection) -> AppResult<CommentDto> {
-        use crate::db::schema::comments::dsl::*;
+        use crate::db::schema::{comments, posts};
         
-        let comment = comments
-            .filter(id.eq(comment_id))
+        let comment = comments::table
+            .inner_join(posts::table)
+            .filter(comments::id.eq(comment_id))
+            .filter(posts::status.eq(POST_STATUS_PUBLISHED))
+            .select(comments::all_columns)
             .first::<Comment>(conn)
             .map_err(|e| match e {
                 DieselError::NotFound => AppError::NotFound(format!("Comentario con ID {} no encontrado", comment_id)),
@@ -86,6 +120,7 @@ impl CommentService {
             id: comment.id,
             post_id: comment.post_id,
             user_id: comment.user_id,
+            parent_id: comment.parent_id,
             author_name: comment.author_name,
             author_email: comment.author_email,
             content: comment.content,
@@ -95,6 +130,7 @@ impl CommentService {
     }
     
     /// Crea un nuevo comentario
+    #[instrument(skip_all)]
     pub fn create_comment(dto: CreateCommentDto, conn: &mut DbConnection) -> AppResult<CommentDto> {
         use crate::db::schema::comments;
         use crate::db::schema::posts::dsl::*;
@@ -104,14 +140,49 @@ impl CommentService {
             return Err(AppError::ValidationError("El contenido no puede estar vacío".to_string()));
         }
         
-        // Verificar que el post existe
-        let post_exists = posts
+        // Aplicar la política de comentarios de los ajustes del sitio
+        let settings = SiteSettingsService::get(conn)?;
+        if !settings.comments_enabled {
+            return Err(AppError::AuthorizationError("Los comentarios están desactivados".to_string()));
+        }
+        if dto.user_id.is_none() && !settings.comments_allow_anonymous {
+            return Err(AppError::AuthorizationError("Inicia sesión para comentar".to_string()));
+        }
+        Self::check_length(&dto.content, settings.comments_max_length)?;
+        
+        // Verificar que el post existe y está publicado
+        let post = match posts
             .filter(id.eq(dto.post_id))
-            .first::<crate::models::entities::Post>(conn)
-            .is_ok();
+            .filter(status.eq(POST_STATUS_PUBLISHED))
+            .first::<crate::models::entities::Post>(conn) {
+            Ok(post) => post,
+            Err(_) => return Err(AppError::ValidationError(format!("El post con ID {} no existe", dto.post_id))),
+        };
         
-        if !post_exists {
-            return Err(AppError::ValidationError(format!("El post con ID {} no existe", dto.post_id)));
+        // Los comentarios se cierran pasados unos días desde la publicación, si así se ha configurado
+        if settings.comments_close_after_days > 0 {
+            let closes_at = post.created_at
+                .map(|created| created + Duration::days(settings.comments_close_after_days.into()));
+            if closes_at.is_some_and(|closes_at| closes_at < Local::now().naive_local()) {
+                return Err(AppError::AuthorizationError("Los comentarios de este post están cerrados".to_string()));
+            }
+        }
+        
+        // Una respuesta debe serlo a un comentario del mismo post
+        if let Some(parent_id) = dto.parent_id {
+            use crate::db::schema::comments::dsl as c;
+            
+            let parent_post = c::comments
+                .filter(c::id.eq(parent_id))
+                .select(c::post_id)
+                .first::<i32>(conn)
+                .optional()?;
+            if parent_post != Some(dto.post_id) {
+                return Err(AppError::ValidationError(format!(
+                    "El comentario con ID {} no existe en este post",
+                    parent_id
+                )));
+            }
         }
         
         // Variables para almacenar el nombre y email del autor
@@ -145,11 +216,15 @@ impl CommentService {
             }
         }
         
+        // Con el email ya resuelto, comprobar que ni él ni la IP están bloqueados
+        BanService::check_commenter(author_email.as_deref(), dto.author_ip.as_deref(), conn)?;
+        
         // Crear el comentario
         let now = Local::now().naive_local();
         let new_comment = NewComment {
             post_id: dto.post_id,
             user_id: dto.user_id,
+            parent_id: dto.parent_id,
             author_name,
             author_email,
             content: dto.content,
@@ -161,12 +236,16 @@ impl CommentService {
         let comment = diesel::insert_into(comments::table)
             .values(&new_comment)
             .get_result::<Comment>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+            .map_err(AppError::DatabaseError)?;
+        
+        // Todavía no hay moderación: los comentarios se publican directamente
+        metrics::COMMENTS_CREATED_TOTAL.with_label_values(&["approved"]).inc();
         
         Ok(CommentDto {
             id: comment.id,
             post_id: comment.post_id,
             user_id: comment.user_id,
+            parent_id: comment.parent_id,
             author_name: comment.author_name,
             author_email: comment.author_email,
             content: comment.content,
@@ -176,6 +255,7 @@ impl CommentService {
     }
     
     /// Actualiza un comentario existente
+    #[instrument(skip(dto, conn))]
     pub fn update_comment(comment_id: i32, dto: UpdateCommentDto, conn: &mut DbConnection) -> AppResult<CommentDto> {
         use crate::db::schema::comments::dsl::*;
         
@@ -193,6 +273,7 @@ impl CommentService {
             if content_value.is_empty() {
                 return Err(AppError::ValidationError("El contenido no puede estar vacío".to_string()));
             }
+            Self::check_length(content_value, SiteSettingsService::get(conn)?.comments_max_length)?;
         }
         
         // Preparar datos para actualización
@@ -205,12 +286,13 @@ impl CommentService {
         let updated_comment = diesel::update(comments.filter(id.eq(comment_id)))
             .set(&update_data)
             .get_result::<Comment>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+            .map_err(AppError::DatabaseError)?;
         
         Ok(CommentDto {
             id: updated_comment.id,
             post_id: updated_comment.post_id,
             user_id: updated_comment.user_id,
+            parent_id: updated_comment.parent_id,
             author_name: updated_comment.author_name,
             author_email: updated_comment.author_email,
             content: updated_comment.content,
@@ -220,6 +302,7 @@ impl CommentService {
     }
     
     /// Elimina un comentario
+    #[instrument(skip(conn))]
     pub fn delete_comment(comment_id: i32, conn: &mut DbConnection) -> AppResult<()> {
         use crate::db::schema::comments::dsl::*;
         
@@ -235,7 +318,7 @@ impl CommentService {
         // Eliminar el comentario
         let deleted = diesel::delete(comments.filter(id.eq(comment_id)))
             .execute(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+            .map_err(AppError::DatabaseError)?;
         
         if deleted == 0 {
             return Err(AppError::NotFound(format!("No se pudo eliminar el comentario con ID {}", comment_id)));
@@ -243,4 +326,28 @@ impl CommentService {
         
         Ok(())
     }
-} 
\ No newline at end of file
+    
+    pub fn to_dto(comment: Comment) -> CommentDto {
+        CommentDto {
+            id: comment.id,
+            post_id: comment.post_id,
+            user_id: comment.user_id,
+            parent_id: comment.parent_id,
+            author_name: comment.author_name,
+            author_email: comment.author_email,
+            content: comment.content,
+            created_at: comment.created_at,
+            updated_at: comment.updated_at,
+        }
+    }
+    
+    fn check_length(content: &str, max_length: u32) -> AppResult<()> {
+        if content.chars().count() > max_length as usize {
+            return Err(AppError::ValidationError(format!(
+                "El comentario no puede superar los {} caracteres",
+                max_length
+            )));
+        }
+        Ok(())
+    }
+}
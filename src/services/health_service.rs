@@ -0,0 +1,78 @@
+use chrono::DateTime;
+use std::time::Duration;
+use crate::db::{self, DbPool};
+use crate::models::dto::{LivenessDto, PoolStateDto, ReadinessDto, VersionDto};
+
+/// Tiempo máximo de espera por una conexión durante la comprobación de readiness
+const READINESS_CONNECTION_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct HealthService;
+
+impl HealthService {
+    /// Indica que el proceso está vivo; no toca la base de datos
+    pub fn liveness() -> LivenessDto {
+        LivenessDto {
+            status: "ok".to_string(),
+        }
+    }
+
+    /// Comprueba que el pool tiene conexiones disponibles, que la base de datos
+    /// responde y que no quedan migraciones pendientes
+    pub fn readiness(pool: &DbPool) -> ReadinessDto {
+        let state = pool.state();
+        let max_size = pool.max_size();
+        let mut errors = Vec::new();
+        let mut database = false;
+        let mut pending_migrations = Vec::new();
+
+        if state.idle_connections == 0 && state.connections >= max_size {
+            errors.push("El pool de conexiones está agotado".to_string());
+        } else {
+            match pool.get_timeout(READINESS_CONNECTION_TIMEOUT) {
+                Ok(mut conn) => {
+                    database = db::ping(&mut conn).is_ok();
+                    if !database {
+                        errors.push("La base de datos no responde".to_string());
+                    }
+
+                    match db::migrations::pending_migrations(&mut conn) {
+                        Ok(pending) => pending_migrations = pending,
+                        Err(e) => errors.push(e.to_string()),
+                    }
+
+                    if !pending_migrations.is_empty() {
+                        errors.push(format!("Hay {} migraciones pendientes", pending_migrations.len()));
+                    }
+                }
+                Err(e) => errors.push(format!("No se pudo obtener una conexión: {}", e)),
+            }
+        }
+
+        ReadinessDto {
+            status: if errors.is_empty() { "ok" } else { "unavailable" }.to_string(),
+            database,
+            pending_migrations,
+            pool: PoolStateDto {
+                connections: state.connections,
+                idle_connections: state.idle_connections,
+                max_size,
+            },
+            errors,
+        }
+    }
+
+    /// Versión del crate, commit de git y fecha de compilación
+    pub fn version() -> VersionDto {
+        let build_time = env!("BUILD_TIMESTAMP")
+            .parse::<i64>()
+            .ok()
+            .and_then(|ts| DateTime::from_timestamp(ts, 0))
+            .map(|dt| dt.to_rfc3339());
+
+        VersionDto {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("GIT_COMMIT").to_string(),
+            build_time,
+        }
+    }
+}
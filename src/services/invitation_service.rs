@@ -0,0 +1,105 @@
+use chrono::{Local, NaiveDateTime};
+use tracing::instrument;
+use uuid::Uuid;
+use crate::auth::hash_token;
+use crate::db::DbConnection;
+use crate::models::dto::{CreateInvitationDto, InvitationDto};
+use crate::models::entities::{Invitation, NewInvitation};
+use crate::repositories::InvitationRepository;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::validation::is_not_empty;
+
+const MAX_INVITATION_USES: i32 = 10_000;
+
+/// Códigos de invitación para registrarse
+pub struct InvitationService;
+
+impl InvitationService {
+    #[instrument(skip_all)]
+    pub fn get_all_invitations(conn: &mut DbConnection) -> AppResult<Vec<InvitationDto>> {
+        let invitations = InvitationRepository::find_all(conn)?;
+        let now = Local::now().naive_local();
+        Ok(invitations.into_iter().map(|invitation| Self::to_dto(invitation, None, now)).collect())
+    }
+
+    /// Crea una invitación y devuelve su código, que no vuelve a mostrarse
+    #[instrument(skip(dto, conn))]
+    pub fn create_invitation(dto: CreateInvitationDto, acting_user_id: i32, conn: &mut DbConnection) -> AppResult<InvitationDto> {
+        let role = dto.role.map(|r| r.trim().to_string()).unwrap_or_else(|| "user".to_string());
+        if !is_not_empty(&role) || role.len() > 50 {
+            return Err(AppError::ValidationError("El rol debe tener entre 1 y 50 caracteres".to_string()));
+        }
+
+        let max_uses = dto.max_uses.unwrap_or(1);
+        if !(1..=MAX_INVITATION_USES).contains(&max_uses) {
+            return Err(AppError::ValidationError(format!(
+                "El número de usos debe estar entre 1 y {}",
+                MAX_INVITATION_USES
+            )));
+        }
+
+        let now = Local::now().naive_local();
+        if dto.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(AppError::ValidationError("La fecha de caducidad debe ser futura".to_string()));
+        }
+
+        let code = Uuid::new_v4().simple().to_string();
+        let invitation = InvitationRepository::create(
+            &NewInvitation {
+                code_hash: hash_token(&code),
+                role,
+                max_uses,
+                expires_at: dto.expires_at,
+                note: dto.note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+                created_by: Some(acting_user_id),
+                created_at: Some(now),
+            },
+            conn,
+        )?;
+
+        tracing::info!(invitation_id = invitation.id, role = %invitation.role, acting_user_id, "Invitación creada");
+        Ok(Self::to_dto(invitation, Some(code), now))
+    }
+
+    /// Revoca una invitación; las cuentas ya registradas con ella no cambian
+    #[instrument(skip(conn))]
+    pub fn delete_invitation(invitation_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        if InvitationRepository::delete(invitation_id, conn)? == 0 {
+            return Err(AppError::NotFound(format!("Invitación con ID {} no encontrada", invitation_id)));
+        }
+        Ok(())
+    }
+
+    /// Gasta un uso del código. Debe llamarse en la misma transacción que crea la
+    /// cuenta, para que el uso se devuelva si el registro falla.
+    #[instrument(skip_all)]
+    pub fn consume(code: &str, conn: &mut DbConnection) -> AppResult<Invitation> {
+        InvitationRepository::consume(&hash_token(code.trim()), Local::now().naive_local(), conn)?
+            .ok_or_else(|| {
+                AppError::ValidationError("El código de invitación no es válido, ha caducado o ya se ha usado".to_string())
+            })
+    }
+
+    fn to_dto(invitation: Invitation, code: Option<String>, now: NaiveDateTime) -> InvitationDto {
+        let status = if invitation.uses >= invitation.max_uses {
+            "used_up"
+        } else if invitation.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            "expired"
+        } else {
+            "active"
+        };
+
+        InvitationDto {
+            id: invitation.id,
+            code,
+            role: invitation.role,
+            max_uses: invitation.max_uses,
+            uses: invitation.uses,
+            expires_at: invitation.expires_at,
+            note: invitation.note,
+            status: status.to_string(),
+            created_by: invitation.created_by,
+            created_at: invitation.created_at,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use chrono::Local;
+use diesel::Connection;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::models::dto::{CreateCategoryDto, CreatePostDto, PostDto};
+use crate::models::entities::{POST_STATUS_DRAFT, POST_STATUS_PUBLISHED};
+use crate::repositories::{PostRepository, TagRepository};
+use crate::services::{CategoryService, PostService};
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::front_matter::{self, ExportedFrontMatter, FrontMatterFormat};
+use crate::utils::slug::{is_valid_slug, slugify};
+
+/// Fichero Markdown exportado, con el nombre `AAAA-MM-DD-slug.md`
+pub struct MarkdownFile {
+    pub file_name: String,
+    pub content: String,
+}
+
+pub struct MarkdownService;
+
+impl MarkdownService {
+    /// Crea un post a partir de un fichero Markdown con cabecera YAML o TOML.
+    ///
+    /// El slug sigue las reglas de `PostService::create_post`: uno indicado en la
+    /// cabecera que ya existe es un error; sin él, se genera a partir del título
+    /// y se desambigua con un sufijo. Las categorías se buscan por su slug y se
+    /// crean si no existen. El estado es el de `status` o, si no se indica,
+    /// borrador con `draft: true` y publicado sin él.
+    #[instrument(skip(content, conn))]
+    pub fn import_file(file_name: &str, content: &str, conn: &mut DbConnection) -> AppResult<PostDto> {
+        let (front_matter, body) = front_matter::parse(content).map_err(AppError::ValidationError)?;
+        let title = front_matter
+            .title
+            .ok_or_else(|| AppError::ValidationError("La cabecera no indica el título (title)".to_string()))?;
+        let date = front_matter
+            .date
+            .or_else(|| front_matter::file_name_date(file_name))
+            .unwrap_or_else(|| Local::now().naive_local());
+        let status = front_matter.status.unwrap_or_else(|| {
+            let status = if front_matter.draft { POST_STATUS_DRAFT } else { POST_STATUS_PUBLISHED };
+            status.to_string()
+        });
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let mut category_ids = Vec::with_capacity(front_matter.categories.len());
+            for name in &front_matter.categories {
+                let category_id = Self::resolve_category(name, conn)?;
+                if !category_ids.contains(&category_id) {
+                    category_ids.push(category_id);
+                }
+            }
+
+            let post = PostService::create_post(
+                CreatePostDto {
+                    title,
+                    body,
+                    slug: front_matter.slug,
+                    excerpt: None,
+                    featured_media_id: None,
+                    featured_image_url: None,
+                    status: Some(status),
+                    category_ids: Some(category_ids),
+                    tags: Some(front_matter.tags),
+                },
+                None,
+                conn,
+            )?;
+
+            let post = PostRepository::set_dates(post.id, date, date, conn)?;
+            PostService::to_dto(post, conn)
+        })
+    }
+
+    /// Exporta todos los posts, sea cual sea su estado, como ficheros Markdown.
+    /// La cabecera lleva el estado en `status` y, para los generadores estáticos,
+    /// marca con `draft: true` los borradores y los privados. Las categorías se
+    /// escriben por su slug, que es como las busca la importación.
+    #[instrument(skip(conn))]
+    pub fn export_all(format: FrontMatterFormat, conn: &mut DbConnection) -> AppResult<Vec<MarkdownFile>> {
+        let posts = PostRepository::find_all(conn)?;
+
+        let mut files = Vec::with_capacity(posts.len());
+        for post in posts {
+            let categories = PostRepository::get_categories(post.id, conn)?
+                .into_iter()
+                .map(|c| c.slug)
+                .collect();
+            let tags = TagRepository::find_by_post(post.id, conn)?
+                .into_iter()
+                .map(|t| t.name)
+                .collect();
+            let date = post.created_at.unwrap_or_else(|| Local::now().naive_local());
+
+            let header = ExportedFrontMatter {
+                title: post.title,
+                date: date.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                slug: post.slug.clone(),
+                categories,
+                tags,
+                draft: post.status != POST_STATUS_PUBLISHED,
+                status: post.status,
+            };
+            let content = front_matter::render(&header, &post.body, format).map_err(|e| {
+                AppError::InternalServerError(format!("No se pudo generar la cabecera del post {}: {}", post.id, e))
+            })?;
+
+            // El slug forma parte de la ruta del fichero: uno que no es válido
+            // (p. ej. con `/` o `..`) se sustituye por el ID del post
+            let file_slug = if is_valid_slug(&post.slug) { post.slug } else { format!("post-{}", post.id) };
+            files.push(MarkdownFile {
+                file_name: format!("{}-{}.md", date.format("%Y-%m-%d"), file_slug),
+                content,
+            });
+        }
+
+        Ok(files)
+    }
+
+    /// Categoría con el slug indicado o, si es un nombre, con el slug que le
+    /// corresponde. Un slug antiguo lleva a la categoría renombrada; si no hay
+    /// ninguna, se crea en la raíz.
+    fn resolve_category(name: &str, conn: &mut DbConnection) -> AppResult<i32> {
+        let slug = if is_valid_slug(name) { name.to_string() } else { slugify(name) };
+        match CategoryService::find_id_by_slug(&slug, conn)? {
+            Some(category_id) => Ok(category_id),
+            None => {
+                let category = CategoryService::create_category(
+                    CreateCategoryDto {
+                        name: name.to_string(),
+                        slug: Some(slug),
+                        description: None,
+                        parent_id: None,
+                        position: None,
+                    },
+                    conn,
+                )?;
+                Ok(category.id)
+            }
+        }
+    }
+}
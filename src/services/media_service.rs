@@ -0,0 +1,403 @@
+use chrono::Local;
+use diesel::result::Error as DieselError;
+use diesel::Connection;
+use std::collections::HashMap;
+use uuid::Uuid;
+use crate::auth::Claims;
+use crate::config;
+use crate::db::DbConnection;
+use crate::models::entities::{Media, MediaVariant, NewMedia, NewMediaVariant, UpdateMedia};
+use crate::models::dto::{AttachMediaDto, MediaDto, MediaVariantDto, UpdateMediaDto};
+use crate::repositories::MediaRepository;
+use crate::services::PostService;
+use crate::storage;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::image_processing;
+use tracing::instrument;
+
+/// Tipos aceptados, detectados a partir del contenido y no de la extensión o del
+/// `Content-Type` declarado por el cliente
+const ALLOWED_TYPES: [(&str, &str); 5] = [
+    ("image/jpeg", "jpg"),
+    ("image/png", "png"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+    ("application/pdf", "pdf"),
+];
+
+/// Fichero subido por un usuario, ya leído de la petición
+pub struct UploadedFile {
+    pub filename: String,
+    pub data: Vec<u8>,
+    pub alt_text: Option<String>,
+}
+
+/// Fichero pendiente de guardar en el backend de almacenamiento
+struct StoredObject {
+    key: String,
+    data: Vec<u8>,
+    mime_type: &'static str,
+}
+
+pub struct MediaService;
+
+impl MediaService {
+    /// Lista la biblioteca de medios; con `user_id` solo los ficheros de ese usuario
+    #[instrument(skip(conn))]
+    pub fn get_all_media(user_id: Option<i32>, conn: &mut DbConnection) -> AppResult<Vec<MediaDto>> {
+        let media = MediaRepository::find_all(user_id, conn)?;
+        Self::to_dtos(media, conn)
+    }
+
+    /// Obtiene un fichero por su ID
+    #[instrument(skip(conn))]
+    pub fn get_media_by_id(media_id: i32, conn: &mut DbConnection) -> AppResult<MediaDto> {
+        let media = Self::find_media(media_id, conn)?;
+        let variants = MediaRepository::find_variants(&[media.id], conn)?;
+
+        Ok(Self::to_dto(media, variants))
+    }
+
+    /// Guarda un fichero subido: valida su tipo real y la cuota del usuario, genera las
+    /// variantes si es una imagen y lo registra en la biblioteca de medios.
+    ///
+    /// Los administradores no tienen cuota.
+    #[instrument(skip(file, conn), fields(size = file.data.len()))]
+    pub fn upload(user_id: i32, is_admin: bool, file: UploadedFile, conn: &mut DbConnection) -> AppResult<MediaDto> {
+        let (mime_type, extension) = infer::get(&file.data)
+            .and_then(|kind| ALLOWED_TYPES.iter().find(|(mime, _)| *mime == kind.mime_type()))
+            .copied()
+            .ok_or_else(|| AppError::ValidationError(
+                "Tipo de fichero no permitido (se aceptan JPEG, PNG, GIF, WebP y PDF)".to_string()
+            ))?;
+
+        let base_key = format!("{}/{}", Local::now().format("%Y/%m"), Uuid::new_v4().simple());
+
+        // Procesar la imagen antes de tocar el almacenamiento: el tamaño final incluye las variantes
+        let mut objects = Vec::new();
+        let mut new_variants = Vec::new();
+        let (width, height) = if mime_type.starts_with("image/") {
+            let processed = image_processing::process(&file.data, mime_type)?;
+            let original = processed.original;
+
+            objects.push(StoredObject {
+                key: format!("{}.{}", base_key, original.extension),
+                data: original.data,
+                mime_type: original.mime_type,
+            });
+
+            for variant in processed.variants {
+                let image = variant.image;
+                let suffix = match variant.kind {
+                    "thumbnail" => "thumb".to_string(),
+                    _ => format!("{}w", image.width),
+                };
+                let key = format!("{}-{}.{}", base_key, suffix, image.extension);
+
+                new_variants.push(NewMediaVariant {
+                    media_id: 0,
+                    kind: variant.kind.to_string(),
+                    mime_type: image.mime_type.to_string(),
+                    width: image.width as i32,
+                    height: image.height as i32,
+                    size_bytes: image.data.len() as i64,
+                    storage_key: key.clone(),
+                });
+                objects.push(StoredObject { key, data: image.data, mime_type: image.mime_type });
+            }
+
+            (Some(original.width as i32), Some(original.height as i32))
+        } else {
+            objects.push(StoredObject {
+                key: format!("{}.{}", base_key, extension),
+                data: file.data,
+                mime_type,
+            });
+            (None, None)
+        };
+
+        let total_bytes: i64 = objects.iter().map(|o| o.data.len() as i64).sum();
+        let quota = if is_admin { 0 } else { config::media_user_quota_bytes() };
+
+        // Comprobación previa, para no escribir en el almacenamiento un fichero que
+        // no cabe; la definitiva se repite al insertar
+        Self::check_quota(user_id, quota, total_bytes, conn)?;
+
+        // El primer objeto es siempre el original
+        let original_key = objects[0].key.clone();
+        let original_size = objects[0].data.len() as i64;
+        let original_mime = objects[0].mime_type;
+
+        Self::store_all(&objects)?;
+
+        let now = Local::now().naive_local();
+        let new_media = NewMedia {
+            user_id: Some(user_id),
+            storage_key: original_key,
+            original_filename: file.filename,
+            mime_type: original_mime.to_string(),
+            size_bytes: original_size,
+            width,
+            height,
+            alt_text: file.alt_text.filter(|alt| !alt.trim().is_empty()),
+            created_at: Some(now),
+            updated_at: Some(now),
+        };
+
+        // La cuota se comprueba con la fila del usuario bloqueada, para que dos
+        // subidas simultáneas no la superen entre las dos
+        let created = conn.transaction::<_, AppError, _>(|conn| {
+            if quota > 0 {
+                MediaRepository::lock_owner(user_id, conn)?;
+                Self::check_quota(user_id, quota, total_bytes, conn)?;
+            }
+            Ok(MediaRepository::create(&new_media, new_variants, conn)?)
+        });
+        let media = match created {
+            Ok(media) => media,
+            Err(e) => {
+                // Sin fila en la base de datos los ficheros quedarían huérfanos
+                Self::remove_all(objects.iter().map(|o| o.key.as_str()));
+                return Err(e);
+            }
+        };
+
+        Self::get_media_by_id(media.id, conn)
+    }
+
+    /// Rechaza el fichero si con él el usuario superaría `quota` bytes (0 = sin cuota)
+    fn check_quota(user_id: i32, quota: i64, total_bytes: i64, conn: &mut DbConnection) -> AppResult<()> {
+        if quota <= 0 {
+            return Ok(());
+        }
+        let used = MediaRepository::used_bytes(user_id, conn)?;
+        if used + total_bytes > quota {
+            return Err(AppError::ValidationError(format!(
+                "Cuota de almacenamiento superada: {} de {} bytes usados y el fichero ocupa {} bytes",
+                used, quota, total_bytes
+            )));
+        }
+        Ok(())
+    }
+
+    /// Actualiza el texto alternativo; solo el propietario o un administrador
+    #[instrument(skip(dto, conn))]
+    pub fn update_media(media_id: i32, dto: UpdateMediaDto, user_id: i32, is_admin: bool, conn: &mut DbConnection) -> AppResult<MediaDto> {
+        let media = Self::find_media(media_id, conn)?;
+        Self::check_owner(&media, user_id, is_admin)?;
+
+        let update = UpdateMedia {
+            alt_text: dto.alt_text,
+            updated_at: Some(Local::now().naive_local()),
+        };
+        let updated = MediaRepository::update(media_id, &update, conn)?;
+        let variants = MediaRepository::find_variants(&[updated.id], conn)?;
+
+        Ok(Self::to_dto(updated, variants))
+    }
+
+    /// Elimina un fichero, sus variantes y sus asociaciones con posts; solo el
+    /// propietario o un administrador
+    #[instrument(skip(conn))]
+    pub fn delete_media(media_id: i32, user_id: i32, is_admin: bool, conn: &mut DbConnection) -> AppResult<()> {
+        let media = Self::find_media(media_id, conn)?;
+        Self::check_owner(&media, user_id, is_admin)?;
+
+        let variants = MediaRepository::find_variants(&[media.id], conn)?;
+        let deleted = MediaRepository::delete(media_id, conn)?;
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!("No se pudo eliminar el fichero con ID {}", media_id)));
+        }
+
+        // Una vez borrada la fila, un fallo al borrar los ficheros solo deja basura en el almacenamiento
+        Self::remove_all(
+            std::iter::once(media.storage_key.as_str()).chain(variants.iter().map(|v| v.storage_key.as_str())),
+        );
+
+        Ok(())
+    }
+
+    /// Elimina de la base de datos todos los ficheros de un usuario, avatar
+    /// incluido, y devuelve sus claves en el almacenamiento (con las de las
+    /// variantes), para borrarlas con `remove_stored` cuando se confirme la
+    /// transacción
+    #[instrument(skip(conn))]
+    pub fn delete_user_media(user_id: i32, conn: &mut DbConnection) -> AppResult<Vec<String>> {
+        let media = MediaRepository::find_all(Some(user_id), conn)?;
+        let media_ids: Vec<i32> = media.iter().map(|m| m.id).collect();
+        let variants = MediaRepository::find_variants(&media_ids, conn)?;
+        MediaRepository::delete_by_user(user_id, conn)?;
+
+        Ok(media
+            .into_iter()
+            .map(|m| m.storage_key)
+            .chain(variants.into_iter().map(|v| v.storage_key))
+            .collect())
+    }
+
+    /// Borra del almacenamiento los ficheros cuyas filas ya se han eliminado
+    pub fn remove_stored(keys: &[String]) {
+        Self::remove_all(keys.iter().map(String::as_str));
+    }
+
+    /// Ficheros asociados a un post, en el orden de su galería; la de un
+    /// borrador o post privado solo la ven su autor y los administradores
+    #[instrument(skip(viewer, conn))]
+    pub fn get_post_media(post_id: i32, viewer: Option<&Claims>, conn: &mut DbConnection) -> AppResult<Vec<MediaDto>> {
+        PostService::find_visible(post_id, viewer, conn)?;
+
+        let media = MediaRepository::find_by_post(post_id, conn)?;
+        Self::to_dtos(media, conn)
+    }
+
+    /// Asocia un fichero de la biblioteca a un post; solo su autor o un administrador
+    #[instrument(skip(dto, user, conn))]
+    pub fn attach_to_post(post_id: i32, dto: AttachMediaDto, user: &Claims, conn: &mut DbConnection) -> AppResult<Vec<MediaDto>> {
+        PostService::find_editable(post_id, user, conn)?;
+        MediaRepository::find_by_id(dto.media_id, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::ValidationError(format!("El fichero con ID {} no existe", dto.media_id)),
+            _ => AppError::DatabaseError(e),
+        })?;
+
+        MediaRepository::attach(post_id, dto.media_id, dto.position, conn)?;
+
+        Self::get_post_media(post_id, Some(user), conn)
+    }
+
+    /// Quita un fichero de un post; el fichero sigue en la biblioteca. Solo el
+    /// autor del post o un administrador.
+    #[instrument(skip(user, conn))]
+    pub fn detach_from_post(post_id: i32, media_id: i32, user: &Claims, conn: &mut DbConnection) -> AppResult<()> {
+        PostService::find_editable(post_id, user, conn)?;
+        let deleted = MediaRepository::detach(post_id, media_id, conn)?;
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!(
+                "El fichero con ID {} no está asociado al post con ID {}",
+                media_id, post_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Convierte varios ficheros a DTOs cargando sus variantes en una sola consulta
+    pub fn to_dtos(media: Vec<Media>, conn: &mut DbConnection) -> AppResult<Vec<MediaDto>> {
+        let ids: Vec<i32> = media.iter().map(|m| m.id).collect();
+        let mut variants_by_media: HashMap<i32, Vec<MediaVariant>> = HashMap::new();
+        for variant in MediaRepository::find_variants(&ids, conn)? {
+            variants_by_media.entry(variant.media_id).or_default().push(variant);
+        }
+
+        Ok(media
+            .into_iter()
+            .map(|m| {
+                let variants = variants_by_media.remove(&m.id).unwrap_or_default();
+                Self::to_dto(m, variants)
+            })
+            .collect())
+    }
+
+    pub fn to_dto(media: Media, variants: Vec<MediaVariant>) -> MediaDto {
+        let storage = storage::backend();
+        let url = storage.public_url(&media.storage_key);
+
+        // La miniatura en JPEG o PNG tiene preferencia sobre la WebP
+        let thumbnail_url = variants
+            .iter()
+            .filter(|v| v.kind == "thumbnail")
+            .min_by_key(|v| v.mime_type == "image/webp")
+            .map(|v| storage.public_url(&v.storage_key));
+
+        // srcset con las variantes del formato original más el propio original
+        let mut srcset = None;
+        let mut srcset_webp = None;
+        if let Some(width) = media.width {
+            let responsive: Vec<&MediaVariant> = variants.iter().filter(|v| v.kind == "responsive").collect();
+
+            let mut entries: Vec<String> = responsive
+                .iter()
+                .filter(|v| v.mime_type == media.mime_type)
+                .map(|v| format!("{} {}w", storage.public_url(&v.storage_key), v.width))
+                .collect();
+            entries.push(format!("{} {}w", url, width));
+            srcset = Some(entries.join(", "));
+
+            let webp: Vec<String> = if media.mime_type == "image/webp" {
+                entries
+            } else {
+                responsive
+                    .iter()
+                    .filter(|v| v.mime_type == "image/webp")
+                    .map(|v| format!("{} {}w", storage.public_url(&v.storage_key), v.width))
+                    .collect()
+            };
+            srcset_webp = Some(webp.join(", ")).filter(|s| !s.is_empty());
+        }
+
+        let variant_dtos = variants
+            .into_iter()
+            .map(|v| MediaVariantDto {
+                url: storage.public_url(&v.storage_key),
+                kind: v.kind,
+                mime_type: v.mime_type,
+                width: v.width,
+                height: v.height,
+                size_bytes: v.size_bytes,
+            })
+            .collect();
+
+        MediaDto {
+            id: media.id,
+            user_id: media.user_id,
+            url,
+            original_filename: media.original_filename,
+            mime_type: media.mime_type,
+            size_bytes: media.size_bytes,
+            width: media.width,
+            height: media.height,
+            alt_text: media.alt_text,
+            created_at: media.created_at,
+            updated_at: media.updated_at,
+            thumbnail_url,
+            srcset,
+            srcset_webp,
+            variants: variant_dtos,
+        }
+    }
+
+    fn find_media(media_id: i32, conn: &mut DbConnection) -> AppResult<Media> {
+        MediaRepository::find_by_id(media_id, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Fichero con ID {} no encontrado", media_id)),
+            _ => AppError::DatabaseError(e),
+        })
+    }
+
+    fn check_owner(media: &Media, user_id: i32, is_admin: bool) -> AppResult<()> {
+        if is_admin || media.user_id == Some(user_id) {
+            Ok(())
+        } else {
+            Err(AppError::AuthorizationError("Solo el propietario puede modificar este fichero".to_string()))
+        }
+    }
+
+    /// Guarda todos los objetos; si alguno falla, borra los que ya se habían guardado
+    fn store_all(objects: &[StoredObject]) -> AppResult<()> {
+        let storage = storage::backend();
+        for (i, object) in objects.iter().enumerate() {
+            if let Err(e) = storage.put(&object.key, &object.data, object.mime_type) {
+                Self::remove_all(objects[..i].iter().map(|o| o.key.as_str()));
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_all<'a>(keys: impl Iterator<Item = &'a str>) {
+        let storage = storage::backend();
+        for key in keys {
+            if let Err(e) = storage.delete(key) {
+                tracing::warn!(key, error = %e, "No se pudo eliminar el fichero del almacenamiento");
+            }
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use chrono::Local;
+use diesel::result::Error as DieselError;
+use diesel::Connection;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::models::entities::{
+    Menu, MenuItem, NewMenu, NewMenuItem, UpdateMenu, MENU_ITEM_CATEGORY, MENU_ITEM_PAGE, MENU_ITEM_POST,
+    MENU_ITEM_URL,
+};
+use crate::models::dto::{CreateMenuDto, MenuDto, MenuItemDto, MenuItemInputDto, MenuSummaryDto, UpdateMenuDto};
+use crate::repositories::{CategoryRepository, MenuRepository, PageRepository, PostRepository};
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::slug::is_valid_slug;
+
+const MENU_ITEM_TYPES: [&str; 4] = [MENU_ITEM_PAGE, MENU_ITEM_POST, MENU_ITEM_CATEGORY, MENU_ITEM_URL];
+/// Niveles de anidamiento admitidos en un menú
+const MAX_MENU_DEPTH: usize = 5;
+
+/// Títulos y slugs actuales de los destinos de un menú, por tipo e ID
+struct MenuLinks {
+    pages: HashMap<i32, (String, String)>,
+    posts: HashMap<i32, (String, String)>,
+    categories: HashMap<i32, (String, String)>,
+}
+
+pub struct MenuService;
+
+impl MenuService {
+    #[instrument(skip_all)]
+    pub fn get_all_menus(conn: &mut DbConnection) -> AppResult<Vec<MenuSummaryDto>> {
+        let menus = MenuRepository::find_all(conn)?;
+
+        Ok(menus
+            .into_iter()
+            .map(|menu| MenuSummaryDto {
+                id: menu.id,
+                name: menu.name,
+                location: menu.location,
+                created_at: menu.created_at,
+                updated_at: menu.updated_at,
+            })
+            .collect())
+    }
+
+    /// Menú de una ubicación con sus elementos resueltos: etiquetas y slugs actuales
+    #[instrument(skip(conn))]
+    pub fn get_menu_by_location(menu_location: &str, conn: &mut DbConnection) -> AppResult<MenuDto> {
+        let menu = MenuRepository::find_by_location(menu_location, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("No hay ningún menú en la ubicación '{}'", menu_location)),
+            _ => AppError::DatabaseError(e),
+        })?;
+
+        Self::load_dto(menu, conn)
+    }
+
+    #[instrument(skip(dto, conn))]
+    pub fn create_menu(dto: CreateMenuDto, conn: &mut DbConnection) -> AppResult<MenuDto> {
+        if dto.name.trim().is_empty() {
+            return Err(AppError::ValidationError("El nombre no puede estar vacío".to_string()));
+        }
+        Self::validate_location(&dto.location)?;
+        Self::check_location_available(&dto.location, conn)?;
+        if let Some(ref items) = dto.items {
+            Self::validate_items(items, 1, conn)?;
+        }
+
+        let now = Local::now().naive_local();
+        let new_menu = NewMenu {
+            name: dto.name,
+            location: dto.location,
+            created_at: Some(now),
+            updated_at: Some(now),
+        };
+
+        let menu = conn.transaction::<_, AppError, _>(|conn| {
+            let menu = MenuRepository::create(&new_menu, conn)?;
+            if let Some(ref items) = dto.items {
+                Self::insert_items(menu.id, None, items, conn)?;
+            }
+            Ok(menu)
+        })?;
+
+        Self::load_dto(menu, conn)
+    }
+
+    #[instrument(skip(dto, conn))]
+    pub fn update_menu(menu_id: i32, dto: UpdateMenuDto, conn: &mut DbConnection) -> AppResult<MenuDto> {
+        let menu = Self::find_menu(menu_id, conn)?;
+
+        if let Some(ref name) = dto.name {
+            if name.trim().is_empty() {
+                return Err(AppError::ValidationError("El nombre no puede estar vacío".to_string()));
+            }
+        }
+        if let Some(ref location) = dto.location {
+            Self::validate_location(location)?;
+            if location != &menu.location {
+                Self::check_location_available(location, conn)?;
+            }
+        }
+
+        let update_data = UpdateMenu {
+            name: dto.name,
+            location: dto.location,
+            updated_at: Some(Local::now().naive_local()),
+        };
+        let menu = MenuRepository::update(menu_id, &update_data, conn)?;
+
+        Self::load_dto(menu, conn)
+    }
+
+    /// Sustituye todos los elementos del menú por el árbol indicado, en una única transacción
+    #[instrument(skip(items, conn))]
+    pub fn set_menu_items(menu_id: i32, items: Vec<MenuItemInputDto>, conn: &mut DbConnection) -> AppResult<MenuDto> {
+        let menu = Self::find_menu(menu_id, conn)?;
+        Self::validate_items(&items, 1, conn)?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            MenuRepository::delete_items(menu_id, conn)?;
+            Self::insert_items(menu_id, None, &items, conn)
+        })?;
+
+        Self::load_dto(menu, conn)
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete_menu(menu_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        Self::find_menu(menu_id, conn)?;
+
+        let deleted = MenuRepository::delete(menu_id, conn)?;
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!("No se pudo eliminar el menú con ID {}", menu_id)));
+        }
+
+        Ok(())
+    }
+
+    fn load_dto(menu: Menu, conn: &mut DbConnection) -> AppResult<MenuDto> {
+        let items = MenuRepository::find_items(menu.id, conn)?;
+
+        // Resolver los destinos con una consulta por tipo
+        let ids = |f: fn(&MenuItem) -> Option<i32>| items.iter().filter_map(f).collect::<Vec<i32>>();
+        let links = MenuLinks {
+            pages: MenuRepository::page_links(&ids(|i| i.page_id), conn)?,
+            posts: MenuRepository::post_links(&ids(|i| i.post_id), conn)?,
+            categories: MenuRepository::category_links(&ids(|i| i.category_id), conn)?,
+        };
+
+        let mut children: HashMap<Option<i32>, Vec<MenuItem>> = HashMap::new();
+        for item in items {
+            children.entry(item.parent_id).or_default().push(item);
+        }
+
+        Ok(MenuDto {
+            id: menu.id,
+            name: menu.name,
+            location: menu.location,
+            items: Self::build_tree(None, &mut children, &links),
+        })
+    }
+
+    fn build_tree(
+        parent: Option<i32>,
+        children: &mut HashMap<Option<i32>, Vec<MenuItem>>,
+        links: &MenuLinks,
+    ) -> Vec<MenuItemDto> {
+        let nodes = children.remove(&parent).unwrap_or_default();
+
+        let mut tree = Vec::with_capacity(nodes.len());
+        for item in nodes {
+            let (target_id, link) = match item.item_type.as_str() {
+                MENU_ITEM_PAGE => (item.page_id, item.page_id.and_then(|id| links.pages.get(&id))),
+                MENU_ITEM_POST => (item.post_id, item.post_id.and_then(|id| links.posts.get(&id))),
+                MENU_ITEM_CATEGORY => (item.category_id, item.category_id.and_then(|id| links.categories.get(&id))),
+                _ => (None, None),
+            };
+            let item_children = Self::build_tree(Some(item.id), children, links);
+
+            let (label, slug) = match link {
+                Some((title, slug)) => (item.label.unwrap_or_else(|| title.clone()), Some(slug.clone())),
+                None if item.item_type == MENU_ITEM_URL => (item.label.unwrap_or_default(), None),
+                // Un destino eliminado o que no se puede mostrar: el elemento se omite
+                // y sus subelementos ocupan su lugar
+                None => {
+                    tree.extend(item_children);
+                    continue;
+                }
+            };
+
+            tree.push(MenuItemDto {
+                children: item_children,
+                id: item.id,
+                label,
+                item_type: item.item_type,
+                target_id,
+                slug,
+                url: item.url,
+            });
+        }
+
+        tree
+    }
+
+    fn insert_items(
+        menu_id: i32,
+        parent_id: Option<i32>,
+        items: &[MenuItemInputDto],
+        conn: &mut DbConnection,
+    ) -> AppResult<()> {
+        for (position, item) in items.iter().enumerate() {
+            let target = |item_type: &str| if item.item_type == item_type { item.target_id } else { None };
+            let new_item = NewMenuItem {
+                menu_id,
+                parent_id,
+                position: position as i32,
+                label: item.label.as_ref().map(|l| l.trim().to_string()).filter(|l| !l.is_empty()),
+                item_type: item.item_type.clone(),
+                page_id: target(MENU_ITEM_PAGE),
+                post_id: target(MENU_ITEM_POST),
+                category_id: target(MENU_ITEM_CATEGORY),
+                url: if item.item_type == MENU_ITEM_URL { item.url.clone() } else { None },
+            };
+            let created = MenuRepository::create_item(&new_item, conn)?;
+
+            Self::insert_items(menu_id, Some(created.id), &item.children, conn)?;
+        }
+
+        Ok(())
+    }
+
+    /// Comprueba el tipo de cada elemento y que su destino existe, en todos los
+    /// niveles, y que el árbol no pasa de `MAX_MENU_DEPTH` niveles
+    fn validate_items(items: &[MenuItemInputDto], depth: usize, conn: &mut DbConnection) -> AppResult<()> {
+        if !items.is_empty() && depth > MAX_MENU_DEPTH {
+            return Err(AppError::ValidationError(format!(
+                "Un menú admite como mucho {} niveles de anidamiento",
+                MAX_MENU_DEPTH
+            )));
+        }
+
+        for item in items {
+            if !MENU_ITEM_TYPES.contains(&item.item_type.as_str()) {
+                return Err(AppError::ValidationError(format!(
+                    "Tipo de elemento no válido: '{}'; usa page, post, category o url",
+                    item.item_type
+                )));
+            }
+
+            if item.item_type == MENU_ITEM_URL {
+                Self::validate_url(item.url.as_deref())?;
+                if item.label.as_deref().is_none_or(|l| l.trim().is_empty()) {
+                    return Err(AppError::ValidationError("Los elementos de tipo url necesitan una etiqueta".to_string()));
+                }
+            } else {
+                Self::validate_target(&item.item_type, item.target_id, conn)?;
+            }
+
+            Self::validate_items(&item.children, depth + 1, conn)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_target(item_type: &str, target_id: Option<i32>, conn: &mut DbConnection) -> AppResult<()> {
+        let target_id = target_id.ok_or_else(|| {
+            AppError::ValidationError(format!("Los elementos de tipo {} necesitan target_id", item_type))
+        })?;
+
+        let found = match item_type {
+            MENU_ITEM_PAGE => PageRepository::find_by_id(target_id, conn).map(|_| ()),
+            MENU_ITEM_POST => PostRepository::find_by_id(target_id, conn).map(|_| ()),
+            _ => CategoryRepository::find_by_id(target_id, conn).map(|_| ()),
+        };
+        match found {
+            Ok(()) => Ok(()),
+            Err(DieselError::NotFound) => Err(AppError::ValidationError(format!(
+                "El destino {} con ID {} no existe",
+                item_type, target_id
+            ))),
+            Err(e) => Err(AppError::DatabaseError(e)),
+        }
+    }
+
+    /// Una URL puede ser una ruta del sitio, una dirección absoluta o un `mailto:`
+    fn validate_url(url: Option<&str>) -> AppResult<()> {
+        let url = url.map(str::trim).unwrap_or_default();
+        let valid = ["/", "https://", "http://", "mailto:"].iter().any(|prefix| url.starts_with(prefix));
+        if !valid || url.contains(char::is_whitespace) {
+            return Err(AppError::ValidationError(format!("URL no válida: '{}'", url)));
+        }
+        Ok(())
+    }
+
+    fn validate_location(menu_location: &str) -> AppResult<()> {
+        if !is_valid_slug(menu_location) {
+            return Err(AppError::ValidationError(format!(
+                "Ubicación no válida: '{}'; usa minúsculas, números y guiones",
+                menu_location
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_location_available(menu_location: &str, conn: &mut DbConnection) -> AppResult<()> {
+        match MenuRepository::find_by_location(menu_location, conn) {
+            Ok(_) => Err(AppError::ValidationError(format!("Ya existe un menú en la ubicación '{}'", menu_location))),
+            Err(DieselError::NotFound) => Ok(()),
+            Err(e) => Err(AppError::DatabaseError(e)),
+        }
+    }
+
+    fn find_menu(menu_id: i32, conn: &mut DbConnection) -> AppResult<Menu> {
+        MenuRepository::find_by_id(menu_id, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Menú con ID {} no encontrado", menu_id)),
+            _ => AppError::DatabaseError(e),
+        })
+    }
+}
@@ -2,8 +2,40 @@ pub mod post_service;
 pub mod category_service;
 pub mod user_service;
 pub mod comment_service;
+pub mod health_service;
+pub mod tag_service;
+pub mod media_service;
+pub mod series_service;
+pub mod redirect_service;
+pub mod page_service;
+pub mod menu_service;
+pub mod site_settings_service;
+pub mod wordpress_import_service;
+pub mod markdown_service;
+pub mod backup_service;
+pub mod privacy_service;
+pub mod profile_service;
+pub mod ban_service;
+pub mod invitation_service;
+pub mod api_token_service;
 
 pub use post_service::PostService;
 pub use category_service::CategoryService;
 pub use user_service::UserService;
-pub use comment_service::CommentService; 
\ No newline at end of file
+pub use comment_service::CommentService;
+pub use health_service::HealthService;
+pub use tag_service::TagService;
+pub use media_service::MediaService;
+pub use series_service::SeriesService;
+pub use redirect_service::{RedirectService, SlugLookup};
+pub use page_service::PageService;
+pub use menu_service::MenuService;
+pub use site_settings_service::SiteSettingsService;
+pub use wordpress_import_service::WordPressImportService;
+pub use markdown_service::MarkdownService;
+pub use backup_service::BackupService;
+pub use privacy_service::PrivacyService;
+pub use profile_service::ProfileService;
+pub use ban_service::BanService;
+pub use invitation_service::InvitationService;
+pub use api_token_service::ApiTokenService;
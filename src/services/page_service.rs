@@ -0,0 +1,319 @@
+use std::collections::{HashMap, HashSet};
+use chrono::Local;
+use diesel::result::Error as DieselError;
+use diesel::Connection;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::models::entities::{NewPage, Page, UpdatePage};
+use crate::models::dto::{
+    CreatePageDto, PageBreadcrumbDto, PageDto, PageSummaryDto, PageTreeDto, UpdatePageDto,
+};
+use crate::repositories::PageRepository;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::slug::{explicit_slug_error, is_valid_slug, slugify, with_unique_slug};
+
+/// Plantillas con las que el frontend sabe presentar una página
+const PAGE_TEMPLATES: [&str; 5] = ["default", "full-width", "landing", "contact", "legal"];
+
+pub struct PageService;
+
+impl PageService {
+    /// Obtiene todas las páginas, sin cuerpo, en orden de menú
+    #[instrument(skip_all)]
+    pub fn get_all_pages(conn: &mut DbConnection) -> AppResult<Vec<PageSummaryDto>> {
+        let pages = PageRepository::find_all(conn)?;
+        Ok(pages.into_iter().map(Self::to_summary).collect())
+    }
+
+    /// Obtiene el árbol completo de páginas
+    #[instrument(skip_all)]
+    pub fn get_page_tree(conn: &mut DbConnection) -> AppResult<Vec<PageTreeDto>> {
+        let pages = PageRepository::find_all(conn)?;
+
+        let ids: HashSet<i32> = pages.iter().map(|p| p.id).collect();
+        let mut children: HashMap<Option<i32>, Vec<Page>> = HashMap::new();
+        for page in pages {
+            let parent = page.parent_id.filter(|p| ids.contains(p));
+            children.entry(parent).or_default().push(page);
+        }
+
+        Ok(Self::build_tree(None, &mut children))
+    }
+
+    #[instrument(skip(conn))]
+    pub fn get_page_by_id(page_id: i32, conn: &mut DbConnection) -> AppResult<PageDto> {
+        let page = Self::find_page(page_id, conn)?;
+        Self::load_dto(page, conn)
+    }
+
+    /// Obtiene una página por su ruta completa; se ignoran las barras de los extremos
+    #[instrument(skip(conn))]
+    pub fn get_page_by_path(page_path: &str, conn: &mut DbConnection) -> AppResult<PageDto> {
+        let page_path = page_path.trim_matches('/');
+        let page = PageRepository::find_by_path(page_path, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Página con ruta '{}' no encontrada", page_path)),
+            _ => AppError::DatabaseError(e),
+        })?;
+
+        Self::load_dto(page, conn)
+    }
+
+    #[instrument(skip(dto, conn))]
+    pub fn create_page(dto: CreatePageDto, conn: &mut DbConnection) -> AppResult<PageDto> {
+        if dto.title.trim().is_empty() {
+            return Err(AppError::ValidationError("El título no puede estar vacío".to_string()));
+        }
+        let template = Self::validate_template(dto.template.as_deref().unwrap_or("default"))?;
+        // Un slug generado que ya existe entre las hermanas se desambigua con un sufijo
+        let (slug, auto_slug) = match dto.slug {
+            Some(s) if !s.trim().is_empty() => (Self::validate_slug(&s)?, false),
+            _ => (slugify(&dto.title), true),
+        };
+        if !auto_slug {
+            Self::check_slug_available(dto.parent_id, &slug, None, conn)?;
+        }
+
+        let now = Local::now().naive_local();
+        let mut new_page = NewPage {
+            title: dto.title,
+            slug: slug.clone(),
+            path: String::new(),
+            body: dto.body,
+            template,
+            parent_id: dto.parent_id,
+            menu_order: dto.menu_order.unwrap_or(0),
+            created_at: Some(now),
+            updated_at: Some(now),
+        };
+
+        // El padre queda bloqueado hasta insertar, para que su ruta no cambie entretanto
+        let page = conn.transaction::<_, AppError, _>(|conn| {
+            let parent = dto.parent_id.map(|parent_id| Self::find_parent(parent_id, conn)).transpose()?;
+            let mut insert = |candidate: &str, conn: &mut DbConnection| -> AppResult<Page> {
+                new_page.slug = candidate.to_string();
+                new_page.path = Self::child_path(parent.as_ref(), candidate);
+                Ok(PageRepository::create(&new_page, conn)?)
+            };
+            if auto_slug {
+                with_unique_slug(&slug, conn, insert)
+            } else {
+                insert(&slug, conn).map_err(|e| explicit_slug_error(e, &slug))
+            }
+        })?;
+
+        Self::load_dto(page, conn)
+    }
+
+    /// Actualiza una página. Si cambia su slug o su padre, se recalculan su ruta
+    /// y las de todas sus subpáginas en la misma transacción.
+    #[instrument(skip(dto, conn))]
+    pub fn update_page(page_id: i32, dto: UpdatePageDto, conn: &mut DbConnection) -> AppResult<PageDto> {
+        if let Some(ref title) = dto.title {
+            if title.trim().is_empty() {
+                return Err(AppError::ValidationError("El título no puede estar vacío".to_string()));
+            }
+        }
+        let template = dto.template.as_deref().map(Self::validate_template).transpose()?;
+
+        // La página y su nuevo padre se leen bloqueados, para que otro cambio de
+        // padre simultáneo no cree un ciclo ni deje una ruta desactualizada
+        let updated_page = conn.transaction::<_, AppError, _>(|conn| {
+            let page = PageRepository::lock_by_id(page_id, conn).map_err(|e| match e {
+                DieselError::NotFound => AppError::NotFound(format!("Página con ID {} no encontrada", page_id)),
+                _ => AppError::DatabaseError(e),
+            })?;
+
+            // El nuevo padre no puede ser la propia página ni una de sus subpáginas
+            let parent_id = dto.parent_id.unwrap_or(page.parent_id);
+            let parent = match parent_id {
+                Some(parent_id) => {
+                    let parent = Self::find_parent(parent_id, conn)?;
+                    if parent.id == page.id || parent.path.starts_with(&format!("{}/", page.path)) {
+                        return Err(AppError::ValidationError(
+                            "Una página no puede ser hija de sí misma ni de una de sus subpáginas".to_string(),
+                        ));
+                    }
+                    Some(parent)
+                }
+                None => None,
+            };
+
+            // Como en los posts, el slug solo cambia si se indica o se pide regenerarlo
+            let (slug, auto_slug) = match dto.slug {
+                Some(ref s) if !s.trim().is_empty() => (Self::validate_slug(s)?, false),
+                _ if dto.regenerate_slug == Some(true) => {
+                    (slugify(dto.title.as_deref().unwrap_or(&page.title)), true)
+                }
+                _ => (page.slug.clone(), false),
+            };
+            let moved = slug != page.slug || parent_id != page.parent_id;
+            if moved && !auto_slug {
+                Self::check_slug_available(parent_id, &slug, Some(page.id), conn)?;
+            }
+
+            let mut update_data = UpdatePage {
+                title: dto.title,
+                slug: None,
+                path: None,
+                body: dto.body,
+                template,
+                parent_id: dto.parent_id,
+                menu_order: dto.menu_order,
+                updated_at: Some(Local::now().naive_local()),
+            };
+
+            let mut save = |candidate: &str, conn: &mut DbConnection| -> AppResult<Page> {
+                if moved {
+                    update_data.slug = Some(candidate.to_string());
+                    update_data.path = Some(Self::child_path(parent.as_ref(), candidate));
+                }
+                Ok(PageRepository::update(page_id, &update_data, conn)?)
+            };
+            let updated_page = if moved && auto_slug {
+                with_unique_slug(&slug, conn, save)?
+            } else {
+                save(&slug, conn).map_err(|e| explicit_slug_error(e, &slug))?
+            };
+            if updated_page.path != page.path {
+                PageRepository::move_descendants(&page.path, &updated_page.path, conn)?;
+            }
+            Ok(updated_page)
+        })?;
+
+        Self::load_dto(updated_page, conn)
+    }
+
+    /// Elimina una página; si tiene subpáginas hay que moverlas o eliminarlas antes
+    #[instrument(skip(conn))]
+    pub fn delete_page(page_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        Self::find_page(page_id, conn)?;
+
+        let children = PageRepository::count_children(page_id, conn)?;
+        if children > 0 {
+            return Err(AppError::ValidationError(format!(
+                "La página tiene {} subpágina(s); muévelas o elimínalas antes",
+                children
+            )));
+        }
+
+        let deleted = PageRepository::delete(page_id, conn)?;
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!("No se pudo eliminar la página con ID {}", page_id)));
+        }
+
+        Ok(())
+    }
+
+    fn load_dto(page: Page, conn: &mut DbConnection) -> AppResult<PageDto> {
+        let breadcrumbs = PageRepository::find_ancestors(&page.path, conn)?
+            .into_iter()
+            .map(|p| PageBreadcrumbDto { id: p.id, title: p.title, path: p.path })
+            .collect();
+
+        Ok(PageDto {
+            id: page.id,
+            title: page.title,
+            slug: page.slug,
+            path: page.path,
+            body: page.body,
+            template: page.template,
+            parent_id: page.parent_id,
+            menu_order: page.menu_order,
+            created_at: page.created_at,
+            updated_at: page.updated_at,
+            breadcrumbs,
+        })
+    }
+
+    fn to_summary(page: Page) -> PageSummaryDto {
+        PageSummaryDto {
+            id: page.id,
+            title: page.title,
+            slug: page.slug,
+            path: page.path,
+            template: page.template,
+            parent_id: page.parent_id,
+            menu_order: page.menu_order,
+            updated_at: page.updated_at,
+        }
+    }
+
+    fn build_tree(parent: Option<i32>, children: &mut HashMap<Option<i32>, Vec<Page>>) -> Vec<PageTreeDto> {
+        let nodes = children.remove(&parent).unwrap_or_default();
+
+        nodes
+            .into_iter()
+            .map(|page| PageTreeDto {
+                children: Self::build_tree(Some(page.id), children),
+                id: page.id,
+                title: page.title,
+                slug: page.slug,
+                path: page.path,
+                template: page.template,
+                menu_order: page.menu_order,
+            })
+            .collect()
+    }
+
+    /// Ruta de una página con el slug indicado bajo `parent`
+    fn child_path(parent: Option<&Page>, page_slug: &str) -> String {
+        match parent {
+            Some(parent) => format!("{}/{}", parent.path, page_slug),
+            None => page_slug.to_string(),
+        }
+    }
+
+    /// Los slugs forman la ruta, así que no pueden contener `/` ni otros símbolos
+    fn validate_slug(page_slug: &str) -> AppResult<String> {
+        let page_slug = page_slug.trim();
+        if !is_valid_slug(page_slug) {
+            return Err(AppError::ValidationError(format!(
+                "Slug no válido: '{}'; usa minúsculas, números y guiones",
+                page_slug
+            )));
+        }
+        Ok(page_slug.to_string())
+    }
+
+    fn validate_template(template: &str) -> AppResult<String> {
+        if !PAGE_TEMPLATES.contains(&template) {
+            return Err(AppError::ValidationError(format!(
+                "Plantilla no válida: '{}'; usa una de: {}",
+                template,
+                PAGE_TEMPLATES.join(", ")
+            )));
+        }
+        Ok(template.to_string())
+    }
+
+    fn check_slug_available(
+        parent_id: Option<i32>,
+        page_slug: &str,
+        except_id: Option<i32>,
+        conn: &mut DbConnection,
+    ) -> AppResult<()> {
+        match PageRepository::find_sibling(parent_id, page_slug, conn)? {
+            Some(existing) if Some(existing.id) != except_id => Err(AppError::ValidationError(format!(
+                "Ya existe una página con el slug '{}' en ese nivel",
+                page_slug
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    fn find_page(page_id: i32, conn: &mut DbConnection) -> AppResult<Page> {
+        PageRepository::find_by_id(page_id, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Página con ID {} no encontrada", page_id)),
+            _ => AppError::DatabaseError(e),
+        })
+    }
+
+    /// El padre se lee bloqueado: quien lo llama está en una transacción y
+    /// construye con su ruta la de la página hija
+    fn find_parent(parent_id: i32, conn: &mut DbConnection) -> AppResult<Page> {
+        PageRepository::lock_by_id(parent_id, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::ValidationError(format!("La página padre con ID {} no existe", parent_id)),
+            _ => AppError::DatabaseError(e),
+        })
+    }
+}
@@ -1,119 +1,140 @@
+use std::collections::HashMap;
 use chrono::Local;
 use diesel::result::Error as DieselError;
-use diesel::prelude::*;
-use diesel::RunQueryDsl;
-use diesel::QueryDsl;
-use diesel::ExpressionMethods;
 use diesel::Connection;
-use crate::config::database::DbConnection;
-use crate::models::entities::{NewPost, UpdatePost};
-use crate::models::dto::{PostDto, PostDetailDto, CreatePostDto, UpdatePostDto};
-use crate::repositories::PostRepository;
+use crate::auth::Claims;
+use crate::db::DbConnection;
+use crate::models::entities::{NewPost, Post, UpdatePost, POST_STATUSES, POST_STATUS_PUBLISHED, SLUG_ENTITY_POST};
+use crate::models::dto::{
+    CreatePostDto, FeaturedImageDto, MediaDto, PostDetailDto, PostDto, PostSummaryDto, UpdatePostDto,
+};
+use crate::repositories::{MediaRepository, PostRepository, SlugHistoryRepository, TagRepository};
+use crate::services::{CategoryService, MediaService, SeriesService, SiteSettingsService, SlugLookup, TagService};
 use crate::utils::error::{AppError, AppResult};
-use crate::utils::slug::slugify;
+use crate::metrics;
+use crate::utils::slug::{explicit_slug_error, slugify, with_unique_slug_in_history};
+use crate::utils::text;
+use tracing::instrument;
 
 pub struct PostService;
 
 impl PostService {
-    /// Obtiene todos los posts
-    pub fn get_all_posts(conn: &mut DbConnection) -> AppResult<Vec<PostDto>> {
-        let posts = PostRepository::find_all(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+    /// Obtiene los posts publicados: todos, o con `page` solo esa página, con
+    /// el tamaño del ajuste `posts_per_page`
+    #[instrument(skip(conn))]
+    pub fn get_all_posts(page: Option<u32>, conn: &mut DbConnection) -> AppResult<Vec<PostDto>> {
+        let posts = match page {
+            None => PostRepository::find_published(conn),
+            Some(0) => return Err(AppError::ValidationError("La página debe ser 1 o mayor".to_string())),
+            Some(page) => {
+                let per_page = i64::from(SiteSettingsService::get(conn)?.posts_per_page);
+                PostRepository::find_published_page(per_page, (i64::from(page) - 1) * per_page, conn)
+            }
+        }
+        .map_err(AppError::DatabaseError)?;
         
         // Convertir entidades a DTOs
-        let post_dtos = posts.into_iter()
-            .map(|post| PostDto {
-                id: post.id,
-                title: post.title,
-                slug: post.slug,
-                body: post.body,
-                created_at: post.created_at,
-                updated_at: post.updated_at,
-            })
-            .collect();
-        
-        Ok(post_dtos)
+        Self::to_dtos(posts, conn)
     }
     
-    /// Obtiene un post por su ID
-    pub fn get_post_by_id(post_id: i32, conn: &mut DbConnection) -> AppResult<PostDto> {
-        let post = PostRepository::find_by_id(post_id, conn)
-            .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Post con ID {} no encontrado", post_id)),
-                _ => AppError::DatabaseError(e)
-            })?;
-        
-        Ok(PostDto {
-            id: post.id,
-            title: post.title,
-            slug: post.slug,
-            body: post.body,
-            created_at: post.created_at,
-            updated_at: post.updated_at,
-        })
+    /// Obtiene los posts en su versión resumida, sin el cuerpo
+    #[instrument(skip(conn))]
+    pub fn get_post_summaries(page: Option<u32>, conn: &mut DbConnection) -> AppResult<Vec<PostSummaryDto>> {
+        let posts = Self::get_all_posts(page, conn)?;
+        Ok(posts.into_iter().map(PostSummaryDto::from).collect())
     }
     
-    /// Obtiene un post por su slug
-    pub fn get_post_by_slug(slug: &str, conn: &mut DbConnection) -> AppResult<PostDto> {
-        let post = PostRepository::find_by_slug(slug, conn)
-            .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Post con slug '{}' no encontrado", slug)),
-                _ => AppError::DatabaseError(e)
-            })?;
+    /// Obtiene un post por su ID. Los borradores y posts privados solo los ven
+    /// su autor y los administradores.
+    #[instrument(skip(viewer, conn))]
+    pub fn get_post_by_id(post_id: i32, viewer: Option<&Claims>, conn: &mut DbConnection) -> AppResult<PostDto> {
+        let post = Self::find_visible(post_id, viewer, conn)?;
+        Self::to_dto(post, conn)
+    }
+    
+    /// Obtiene un post por su slug. Si es un slug antiguo, devuelve el actual
+    /// para que el cliente sea redirigido.
+    #[instrument(skip(viewer, conn))]
+    pub fn get_post_by_slug(slug: &str, viewer: Option<&Claims>, conn: &mut DbConnection) -> AppResult<SlugLookup<PostDto>> {
+        match PostRepository::find_by_slug(slug, conn) {
+            Ok(post) if !Self::is_visible(&post, viewer) => {
+                return Err(AppError::NotFound(format!("Post con slug '{}' no encontrado", slug)));
+            }
+            Ok(post) => return Ok(SlugLookup::Found(Self::to_dto(post, conn)?)),
+            Err(DieselError::NotFound) => {}
+            Err(e) => return Err(AppError::DatabaseError(e)),
+        }
         
-        Ok(PostDto {
-            id: post.id,
-            title: post.title,
-            slug: post.slug,
-            body: post.body,
-            created_at: post.created_at,
-            updated_at: post.updated_at,
-        })
+        // Buscar en el historial de slugs
+        match SlugHistoryRepository::find(SLUG_ENTITY_POST, slug, conn)? {
+            // La redirección revelaría el slug actual de un post no publicado
+            Some(entry) => match PostRepository::find_by_id(entry.entity_id, conn)? {
+                post if Self::is_visible(&post, viewer) => Ok(SlugLookup::Moved(post.slug)),
+                _ => Err(AppError::NotFound(format!("Post con slug '{}' no encontrado", slug))),
+            },
+            None => Err(AppError::NotFound(format!("Post con slug '{}' no encontrado", slug))),
+        }
     }
     
     /// Obtiene un post con sus categorías y comentarios
-    pub fn get_post_detail(post_id: i32, conn: &mut DbConnection) -> AppResult<PostDetailDto> {
+    #[instrument(skip(viewer, conn))]
+    pub fn get_post_detail(post_id: i32, viewer: Option<&Claims>, conn: &mut DbConnection) -> AppResult<PostDetailDto> {
         // Obtener el post
-        let post = PostRepository::find_by_id(post_id, conn)
-            .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Post con ID {} no encontrado", post_id)),
-                _ => AppError::DatabaseError(e)
-            })?;
+        let post = Self::find_visible(post_id, viewer, conn)?;
         
         // Obtener categorías del post
         let categories = PostRepository::get_categories(post_id, conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+            .map_err(AppError::DatabaseError)?;
         
         // Convertir categorías a DTOs
-        let category_dtos = categories.into_iter()
-            .map(|category| crate::models::dto::CategoryDto {
-                id: category.id,
-                name: category.name,
-                slug: category.slug,
-                description: category.description,
-                created_at: category.created_at,
-                updated_at: category.updated_at,
-            })
+        let mut category_dtos = Vec::with_capacity(categories.len());
+        for category in categories {
+            category_dtos.push(CategoryService::load_dto(category, conn)?);
+        }
+        
+        // Obtener etiquetas del post
+        let tag_dtos = TagRepository::find_by_post(post_id, conn)?
+            .into_iter()
+            .map(TagService::to_dto)
             .collect();
         
+        // Obtener la galería del post
+        let media = MediaRepository::find_by_post(post_id, conn)?;
+        let media_dtos = MediaService::to_dtos(media, conn)?;
+        
+        // Navegación de la serie, si el post pertenece a una
+        let series = SeriesService::get_navigation(post_id, conn)?;
+        
         // Obtener comentarios del post (pendiente de implementar el repositorio de comentarios)
         // Por ahora, devolvemos una lista vacía
         let comment_dtos = Vec::new();
         
+        let post = Self::to_dto(post, conn)?;
+        
         Ok(PostDetailDto {
             id: post.id,
             title: post.title,
             slug: post.slug,
             body: post.body,
+            excerpt: post.excerpt,
+            featured_image: post.featured_image,
+            word_count: post.word_count,
+            reading_time_minutes: post.reading_time_minutes,
+            author_id: post.author_id,
+            status: post.status,
             created_at: post.created_at,
             updated_at: post.updated_at,
             categories: category_dtos,
+            tags: tag_dtos,
+            media: media_dtos,
+            series,
             comments: comment_dtos,
         })
     }
     
-    /// Crea un nuevo post
-    pub fn create_post(dto: CreatePostDto, conn: &mut DbConnection) -> AppResult<PostDto> {
+    /// Crea un nuevo post firmado por `author_id`
+    #[instrument(skip_all)]
+    pub fn create_post(dto: CreatePostDto, author_id: Option<i32>, conn: &mut DbConnection) -> AppResult<PostDto> {
         // Validar datos
         if dto.title.trim().is_empty() {
             return Err(AppError::ValidationError("El título no puede estar vacío".to_string()));
@@ -123,138 +144,358 @@ impl PostService {
             return Err(AppError::ValidationError("El contenido no puede estar vacío".to_string()));
         }
         
-        // Generar slug si no se proporciona
-        let slug = match dto.slug {
-            Some(s) if !s.trim().is_empty() => s,
-            _ => slugify(&dto.title),
+        // Generar slug si no se proporciona; si el generado ya existe se le añade un sufijo
+        let (slug, auto_slug) = match dto.slug {
+            Some(s) if !s.trim().is_empty() => (s, false),
+            _ => (slugify(&dto.title), true),
         };
         
-        // Verificar si ya existe un post con el mismo slug
-        match PostRepository::find_by_slug(&slug, conn) {
-            Ok(_) => return Err(AppError::ValidationError(format!("Ya existe un post con el slug '{}'", slug))),
-            Err(DieselError::NotFound) => {}, // Es lo que queremos, que no exista
-            Err(e) => return Err(AppError::DatabaseError(e)),
+        Self::validate_featured_image(dto.featured_media_id, dto.featured_image_url.as_deref(), conn)?;
+        let status = match dto.status {
+            Some(status) => Self::validate_status(status)?,
+            None => POST_STATUS_PUBLISHED.to_string(),
+        };
+        
+        // Verificar si ya existe un post con el slug indicado
+        if !auto_slug {
+            match PostRepository::find_by_slug(&slug, conn) {
+                Ok(_) => return Err(AppError::ValidationError(format!("Ya existe un post con el slug '{}'", slug))),
+                Err(DieselError::NotFound) => {}, // Es lo que queremos, que no exista
+                Err(e) => return Err(AppError::DatabaseError(e)),
+            }
         }
         
         // Crear el post
         let now = Local::now().naive_local();
-        let new_post = NewPost {
+        let mut new_post = NewPost {
             title: dto.title,
             body: dto.body,
-            slug,
+            slug: slug.clone(),
             created_at: Some(now),
             updated_at: Some(now),
+            excerpt: Self::normalize_excerpt(dto.excerpt),
+            featured_media_id: dto.featured_media_id,
+            featured_image_url: dto.featured_image_url,
+            author_id,
+            status,
         };
         
-        // Insertar el post
-        let post = if let Some(category_ids) = dto.category_ids {
-            if !category_ids.is_empty() {
-                // Insertar con categorías
-                PostRepository::create_with_categories(
-                    &new_post.title,
-                    &new_post.body,
-                    Some(&new_post.slug),
-                    &category_ids,
-                    conn
-                )
-                .map_err(|e| AppError::DatabaseError(e))?
+        // Insertar el post y sus etiquetas en una única transacción
+        let category_ids = dto.category_ids.unwrap_or_default();
+        let post = conn.transaction::<_, AppError, _>(|conn| {
+            let mut insert = |slug: &str, conn: &mut DbConnection| -> AppResult<Post> {
+                new_post.slug = slug.to_string();
+                let post = if !category_ids.is_empty() {
+                    // Insertar con categorías
+                    PostRepository::create_with_categories(&new_post, &category_ids, conn)?
+                } else {
+                    // Insertar sin categorías
+                    PostRepository::create(&new_post, conn)?
+                };
+                Ok(post)
+            };
+            
+            let post = if auto_slug {
+                with_unique_slug_in_history(SLUG_ENTITY_POST, None, &slug, conn, insert)?
             } else {
-                // Insertar sin categorías
-                PostRepository::create(&new_post, conn)
-                    .map_err(|e| AppError::DatabaseError(e))?
+                insert(&slug, conn).map_err(|e| explicit_slug_error(e, &slug))?
+            };
+            
+            // Crear al vuelo las etiquetas que no existan
+            if let Some(ref tag_names) = dto.tags {
+                TagRepository::set_post_tags(post.id, tag_names, conn)?;
             }
-        } else {
-            // Insertar sin categorías
-            PostRepository::create(&new_post, conn)
-                .map_err(|e| AppError::DatabaseError(e))?
-        };
+            
+            // Si el slug indicado era uno antiguo de otro post, deja de redirigir
+            // (los generados nunca lo son)
+            SlugHistoryRepository::release(SLUG_ENTITY_POST, &post.slug, conn)?;
+            
+            Ok(post)
+        })?;
         
-        Ok(PostDto {
-            id: post.id,
-            title: post.title,
-            slug: post.slug,
-            body: post.body,
-            created_at: post.created_at,
-            updated_at: post.updated_at,
-        })
+        if post.status == POST_STATUS_PUBLISHED {
+            metrics::POSTS_PUBLISHED_TOTAL.inc();
+        }
+        
+        Self::to_dto(post, conn)
     }
     
-    /// Actualiza un post existente
-    pub fn update_post(post_id: i32, dto: UpdatePostDto, conn: &mut DbConnection) -> AppResult<PostDto> {
-        // Verificar que el post existe
-        let post = PostRepository::find_by_id(post_id, conn)
-            .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Post con ID {} no encontrado", post_id)),
-                _ => AppError::DatabaseError(e)
-            })?;
+    /// Actualiza un post existente; solo su autor o un administrador
+    #[instrument(skip(dto, user, conn))]
+    pub fn update_post(post_id: i32, dto: UpdatePostDto, user: &Claims, conn: &mut DbConnection) -> AppResult<PostDto> {
+        let post = Self::find_editable(post_id, user, conn)?;
         
-        // Verificar si se está cambiando el slug y si el nuevo slug ya existe
-        if let Some(ref new_slug) = dto.slug {
-            if new_slug != &post.slug {
-                match PostRepository::find_by_slug(new_slug, conn) {
-                    Ok(_) => return Err(AppError::ValidationError(format!("Ya existe un post con el slug '{}'", new_slug))),
-                    Err(DieselError::NotFound) => {}, // Es lo que queremos, que no exista
-                    Err(e) => return Err(AppError::DatabaseError(e)),
-                }
+        // El slug solo cambia si se indica uno o se pide regenerarlo a partir del título,
+        // para no romper los enlaces existentes. El regenerado se desambigua con un sufijo.
+        let (new_slug, auto_slug) = match dto.slug {
+            Some(s) if !s.trim().is_empty() => (Some(s), false),
+            _ if dto.regenerate_slug == Some(true) => {
+                (Some(slugify(dto.title.as_deref().unwrap_or(&post.title))), true)
+            }
+            _ => (None, false),
+        };
+        let new_slug = new_slug.filter(|s| s != &post.slug);
+        
+        // Verificar que el slug indicado no esté en uso
+        if let Some(new_slug) = new_slug.as_ref().filter(|_| !auto_slug) {
+            match PostRepository::find_by_slug(new_slug, conn) {
+                Ok(_) => return Err(AppError::ValidationError(format!("Ya existe un post con el slug '{}'", new_slug))),
+                Err(DieselError::NotFound) => {}, // Es lo que queremos, que no exista
+                Err(e) => return Err(AppError::DatabaseError(e)),
             }
         }
         
+        let status = dto.status.map(Self::validate_status).transpose()?;
+        
+        // Una imagen destacada sustituye a la otra: asignar una quita la anterior
+        let featured_media_id = dto.featured_media_id;
+        let featured_image_url = dto.featured_image_url;
+        Self::validate_featured_image(
+            featured_media_id.flatten(),
+            featured_image_url.as_ref().and_then(|url| url.as_deref()),
+            conn,
+        )?;
+        let (featured_media_id, featured_image_url) = match (featured_media_id, featured_image_url) {
+            (Some(Some(media_id)), None) => (Some(Some(media_id)), Some(None)),
+            (None, Some(Some(url))) => (Some(None), Some(Some(url))),
+            other => other,
+        };
+        
         // Preparar datos para actualización
-        let update_data = UpdatePost {
+        let mut update_data = UpdatePost {
             title: dto.title,
             body: dto.body,
-            slug: dto.slug,
+            slug: new_slug.clone(),
             updated_at: Some(Local::now().naive_local()),
+            excerpt: dto.excerpt.map(Self::normalize_excerpt),
+            featured_media_id,
+            featured_image_url,
+            status,
         };
         
-        // Actualizar el post
-        let updated_post = match &dto.category_ids {
-            Some(category_ids) => {
-                // Actualizar con categorías
-                PostRepository::update_with_categories(
-                    post_id,
-                    update_data.title.as_deref(),
-                    update_data.body.as_deref(),
-                    update_data.slug.as_deref(),
-                    Some(category_ids),
-                    conn
-                )
-                .map_err(|e| AppError::DatabaseError(e))?
-            },
-            None => {
-                // Actualizar sin categorías
-                PostRepository::update(post_id, &update_data, conn)
-                    .map_err(|e| AppError::DatabaseError(e))?
+        // Actualizar el post y sus etiquetas en una única transacción
+        let updated_post = conn.transaction::<_, AppError, _>(|conn| {
+            let save = |update_data: &UpdatePost, conn: &mut DbConnection| -> AppResult<Post> {
+                let updated_post = match &dto.category_ids {
+                    Some(category_ids) => {
+                        // Actualizar con categorías
+                        PostRepository::update_with_categories(post_id, update_data, Some(category_ids), conn)?
+                    },
+                    None => {
+                        // Actualizar sin categorías
+                        PostRepository::update(post_id, update_data, conn)?
+                    }
+                };
+                Ok(updated_post)
+            };
+            
+            let updated_post = match new_slug {
+                Some(ref base) if auto_slug => {
+                    with_unique_slug_in_history(SLUG_ENTITY_POST, Some(post_id), base, conn, |slug, conn| {
+                        update_data.slug = Some(slug.to_string());
+                        save(&update_data, conn)
+                    })?
+                }
+                _ => save(&update_data, conn)
+                    .map_err(|e| explicit_slug_error(e, update_data.slug.as_deref().unwrap_or_default()))?,
+            };
+            
+            // Si se proporcionan etiquetas, reemplazar las actuales
+            if let Some(ref tag_names) = dto.tags {
+                TagRepository::set_post_tags(post_id, tag_names, conn)?;
+            }
+            
+            // El slug anterior queda en el historial para redirigir los enlaces antiguos
+            if updated_post.slug != post.slug {
+                SlugHistoryRepository::record_rename(SLUG_ENTITY_POST, post_id, &post.slug, &updated_post.slug, conn)?;
             }
+            
+            Ok(updated_post)
+        })?;
+        
+        // Un borrador o post privado que pasa a publicado también cuenta
+        if post.status != POST_STATUS_PUBLISHED && updated_post.status == POST_STATUS_PUBLISHED {
+            metrics::POSTS_PUBLISHED_TOTAL.inc();
+        }
+        
+        Self::to_dto(updated_post, conn)
+    }
+    
+    /// Elimina un post; solo su autor o un administrador
+    #[instrument(skip(user, conn))]
+    pub fn delete_post(post_id: i32, user: &Claims, conn: &mut DbConnection) -> AppResult<()> {
+        Self::find_editable(post_id, user, conn)?;
+        
+        // Eliminar el post
+        let deleted = PostRepository::delete(post_id, conn)
+            .map_err(AppError::DatabaseError)?;
+        
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!("No se pudo eliminar el post con ID {}", post_id)));
+        }
+        
+        Ok(())
+    }
+    
+    /// Convierte varios posts a DTOs cargando sus imágenes destacadas en una sola consulta
+    pub fn to_dtos(posts: Vec<Post>, conn: &mut DbConnection) -> AppResult<Vec<PostDto>> {
+        let media_ids: Vec<i32> = posts.iter().filter_map(|p| p.featured_media_id).collect();
+        let featured: HashMap<i32, MediaDto> = if media_ids.is_empty() {
+            HashMap::new()
+        } else {
+            let media = MediaRepository::find_by_ids(&media_ids, conn)?;
+            MediaService::to_dtos(media, conn)?
+                .into_iter()
+                .map(|m| (m.id, m))
+                .collect()
         };
         
-        Ok(PostDto {
-            id: updated_post.id,
-            title: updated_post.title,
-            slug: updated_post.slug,
-            body: updated_post.body,
-            created_at: updated_post.created_at,
-            updated_at: updated_post.updated_at,
-        })
+        Ok(posts
+            .into_iter()
+            .map(|post| {
+                let media = post.featured_media_id.and_then(|id| featured.get(&id));
+                Self::build_dto(post, media)
+            })
+            .collect())
+    }
+    
+    pub fn to_dto(post: Post, conn: &mut DbConnection) -> AppResult<PostDto> {
+        let mut dtos = Self::to_dtos(vec![post], conn)?;
+        Ok(dtos.remove(0))
+    }
+    
+    /// Completa el post con los campos calculados: extracto, recuento de
+    /// palabras, tiempo de lectura e imagen destacada
+    fn build_dto(post: Post, featured_media: Option<&MediaDto>) -> PostDto {
+        let word_count = text::word_count(&post.body);
+        let excerpt = post.excerpt.unwrap_or_else(|| text::excerpt(&post.body));
+        
+        let featured_image = match (featured_media, post.featured_image_url) {
+            (Some(media), _) => Some(FeaturedImageDto {
+                media_id: Some(media.id),
+                url: media.url.clone(),
+                thumbnail_url: media.thumbnail_url.clone(),
+                srcset: media.srcset.clone(),
+                srcset_webp: media.srcset_webp.clone(),
+                alt_text: media.alt_text.clone(),
+                width: media.width,
+                height: media.height,
+            }),
+            (None, Some(url)) => Some(FeaturedImageDto {
+                media_id: None,
+                url,
+                thumbnail_url: None,
+                srcset: None,
+                srcset_webp: None,
+                alt_text: None,
+                width: None,
+                height: None,
+            }),
+            (None, None) => None,
+        };
+        
+        PostDto {
+            id: post.id,
+            title: post.title,
+            slug: post.slug,
+            body: post.body,
+            excerpt,
+            featured_image,
+            word_count,
+            reading_time_minutes: text::reading_time_minutes(word_count),
+            author_id: post.author_id,
+            status: post.status,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+        }
     }
     
-    /// Elimina un post
-    pub fn delete_post(post_id: i32, conn: &mut DbConnection) -> AppResult<()> {
-        // Verificar que el post existe
-        let _ = PostRepository::find_by_id(post_id, conn)
+    /// Post que `viewer` puede ver; los que no, como si no existieran
+    pub fn find_visible(post_id: i32, viewer: Option<&Claims>, conn: &mut DbConnection) -> AppResult<Post> {
+        let post = PostRepository::find_by_id(post_id, conn)
             .map_err(|e| match e {
                 DieselError::NotFound => AppError::NotFound(format!("Post con ID {} no encontrado", post_id)),
                 _ => AppError::DatabaseError(e)
             })?;
+        Self::check_visible(&post, viewer)?;
+        Ok(post)
+    }
+    
+    /// Post que `user` puede modificar: solo su autor o un administrador. Los
+    /// que ni siquiera puede ver responden como si no existieran.
+    pub fn find_editable(post_id: i32, user: &Claims, conn: &mut DbConnection) -> AppResult<Post> {
+        let post = Self::find_visible(post_id, Some(user), conn)?;
+        if !Self::is_author_or_admin(&post, user) {
+            return Err(AppError::AuthorizationError(
+                "Solo el autor del post o un administrador puede modificarlo".to_string(),
+            ));
+        }
+        Ok(post)
+    }
+    
+    /// Los borradores y posts privados solo son visibles para su autor y los
+    /// administradores
+    fn is_visible(post: &Post, viewer: Option<&Claims>) -> bool {
+        post.status == POST_STATUS_PUBLISHED || viewer.is_some_and(|claims| Self::is_author_or_admin(post, claims))
+    }
+    
+    fn is_author_or_admin(post: &Post, claims: &Claims) -> bool {
+        claims.role == "admin" || claims.sub.parse::<i32>().ok() == post.author_id
+    }
+    
+    fn check_visible(post: &Post, viewer: Option<&Claims>) -> AppResult<()> {
+        if !Self::is_visible(post, viewer) {
+            return Err(AppError::NotFound(format!("Post con ID {} no encontrado", post.id)));
+        }
+        Ok(())
+    }
+    
+    fn validate_status(status: String) -> AppResult<String> {
+        if !POST_STATUSES.contains(&status.as_str()) {
+            return Err(AppError::ValidationError(format!(
+                "Estado no válido: '{}'; usa published, draft o private",
+                status
+            )));
+        }
+        Ok(status)
+    }
+    
+    /// Un extracto vacío equivale a no tenerlo y usar el generado
+    fn normalize_excerpt(excerpt: Option<String>) -> Option<String> {
+        excerpt
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+    }
+    
+    /// Comprueba que la imagen destacada es un único fichero de imagen existente
+    /// o una URL absoluta (o relativa a la raíz del sitio)
+    fn validate_featured_image(media_id: Option<i32>, url: Option<&str>, conn: &mut DbConnection) -> AppResult<()> {
+        if media_id.is_some() && url.is_some() {
+            return Err(AppError::ValidationError(
+                "Indica featured_media_id o featured_image_url, no ambos".to_string()
+            ));
+        }
         
-        // Eliminar el post
-        let deleted = PostRepository::delete(post_id, conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+        if let Some(media_id) = media_id {
+            let media = match MediaRepository::find_by_id(media_id, conn) {
+                Ok(media) => media,
+                Err(DieselError::NotFound) => {
+                    return Err(AppError::ValidationError(format!("El fichero con ID {} no existe", media_id)));
+                }
+                Err(e) => return Err(AppError::DatabaseError(e)),
+            };
+            if !media.mime_type.starts_with("image/") {
+                return Err(AppError::ValidationError(format!("El fichero con ID {} no es una imagen", media_id)));
+            }
+        }
         
-        if deleted == 0 {
-            return Err(AppError::NotFound(format!("No se pudo eliminar el post con ID {}", post_id)));
+        if let Some(url) = url {
+            let valid = url.starts_with("https://") || url.starts_with("http://") || url.starts_with('/');
+            if !valid || url.chars().any(char::is_whitespace) {
+                return Err(AppError::ValidationError(format!("URL de imagen destacada no válida: '{}'", url)));
+            }
         }
         
         Ok(())
     }
-} 
\ No newline at end of file
+}
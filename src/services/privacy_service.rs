@@ -0,0 +1,135 @@
+use chrono::Local;
+use diesel::prelude::*;
+use diesel::result::Error as DieselError;
+use tracing::instrument;
+use crate::config;
+use crate::db::{lower, DbConnection};
+use crate::db::schema::{comments, posts, users};
+use crate::models::dto::{DeleteAccountDto, PersonalDataExportDto, PurgeCommenterEmailResultDto};
+use crate::models::entities::{Comment, User};
+use crate::repositories::PostRepository;
+use crate::services::{CommentService, MediaService, PostService, ProfileService, UserService};
+use crate::utils::error::{AppError, AppResult};
+
+/// Derechos sobre los datos personales: acceso, supresión de la cuenta y
+/// borrado del email de los comentaristas anónimos
+pub struct PrivacyService;
+
+impl PrivacyService {
+    /// Perfil, posts (en cualquier estado) y comentarios del usuario. Los
+    /// anónimos no se incluyen aunque lleven su email: cualquiera puede
+    /// escribir un comentario con un email ajeno.
+    #[instrument(skip(conn))]
+    pub fn export_personal_data(user_id: i32, conn: &mut DbConnection) -> AppResult<PersonalDataExportDto> {
+        let user = Self::find_user(user_id, conn)?;
+        let profile = UserService::get_user_by_id(user.id, conn)?;
+        let public_profile = ProfileService::get_profile(user.id, conn)?;
+
+        let user_posts = PostRepository::find_by_author(user.id, conn)?;
+        let user_posts = PostService::to_dtos(user_posts, conn)?;
+
+        let user_comments = comments::table
+            .filter(comments::user_id.eq(user.id))
+            .order(comments::created_at.asc())
+            .load::<Comment>(conn)?
+            .into_iter()
+            .map(CommentService::to_dto)
+            .collect();
+
+        Ok(PersonalDataExportDto {
+            generated_at: Local::now().naive_local(),
+            profile,
+            public_profile,
+            posts: user_posts,
+            comments: user_comments,
+        })
+    }
+
+    /// Elimina la cuenta a petición del usuario, previa confirmación con la
+    /// contraseña actual
+    #[instrument(skip(dto, conn))]
+    pub fn delete_account(user_id: i32, dto: DeleteAccountDto, conn: &mut DbConnection) -> AppResult<()> {
+        let user = Self::find_user(user_id, conn)?;
+        UserService::check_current_password(&user, &dto.current_password)?;
+
+        let stored_keys = conn.transaction::<_, AppError, _>(|conn| Self::erase_account(&user, conn))?;
+
+        // Una vez borradas las filas, un fallo al borrar los ficheros solo deja basura en el almacenamiento
+        MediaService::remove_stored(&stored_keys);
+
+        tracing::info!(user_id = user.id, "Cuenta eliminada a petición del usuario");
+        Ok(())
+    }
+
+    /// Borra la cuenta y sus datos personales; debe ejecutarse dentro de una
+    /// transacción. Sus comentarios se conservan sin autor, nombre ni email, sus
+    /// posts pasan a la cuenta de `DELETED_ACCOUNT_POSTS_OWNER` o se quedan sin
+    /// autor, y sus medios se borran. Devuelve las claves de los ficheros (avatar
+    /// incluido), que se eliminan con `MediaService::remove_stored` al confirmar.
+    pub fn erase_account(user: &User, conn: &mut DbConnection) -> AppResult<Vec<String>> {
+        let new_owner = match config::deleted_account_posts_owner() {
+            Some(username) => {
+                let owner = users::table
+                    .filter(users::username.eq(&username))
+                    .select(users::id)
+                    .first::<i32>(conn)
+                    .optional()?
+                    .ok_or_else(|| {
+                        AppError::InternalServerError(format!(
+                            "La cuenta '{}' de DELETED_ACCOUNT_POSTS_OWNER no existe",
+                            username
+                        ))
+                    })?;
+                if owner == user.id {
+                    return Err(AppError::ValidationError(
+                        "Esta cuenta recibe los posts de las cuentas eliminadas y no puede eliminarse".to_string(),
+                    ));
+                }
+                Some(owner)
+            }
+            None => None,
+        };
+
+        diesel::update(comments::table.filter(comments::user_id.eq(user.id)))
+            .set((
+                comments::user_id.eq(None::<i32>),
+                comments::author_name.eq(None::<String>),
+                comments::author_email.eq(None::<String>),
+            ))
+            .execute(conn)?;
+
+        diesel::update(posts::table.filter(posts::author_id.eq(user.id)))
+            .set(posts::author_id.eq(new_owner))
+            .execute(conn)?;
+
+        let stored_keys = MediaService::delete_user_media(user.id, conn)?;
+        diesel::delete(users::table.find(user.id)).execute(conn)?;
+        Ok(stored_keys)
+    }
+
+    /// Borra el email guardado en los comentarios anónimos escritos con él
+    #[instrument(skip_all)]
+    pub fn purge_commenter_email(email: &str, conn: &mut DbConnection) -> AppResult<PurgeCommenterEmailResultDto> {
+        let email = email.trim();
+        if email.is_empty() {
+            return Err(AppError::ValidationError("El email no puede estar vacío".to_string()));
+        }
+
+        let purged = diesel::update(
+            comments::table
+                .filter(comments::user_id.is_null())
+                .filter(lower(comments::author_email).eq(email.to_lowercase())),
+        )
+        .set(comments::author_email.eq(None::<String>))
+        .execute(conn)?;
+
+        Ok(PurgeCommenterEmailResultDto { comments: purged })
+    }
+
+    fn find_user(user_id: i32, conn: &mut DbConnection) -> AppResult<User> {
+        users::table.find(user_id).first::<User>(conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Usuario con ID {} no encontrado", user_id)),
+            _ => AppError::DatabaseError(e),
+        })
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+use chrono::Local;
+use diesel::prelude::*;
+use diesel::result::Error as DieselError;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::db::schema::users;
+use crate::models::dto::{AuthorPageDto, PostSummaryDto, ProfileDto, UpdateProfileDto};
+use crate::models::entities::{UpdateProfile, User};
+use crate::repositories::{MediaRepository, PostRepository};
+use crate::services::{MediaService, PostService};
+use crate::utils::error::{AppError, AppResult};
+
+const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+const MAX_BIO_LENGTH: usize = 2000;
+const MAX_URL_LENGTH: usize = 255;
+const MAX_SOCIAL_LINKS: usize = 10;
+const MAX_SOCIAL_NETWORK_LENGTH: usize = 30;
+
+/// Perfiles públicos de los usuarios y páginas de autor
+pub struct ProfileService;
+
+impl ProfileService {
+    /// Perfil de un usuario por su ID
+    #[instrument(skip(conn))]
+    pub fn get_profile(user_id: i32, conn: &mut DbConnection) -> AppResult<ProfileDto> {
+        let user = users::table.find(user_id).first::<User>(conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Usuario con ID {} no encontrado", user_id)),
+            _ => AppError::DatabaseError(e),
+        })?;
+        Self::to_dto(user, conn)
+    }
+
+    /// Perfil de un autor y sus posts publicados
+    #[instrument(skip(conn))]
+    pub fn get_author_page(username: &str, conn: &mut DbConnection) -> AppResult<AuthorPageDto> {
+        let user = users::table
+            .filter(users::username.eq(username))
+            .first::<User>(conn)
+            .map_err(|e| match e {
+                DieselError::NotFound => AppError::NotFound(format!("Autor '{}' no encontrado", username)),
+                _ => AppError::DatabaseError(e),
+            })?;
+
+        let posts = PostRepository::find_published_by_author(user.id, conn)?;
+        let posts = PostService::to_dtos(posts, conn)?
+            .into_iter()
+            .map(PostSummaryDto::from)
+            .collect();
+
+        Ok(AuthorPageDto {
+            profile: Self::to_dto(user, conn)?,
+            posts,
+        })
+    }
+
+    /// Actualiza el perfil del propio usuario. Los textos vacíos se guardan como `NULL`.
+    #[instrument(skip(dto, conn))]
+    pub fn update_profile(user_id: i32, dto: UpdateProfileDto, conn: &mut DbConnection) -> AppResult<ProfileDto> {
+        let display_name = dto.display_name.map(Self::normalize);
+        if let Some(Some(ref name)) = display_name {
+            if name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+                return Err(AppError::ValidationError(format!(
+                    "El nombre para mostrar no puede superar los {} caracteres",
+                    MAX_DISPLAY_NAME_LENGTH
+                )));
+            }
+        }
+
+        let bio = dto.bio.map(Self::normalize);
+        if let Some(Some(ref text)) = bio {
+            if text.chars().count() > MAX_BIO_LENGTH {
+                return Err(AppError::ValidationError(format!(
+                    "La biografía no puede superar los {} caracteres",
+                    MAX_BIO_LENGTH
+                )));
+            }
+        }
+
+        let website = dto.website.map(Self::normalize);
+        if let Some(Some(ref url)) = website {
+            Self::validate_url(url, "La web")?;
+        }
+
+        let social_links = match dto.social_links {
+            Some(links) => Some(Self::validate_social_links(links)?),
+            None => None,
+        };
+
+        if let Some(Some(media_id)) = dto.avatar_media_id {
+            let media = MediaRepository::find_by_id(media_id, conn).map_err(|e| match e {
+                DieselError::NotFound => AppError::ValidationError(format!("No existe el fichero con ID {}", media_id)),
+                _ => AppError::DatabaseError(e),
+            })?;
+            if media.user_id != Some(user_id) {
+                return Err(AppError::ValidationError(
+                    "El avatar debe ser una imagen subida por el propio usuario".to_string(),
+                ));
+            }
+            if !media.mime_type.starts_with("image/") {
+                return Err(AppError::ValidationError("El avatar debe ser una imagen".to_string()));
+            }
+        }
+
+        let changes = UpdateProfile {
+            display_name,
+            bio,
+            website,
+            social_links,
+            avatar_media_id: dto.avatar_media_id,
+            updated_at: Some(Local::now().naive_local()),
+        };
+
+        let user = diesel::update(users::table.find(user_id))
+            .set(&changes)
+            .get_result::<User>(conn)
+            .map_err(|e| match e {
+                DieselError::NotFound => AppError::NotFound(format!("Usuario con ID {} no encontrado", user_id)),
+                _ => AppError::DatabaseError(e),
+            })?;
+
+        Self::to_dto(user, conn)
+    }
+
+    pub fn to_dto(user: User, conn: &mut DbConnection) -> AppResult<ProfileDto> {
+        let avatar_url = match user.avatar_media_id {
+            Some(media_id) => {
+                let media = MediaService::get_media_by_id(media_id, conn)?;
+                media.thumbnail_url.unwrap_or(media.url)
+            }
+            None => Self::gravatar_url(&user.email),
+        };
+
+        // Solo se guardan objetos de textos, pero una fila editada a mano no debe romper el perfil
+        let social_links = match user.social_links {
+            Value::Object(map) => map
+                .into_iter()
+                .filter_map(|(network, url)| url.as_str().map(|url| (network, url.to_string())))
+                .collect(),
+            _ => BTreeMap::new(),
+        };
+
+        Ok(ProfileDto {
+            id: user.id,
+            username: user.username,
+            display_name: user.display_name,
+            bio: user.bio,
+            website: user.website,
+            social_links,
+            avatar_media_id: user.avatar_media_id,
+            avatar_url,
+            created_at: user.created_at,
+        })
+    }
+
+    /// Avatar de Gravatar (identicon si el email no tiene ninguno), a partir del
+    /// SHA-256 del email normalizado
+    fn gravatar_url(email: &str) -> String {
+        let hash = Sha256::digest(email.trim().to_lowercase().as_bytes());
+        format!("https://www.gravatar.com/avatar/{}?d=identicon", hex::encode(hash))
+    }
+
+    fn normalize(value: Option<String>) -> Option<String> {
+        value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+    }
+
+    fn validate_url(url: &str, field: &str) -> AppResult<()> {
+        if !(url.starts_with("https://") || url.starts_with("http://")) {
+            return Err(AppError::ValidationError(format!("{} debe ser una URL http:// o https://", field)));
+        }
+        if url.chars().count() > MAX_URL_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "{} no puede superar los {} caracteres",
+                field, MAX_URL_LENGTH
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_social_links(links: BTreeMap<String, String>) -> AppResult<Value> {
+        if links.len() > MAX_SOCIAL_LINKS {
+            return Err(AppError::ValidationError(format!(
+                "No se pueden indicar más de {} redes sociales",
+                MAX_SOCIAL_LINKS
+            )));
+        }
+
+        let mut map = serde_json::Map::new();
+        for (network, url) in links {
+            let network = network.trim().to_lowercase();
+            let valid_network = !network.is_empty()
+                && network.len() <= MAX_SOCIAL_NETWORK_LENGTH
+                && network.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+            if !valid_network {
+                return Err(AppError::ValidationError(format!(
+                    "Nombre de red social no válido: '{}' (letras, números, '_' y '-', hasta {} caracteres)",
+                    network, MAX_SOCIAL_NETWORK_LENGTH
+                )));
+            }
+            let url = url.trim().to_string();
+            Self::validate_url(&url, &format!("El enlace de '{}'", network))?;
+            map.insert(network, Value::String(url));
+        }
+        Ok(Value::Object(map))
+    }
+}
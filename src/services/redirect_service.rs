@@ -0,0 +1,194 @@
+use chrono::Local;
+use diesel::result::Error as DieselError;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::models::entities::{NewRedirect, Redirect, UpdateRedirect};
+use crate::models::dto::{CreateRedirectDto, RedirectDto, UpdateRedirectDto};
+use crate::repositories::RedirectRepository;
+use crate::utils::error::{AppError, AppResult};
+
+/// Códigos de redirección admitidos
+const REDIRECT_STATUS_CODES: [i32; 4] = [301, 302, 307, 308];
+/// Redirecciones encadenadas que se admiten detrás de una nueva, para que el
+/// cliente no tenga que seguir una cadena larga
+const MAX_REDIRECT_HOPS: usize = 5;
+
+/// Resultado de buscar una entidad por slug: la entidad, o su slug actual si
+/// el solicitado es uno antiguo
+pub enum SlugLookup<T> {
+    Found(T),
+    Moved(String),
+}
+
+pub struct RedirectService;
+
+impl RedirectService {
+    #[instrument(skip_all)]
+    pub fn get_all_redirects(conn: &mut DbConnection) -> AppResult<Vec<RedirectDto>> {
+        let redirects = RedirectRepository::find_all(conn)?;
+        Ok(redirects.into_iter().map(Self::to_dto).collect())
+    }
+
+    /// Busca la redirección de una ruta que no corresponde a ningún endpoint
+    #[instrument(skip(conn))]
+    pub fn resolve(path: &str, conn: &mut DbConnection) -> AppResult<Option<RedirectDto>> {
+        let redirect = RedirectRepository::find_by_source(&Self::normalize(path), conn)?;
+        Ok(redirect.map(Self::to_dto))
+    }
+
+    #[instrument(skip(dto, conn))]
+    pub fn create_redirect(dto: CreateRedirectDto, conn: &mut DbConnection) -> AppResult<RedirectDto> {
+        let source_path = Self::validate_source(&dto.source_path)?;
+        let target_path = Self::validate_target(&dto.target_path)?;
+        let status_code = Self::validate_status(dto.status_code.unwrap_or(301))?;
+
+        if source_path == target_path {
+            return Err(AppError::ValidationError("El origen y el destino no pueden coincidir".to_string()));
+        }
+        if RedirectRepository::find_by_source(&source_path, conn)?.is_some() {
+            return Err(AppError::ValidationError(format!("Ya existe una redirección para '{}'", source_path)));
+        }
+        Self::check_chain(&source_path, &target_path, conn)?;
+
+        let now = Local::now().naive_local();
+        let redirect = RedirectRepository::create(
+            &NewRedirect {
+                source_path,
+                target_path,
+                status_code,
+                created_at: Some(now),
+                updated_at: Some(now),
+            },
+            conn,
+        )?;
+
+        Ok(Self::to_dto(redirect))
+    }
+
+    #[instrument(skip(dto, conn))]
+    pub fn update_redirect(redirect_id: i32, dto: UpdateRedirectDto, conn: &mut DbConnection) -> AppResult<RedirectDto> {
+        let current = Self::find_redirect(redirect_id, conn)?;
+
+        let source_path = dto.source_path.as_deref().map(Self::validate_source).transpose()?;
+        let target_path = dto.target_path.as_deref().map(Self::validate_target).transpose()?;
+        let status_code = dto.status_code.map(Self::validate_status).transpose()?;
+
+        let final_source = source_path.as_deref().unwrap_or(&current.source_path);
+        let final_target = target_path.as_deref().unwrap_or(&current.target_path);
+        if final_source == final_target {
+            return Err(AppError::ValidationError("El origen y el destino no pueden coincidir".to_string()));
+        }
+        if let Some(ref source) = source_path {
+            if let Some(existing) = RedirectRepository::find_by_source(source, conn)? {
+                if existing.id != redirect_id {
+                    return Err(AppError::ValidationError(format!("Ya existe una redirección para '{}'", source)));
+                }
+            }
+        }
+        Self::check_chain(final_source, final_target, conn)?;
+
+        let update = UpdateRedirect {
+            source_path,
+            target_path,
+            status_code,
+            updated_at: Some(Local::now().naive_local()),
+        };
+        let redirect = RedirectRepository::update(redirect_id, &update, conn)?;
+
+        Ok(Self::to_dto(redirect))
+    }
+
+    #[instrument(skip(conn))]
+    pub fn delete_redirect(redirect_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        Self::find_redirect(redirect_id, conn)?;
+        RedirectRepository::delete(redirect_id, conn)?;
+        Ok(())
+    }
+
+    /// Sigue el destino a través de las redirecciones existentes y rechaza la nueva
+    /// si acabaría volviendo a `source` o si la cadena resultante es demasiado larga.
+    /// Las URLs absolutas terminan la cadena.
+    fn check_chain(source: &str, target: &str, conn: &mut DbConnection) -> AppResult<()> {
+        let mut current = target.to_string();
+        for _ in 0..=MAX_REDIRECT_HOPS {
+            if !current.starts_with('/') {
+                return Ok(());
+            }
+            // Las redirecciones se buscan por la ruta, sin parámetros
+            let path = Self::normalize(current.split('?').next().unwrap_or_default());
+            if path == source {
+                return Err(AppError::ValidationError(format!(
+                    "La redirección formaría un ciclo: '{}' acaba volviendo a sí misma",
+                    source
+                )));
+            }
+            match RedirectRepository::find_by_source(&path, conn)? {
+                Some(next) => current = next.target_path,
+                None => return Ok(()),
+            }
+        }
+
+        Err(AppError::ValidationError(format!(
+            "El destino '{}' encadena más de {} redirecciones; apunta directamente al destino final",
+            target, MAX_REDIRECT_HOPS
+        )))
+    }
+
+    /// Las rutas se comparan sin la barra final, salvo la raíz
+    fn normalize(path: &str) -> String {
+        let trimmed = path.trim();
+        match trimmed.trim_end_matches('/') {
+            "" => "/".to_string(),
+            path => path.to_string(),
+        }
+    }
+
+    fn validate_source(path: &str) -> AppResult<String> {
+        let path = Self::normalize(path);
+        if !path.starts_with('/') || path.contains(char::is_whitespace) || path.contains('?') {
+            return Err(AppError::ValidationError(format!(
+                "Ruta de origen no válida: '{}'; debe empezar por '/' y no llevar parámetros",
+                path
+            )));
+        }
+        Ok(path)
+    }
+
+    /// El destino puede ser una ruta del sitio o una URL absoluta
+    fn validate_target(path: &str) -> AppResult<String> {
+        let path = path.trim();
+        let valid = path.starts_with('/') || path.starts_with("https://") || path.starts_with("http://");
+        if !valid || path.contains(char::is_whitespace) {
+            return Err(AppError::ValidationError(format!("Destino no válido: '{}'", path)));
+        }
+        Ok(path.to_string())
+    }
+
+    fn validate_status(status_code: i32) -> AppResult<i32> {
+        if !REDIRECT_STATUS_CODES.contains(&status_code) {
+            return Err(AppError::ValidationError(format!(
+                "Código de redirección no válido: {}; usa 301, 302, 307 o 308",
+                status_code
+            )));
+        }
+        Ok(status_code)
+    }
+
+    fn find_redirect(redirect_id: i32, conn: &mut DbConnection) -> AppResult<Redirect> {
+        RedirectRepository::find_by_id(redirect_id, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Redirección con ID {} no encontrada", redirect_id)),
+            _ => AppError::DatabaseError(e),
+        })
+    }
+
+    fn to_dto(redirect: Redirect) -> RedirectDto {
+        RedirectDto {
+            id: redirect.id,
+            source_path: redirect.source_path,
+            target_path: redirect.target_path,
+            status_code: redirect.status_code,
+            created_at: redirect.created_at,
+            updated_at: redirect.updated_at,
+        }
+    }
+}
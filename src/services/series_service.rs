@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+use chrono::Local;
+use diesel::result::Error as DieselError;
+use diesel::Connection;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::models::entities::{NewSeries, Series, UpdateSeries};
+use crate::models::dto::{
+    CreateSeriesDto, PostSummaryDto, SeriesDetailDto, SeriesDto, SeriesNavigationDto, SeriesPostLinkDto,
+    UpdateSeriesDto,
+};
+use crate::repositories::{PostRepository, SeriesRepository};
+use crate::services::PostService;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::slug::{explicit_slug_error, slugify, with_unique_slug};
+
+pub struct SeriesService;
+
+impl SeriesService {
+    /// Obtiene todas las series con su número de posts
+    #[instrument(skip_all)]
+    pub fn get_all_series(conn: &mut DbConnection) -> AppResult<Vec<SeriesDto>> {
+        let series = SeriesRepository::find_all(conn)?;
+        let counts = SeriesRepository::count_posts_by_series(conn)?;
+
+        Ok(series
+            .into_iter()
+            .map(|s| {
+                let post_count = counts.get(&s.id).copied().unwrap_or(0);
+                Self::to_dto(s, post_count)
+            })
+            .collect())
+    }
+
+    /// Página de presentación de una serie a partir de su slug
+    #[instrument(skip(conn))]
+    pub fn get_series_by_slug(series_slug: &str, conn: &mut DbConnection) -> AppResult<SeriesDetailDto> {
+        let series = SeriesRepository::find_by_slug(series_slug, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Serie con slug '{}' no encontrada", series_slug)),
+            _ => AppError::DatabaseError(e),
+        })?;
+
+        Self::load_detail(series, conn)
+    }
+
+    /// Navegación de la serie a la que pertenece un post, si pertenece a alguna
+    #[instrument(skip(conn))]
+    pub fn get_navigation(post_id: i32, conn: &mut DbConnection) -> AppResult<Option<SeriesNavigationDto>> {
+        let series = match SeriesRepository::find_by_post(post_id, conn)? {
+            Some(series) => series,
+            None => return Ok(None),
+        };
+
+        let index: Vec<SeriesPostLinkDto> = SeriesRepository::find_posts(series.id, conn)?
+            .into_iter()
+            .map(|post| SeriesPostLinkDto {
+                id: post.id,
+                title: post.title,
+                slug: post.slug,
+            })
+            .collect();
+
+        let current = match index.iter().position(|p| p.id == post_id) {
+            Some(current) => current,
+            None => return Ok(None),
+        };
+
+        Ok(Some(SeriesNavigationDto {
+            id: series.id,
+            title: series.title,
+            slug: series.slug,
+            part: current + 1,
+            total: index.len(),
+            previous: current.checked_sub(1).map(|i| index[i].clone()),
+            next: index.get(current + 1).cloned(),
+            index,
+        }))
+    }
+
+    /// Crea una serie y, opcionalmente, le asigna sus posts
+    #[instrument(skip(dto, conn))]
+    pub fn create_series(dto: CreateSeriesDto, conn: &mut DbConnection) -> AppResult<SeriesDetailDto> {
+        if dto.title.trim().is_empty() {
+            return Err(AppError::ValidationError("El título no puede estar vacío".to_string()));
+        }
+
+        // Un slug generado que ya existe se desambigua con un sufijo
+        let (slug, auto_slug) = match dto.slug {
+            Some(s) if !s.trim().is_empty() => (s, false),
+            _ => (slugify(&dto.title), true),
+        };
+        if !auto_slug {
+            Self::check_slug_available(&slug, conn)?;
+        }
+
+        let now = Local::now().naive_local();
+        let mut new_series = NewSeries {
+            title: dto.title,
+            slug: slug.clone(),
+            description: dto.description,
+            created_at: Some(now),
+            updated_at: Some(now),
+        };
+
+        let series = conn.transaction::<_, AppError, _>(|conn| {
+            let mut insert = |candidate: &str, conn: &mut DbConnection| -> AppResult<Series> {
+                new_series.slug = candidate.to_string();
+                Ok(SeriesRepository::create(&new_series, conn)?)
+            };
+            let series = if auto_slug {
+                with_unique_slug(&slug, conn, insert)?
+            } else {
+                insert(&slug, conn).map_err(|e| explicit_slug_error(e, &slug))?
+            };
+            if let Some(ref post_ids) = dto.post_ids {
+                Self::validate_posts(series.id, post_ids, conn)?;
+                SeriesRepository::set_posts(series.id, post_ids, conn)?;
+            }
+            Ok(series)
+        })?;
+
+        Self::load_detail(series, conn)
+    }
+
+    /// Actualiza el título, el slug o la descripción de una serie
+    #[instrument(skip(dto, conn))]
+    pub fn update_series(series_id: i32, dto: UpdateSeriesDto, conn: &mut DbConnection) -> AppResult<SeriesDetailDto> {
+        let series = Self::find_series(series_id, conn)?;
+
+        if let Some(ref title) = dto.title {
+            if title.trim().is_empty() {
+                return Err(AppError::ValidationError("El título no puede estar vacío".to_string()));
+            }
+        }
+
+        if let Some(ref new_slug) = dto.slug {
+            if new_slug != &series.slug {
+                Self::check_slug_available(new_slug, conn)?;
+            }
+        }
+
+        let update_data = UpdateSeries {
+            title: dto.title,
+            slug: dto.slug,
+            description: dto.description,
+            updated_at: Some(Local::now().naive_local()),
+        };
+        let series = SeriesRepository::update(series_id, &update_data, conn)
+            .map_err(|e| explicit_slug_error(e.into(), update_data.slug.as_deref().unwrap_or_default()))?;
+
+        Self::load_detail(series, conn)
+    }
+
+    /// Sustituye los posts de la serie y su orden de lectura en una única transacción
+    #[instrument(skip(conn))]
+    pub fn set_series_posts(series_id: i32, post_ids: Vec<i32>, conn: &mut DbConnection) -> AppResult<SeriesDetailDto> {
+        let series = Self::find_series(series_id, conn)?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            Self::validate_posts(series_id, &post_ids, conn)?;
+            SeriesRepository::set_posts(series_id, &post_ids, conn)?;
+            Ok(())
+        })?;
+
+        Self::load_detail(series, conn)
+    }
+
+    /// Elimina una serie; sus posts se conservan
+    #[instrument(skip(conn))]
+    pub fn delete_series(series_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        Self::find_series(series_id, conn)?;
+
+        let deleted = SeriesRepository::delete(series_id, conn)?;
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!("No se pudo eliminar la serie con ID {}", series_id)));
+        }
+
+        Ok(())
+    }
+
+    fn load_detail(series: Series, conn: &mut DbConnection) -> AppResult<SeriesDetailDto> {
+        let posts = SeriesRepository::find_posts(series.id, conn)?;
+        let posts = PostService::to_dtos(posts, conn)?
+            .into_iter()
+            .map(PostSummaryDto::from)
+            .collect();
+
+        Ok(SeriesDetailDto {
+            id: series.id,
+            title: series.title,
+            slug: series.slug,
+            description: series.description,
+            created_at: series.created_at,
+            updated_at: series.updated_at,
+            posts,
+        })
+    }
+
+    fn to_dto(series: Series, post_count: i64) -> SeriesDto {
+        SeriesDto {
+            id: series.id,
+            title: series.title,
+            slug: series.slug,
+            description: series.description,
+            post_count,
+            created_at: series.created_at,
+            updated_at: series.updated_at,
+        }
+    }
+
+    /// Comprueba que los posts existen, no se repiten y no pertenecen a otra serie
+    fn validate_posts(series_id: i32, post_ids: &[i32], conn: &mut DbConnection) -> AppResult<()> {
+        let mut seen = HashSet::new();
+        for &post_id in post_ids {
+            if !seen.insert(post_id) {
+                return Err(AppError::ValidationError(format!("El post con ID {} aparece más de una vez", post_id)));
+            }
+            match PostRepository::find_by_id(post_id, conn) {
+                Ok(_) => {}
+                Err(DieselError::NotFound) => {
+                    return Err(AppError::ValidationError(format!("El post con ID {} no existe", post_id)));
+                }
+                Err(e) => return Err(AppError::DatabaseError(e)),
+            }
+        }
+
+        let memberships = SeriesRepository::find_memberships(post_ids, conn)?;
+        if let Some((post_id, other)) = memberships.into_iter().find(|&(_, s)| s != series_id) {
+            return Err(AppError::ValidationError(format!(
+                "El post con ID {} ya pertenece a la serie con ID {}",
+                post_id, other
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn check_slug_available(series_slug: &str, conn: &mut DbConnection) -> AppResult<()> {
+        match SeriesRepository::find_by_slug(series_slug, conn) {
+            Ok(_) => Err(AppError::ValidationError(format!("Ya existe una serie con el slug '{}'", series_slug))),
+            Err(DieselError::NotFound) => Ok(()),
+            Err(e) => Err(AppError::DatabaseError(e)),
+        }
+    }
+
+    fn find_series(series_id: i32, conn: &mut DbConnection) -> AppResult<Series> {
+        SeriesRepository::find_by_id(series_id, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Serie con ID {} no encontrada", series_id)),
+            _ => AppError::DatabaseError(e),
+        })
+    }
+}
@@ -0,0 +1,149 @@
+use std::sync::RwLock;
+use chrono::Local;
+use diesel::Connection;
+use serde_json::{Map, Value};
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::models::dto::{SiteSettings, UpdateSiteSettingsDto, REGISTRATION_POLICIES};
+use crate::repositories::SiteSettingRepository;
+use crate::utils::error::{AppError, AppResult};
+
+/// Ajustes ya leídos de la base de datos. Al ser una caché por proceso, otras
+/// instancias no ven un cambio hasta que se reinician.
+static CACHE: RwLock<Cache> = RwLock::new(Cache { generation: 0, settings: None });
+
+struct Cache {
+    /// Aumenta con cada escritura; una lectura que empezó antes no puede rellenar
+    /// la caché con los valores anteriores
+    generation: u64,
+    settings: Option<SiteSettings>,
+}
+
+pub struct SiteSettingsService;
+
+impl SiteSettingsService {
+    /// Ajustes actuales, desde la caché si están en ella
+    #[instrument(skip_all)]
+    pub fn get(conn: &mut DbConnection) -> AppResult<SiteSettings> {
+        let generation = match CACHE.read() {
+            Ok(cache) => match cache.settings {
+                Some(ref settings) => return Ok(settings.clone()),
+                None => cache.generation,
+            },
+            Err(_) => return Self::load(conn),
+        };
+
+        let settings = Self::load(conn)?;
+        if let Ok(mut cache) = CACHE.write() {
+            if cache.generation == generation {
+                cache.settings = Some(settings.clone());
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Modifica las claves indicadas. Se valida todo antes de escribir nada, y se
+    /// escribe en una única transacción.
+    #[instrument(skip_all)]
+    pub fn update(changes: UpdateSiteSettingsDto, conn: &mut DbConnection) -> AppResult<SiteSettings> {
+        if changes.is_empty() {
+            return Err(AppError::ValidationError("No se ha indicado ningún ajuste".to_string()));
+        }
+        for (key, value) in &changes {
+            Self::validate(key, value)?;
+        }
+
+        let now = Local::now().naive_local();
+        conn.transaction::<_, AppError, _>(|conn| {
+            for (key, value) in &changes {
+                SiteSettingRepository::upsert(key, value, now, conn)?;
+            }
+            Ok(())
+        })?;
+
+        // Vaciar la caché y pasar a una nueva generación, para que una lectura que
+        // empezó antes de escribir no la rellene con los valores anteriores. Los
+        // nuevos se leen ya sin el bloqueo, como en `get`.
+        if let Ok(mut cache) = CACHE.write() {
+            cache.generation += 1;
+            cache.settings = None;
+        }
+        Self::get(conn)
+    }
+
+    /// Combina los valores por defecto con los guardados. Un valor guardado que ya
+    /// no es válido (p. ej. tras cambiar las reglas) se ignora.
+    fn load(conn: &mut DbConnection) -> AppResult<SiteSettings> {
+        let mut values = match serde_json::to_value(SiteSettings::default()) {
+            Ok(Value::Object(values)) => values,
+            _ => Map::new(),
+        };
+
+        for setting in SiteSettingRepository::find_all(conn)? {
+            match Self::validate(&setting.key, &setting.value) {
+                Ok(()) => {
+                    values.insert(setting.key, setting.value);
+                }
+                Err(e) => tracing::warn!(key = %setting.key, error = %e, "Ajuste guardado no válido; se usa el valor por defecto"),
+            }
+        }
+
+        serde_json::from_value(Value::Object(values))
+            .map_err(|e| AppError::InternalServerError(format!("Ajustes del sitio no válidos: {}", e)))
+    }
+
+    /// Reglas de cada clave; una clave desconocida es un error
+    fn validate(key: &str, value: &Value) -> AppResult<()> {
+        let invalid = |expected: &str| {
+            Err(AppError::ValidationError(format!("El ajuste '{}' debe ser {}", key, expected)))
+        };
+
+        match key {
+            "title" => match value.as_str() {
+                Some(s) if !s.trim().is_empty() && s.chars().count() <= 200 => Ok(()),
+                _ => invalid("un texto no vacío de hasta 200 caracteres"),
+            },
+            "description" => match value.as_str() {
+                Some(s) if s.chars().count() <= 1000 => Ok(()),
+                _ => invalid("un texto de hasta 1000 caracteres"),
+            },
+            "default_locale" => match value.as_str() {
+                Some(s) if Self::is_locale(s) => Ok(()),
+                _ => invalid("una etiqueta de idioma como 'es' o 'es-ES'"),
+            },
+            "posts_per_page" => match value.as_u64() {
+                Some(n) if (1..=100).contains(&n) => Ok(()),
+                _ => invalid("un entero entre 1 y 100"),
+            },
+            "comments_enabled" | "comments_allow_anonymous" => match value {
+                Value::Bool(_) => Ok(()),
+                _ => invalid("true o false"),
+            },
+            "comments_close_after_days" => match value.as_u64() {
+                Some(n) if n <= 3650 => Ok(()),
+                _ => invalid("un entero entre 0 y 3650"),
+            },
+            "comments_max_length" => match value.as_u64() {
+                Some(n) if (1..=100_000).contains(&n) => Ok(()),
+                _ => invalid("un entero entre 1 y 100000"),
+            },
+            "registration_policy" => match value.as_str() {
+                Some(s) if REGISTRATION_POLICIES.contains(&s) => Ok(()),
+                _ => invalid("'open', 'invite_only' o 'closed'"),
+            },
+            _ => Err(AppError::ValidationError(format!("Ajuste desconocido: '{}'", key))),
+        }
+    }
+
+    /// `xx` o `xx-XX`
+    fn is_locale(tag: &str) -> bool {
+        let mut parts = tag.split('-');
+        let language = parts.next().unwrap_or_default();
+        let region = parts.next();
+
+        language.len() == 2
+            && language.chars().all(|c| c.is_ascii_lowercase())
+            && region.is_none_or(|r| r.len() == 2 && r.chars().all(|c| c.is_ascii_uppercase()))
+            && parts.next().is_none()
+    }
+}
@@ -0,0 +1,150 @@
+use chrono::Local;
+use diesel::result::Error as DieselError;
+use tracing::instrument;
+use crate::db::DbConnection;
+use crate::models::entities::{Tag, UpdateTag};
+use crate::models::dto::{PostDto, TagCloudEntryDto, TagDto, TagWithCountDto, UpdateTagDto};
+use crate::repositories::TagRepository;
+use crate::services::PostService;
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::slug::{explicit_slug_error, slugify};
+
+/// Número de niveles de peso de la nube de etiquetas
+const TAG_CLOUD_LEVELS: u8 = 5;
+
+pub struct TagService;
+
+impl TagService {
+    /// Obtiene todas las etiquetas con el número de posts de cada una
+    #[instrument(skip_all)]
+    pub fn get_all_tags(conn: &mut DbConnection) -> AppResult<Vec<TagWithCountDto>> {
+        let tags = TagRepository::find_all_with_counts(conn)?;
+
+        Ok(tags
+            .into_iter()
+            .map(|(tag, post_count)| TagWithCountDto {
+                id: tag.id,
+                name: tag.name,
+                slug: tag.slug,
+                post_count,
+            })
+            .collect())
+    }
+
+    /// Obtiene la nube de etiquetas: las etiquetas en uso con un peso de 1 a 5
+    /// proporcional (en escala logarítmica) a su número de posts
+    #[instrument(skip_all)]
+    pub fn get_tag_cloud(conn: &mut DbConnection) -> AppResult<Vec<TagCloudEntryDto>> {
+        let tags: Vec<(Tag, i64)> = TagRepository::find_all_with_counts(conn)?
+            .into_iter()
+            .filter(|(_, post_count)| *post_count > 0)
+            .collect();
+
+        let min = tags.iter().map(|(_, c)| *c).min().unwrap_or(1) as f64;
+        let max = tags.iter().map(|(_, c)| *c).max().unwrap_or(1) as f64;
+        let range = max.ln() - min.ln();
+
+        Ok(tags
+            .into_iter()
+            .map(|(tag, post_count)| {
+                let weight = if range > 0.0 {
+                    let ratio = ((post_count as f64).ln() - min.ln()) / range;
+                    1 + (ratio * f64::from(TAG_CLOUD_LEVELS - 1)).round() as u8
+                } else {
+                    1
+                };
+
+                TagCloudEntryDto {
+                    name: tag.name,
+                    slug: tag.slug,
+                    post_count,
+                    weight,
+                }
+            })
+            .collect())
+    }
+
+    /// Obtiene los posts de una etiqueta a partir de su slug
+    #[instrument(skip(conn))]
+    pub fn get_posts_by_tag(tag_slug: &str, conn: &mut DbConnection) -> AppResult<Vec<PostDto>> {
+        let tag = TagRepository::find_by_slug(tag_slug, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Etiqueta con slug '{}' no encontrada", tag_slug)),
+            _ => AppError::DatabaseError(e),
+        })?;
+
+        let posts = TagRepository::find_posts(tag.id, conn)?;
+
+        PostService::to_dtos(posts, conn)
+    }
+
+    /// Renombra una etiqueta
+    #[instrument(skip(dto, conn))]
+    pub fn update_tag(tag_id: i32, dto: UpdateTagDto, conn: &mut DbConnection) -> AppResult<TagDto> {
+        let tag = Self::find_tag(tag_id, conn)?;
+
+        if let Some(ref new_name) = dto.name {
+            if new_name.trim().is_empty() {
+                return Err(AppError::ValidationError("El nombre no puede estar vacío".to_string()));
+            }
+        }
+
+        // Generar slug si se proporciona un nuevo nombre pero no un nuevo slug
+        let slug_value = match (&dto.name, dto.slug) {
+            (_, Some(s)) if !s.trim().is_empty() => Some(s),
+            (Some(new_name), _) => Some(slugify(new_name)),
+            _ => None,
+        };
+
+        if let Some(ref new_slug) = slug_value {
+            if new_slug != &tag.slug && TagRepository::find_by_slug(new_slug, conn).is_ok() {
+                return Err(AppError::ValidationError(format!(
+                    "Ya existe una etiqueta con el slug '{}'; use la fusión de etiquetas", new_slug
+                )));
+            }
+        }
+
+        let update_data = UpdateTag {
+            name: dto.name.map(|n| n.trim().to_string()),
+            slug: slug_value,
+            updated_at: Some(Local::now().naive_local()),
+        };
+
+        let updated = TagRepository::update(tag_id, &update_data, conn)
+            .map_err(|e| explicit_slug_error(e.into(), update_data.slug.as_deref().unwrap_or_default()))?;
+
+        Ok(Self::to_dto(updated))
+    }
+
+    /// Fusiona una etiqueta en otra: los posts de la etiqueta origen pasan a la
+    /// etiqueta destino y la etiqueta origen se elimina
+    #[instrument(skip(conn))]
+    pub fn merge_tags(source_id: i32, target_id: i32, conn: &mut DbConnection) -> AppResult<TagDto> {
+        if source_id == target_id {
+            return Err(AppError::ValidationError("No se puede fusionar una etiqueta consigo misma".to_string()));
+        }
+
+        Self::find_tag(source_id, conn)?;
+        let target = Self::find_tag(target_id, conn)?;
+
+        TagRepository::merge(source_id, target_id, conn)?;
+
+        Ok(Self::to_dto(target))
+    }
+
+    fn find_tag(tag_id: i32, conn: &mut DbConnection) -> AppResult<Tag> {
+        TagRepository::find_by_id(tag_id, conn).map_err(|e| match e {
+            DieselError::NotFound => AppError::NotFound(format!("Etiqueta con ID {} no encontrada", tag_id)),
+            _ => AppError::DatabaseError(e),
+        })
+    }
+
+    pub fn to_dto(tag: Tag) -> TagDto {
+        TagDto {
+            id: tag.id,
+            name: tag.name,
+            slug: tag.slug,
+            created_at: tag.created_at,
+            updated_at: tag.updated_at,
+        }
+    }
+}
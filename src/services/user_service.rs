@@ -1,43 +1,75 @@
-use chrono::Local;
+use chrono::{Duration, Local};
 use diesel::result::Error as DieselError;
-use diesel::prelude::*;
 use diesel::RunQueryDsl;
 use diesel::QueryDsl;
 use diesel::ExpressionMethods;
-use crate::config::database::DbConnection;
+use diesel::PgTextExpressionMethods;
+use diesel::BoolExpressionMethods;
+use diesel::Connection;
+use uuid::Uuid;
+use crate::db::DbConnection;
+use crate::mail;
 use crate::models::entities::{User, NewUser, UpdateUser};
-use crate::models::dto::{UserDto, CreateUserDto, UpdateUserDto, LoginDto, AuthResponseDto, TokenResponseDto};
+use crate::models::dto::{
+    UserDto, CreateUserDto, UpdateUserDto, UserSearchQuery, SuspendUserDto, ChangePasswordDto, ChangeEmailDto, LoginDto,
+    AuthResponseDto, TokenResponseDto, REGISTRATION_CLOSED, REGISTRATION_INVITE_ONLY,
+};
+use crate::services::{InvitationService, MediaService, PrivacyService, SiteSettingsService};
 use crate::utils::error::{AppError, AppResult};
-use crate::auth::{hash_password, verify_password, Claims, create_token};
+use crate::utils::validation::{has_min_length, is_not_empty, is_valid_email};
+use crate::auth::{hash_password, hash_token, verify_password, Claims, create_token};
+use crate::metrics;
+use tracing::instrument;
+
+const MIN_PASSWORD_LENGTH: usize = 6;
+
+/// Horas que tiene el usuario para confirmar un cambio de email
+const EMAIL_CHANGE_TTL_HOURS: i64 = 24;
 
 pub struct UserService;
 
 impl UserService {
-    /// Obtiene todos los usuarios
-    pub fn get_all_users(conn: &mut DbConnection) -> AppResult<Vec<UserDto>> {
+    /// Busca usuarios para la administración
+    #[instrument(skip_all)]
+    pub fn search_users(query: UserSearchQuery, conn: &mut DbConnection) -> AppResult<Vec<UserDto>> {
         use crate::db::schema::users::dsl::*;
         
-        let users_result = users
+        let mut select = users.into_boxed();
+        if let Some(text) = query.q.as_deref().map(str::trim).filter(|t| is_not_empty(t)) {
+            // Los comodines de LIKE que escriba el usuario se buscan literalmente
+            let pattern = format!(
+                "%{}%",
+                text.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+            );
+            select = select.filter(username.ilike(pattern.clone()).or(email.ilike(pattern)));
+        }
+        if let Some(ref role_value) = query.role {
+            select = select.filter(role.eq(role_value.clone()));
+        }
+        // Una suspensión cuyo `suspended_until` ya ha pasado no cuenta
+        let now = Local::now().naive_local();
+        match query.suspended {
+            Some(true) => {
+                select = select
+                    .filter(suspended_at.is_not_null())
+                    .filter(suspended_until.is_null().or(suspended_until.gt(now)));
+            }
+            Some(false) => {
+                select = select.filter(suspended_at.is_null().or(suspended_until.le(now)));
+            }
+            None => {}
+        }
+        
+        let users_result = select
             .order(id.asc())
             .load::<User>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
-        
-        // Convertir entidades a DTOs
-        let user_dtos = users_result.into_iter()
-            .map(|user| UserDto {
-                id: user.id,
-                username: user.username,
-                email: user.email,
-                created_at: user.created_at,
-                updated_at: user.updated_at,
-                role: user.role,
-            })
-            .collect();
+            .map_err(AppError::DatabaseError)?;
         
-        Ok(user_dtos)
+        Ok(users_result.into_iter().map(Self::to_dto).collect())
     }
     
     /// Obtiene un usuario por su ID
+    #[instrument(skip(conn))]
     pub fn get_user_by_id(user_id: i32, conn: &mut DbConnection) -> AppResult<UserDto> {
         use crate::db::schema::users::dsl::*;
         
@@ -49,17 +81,11 @@ impl UserService {
                 _ => AppError::DatabaseError(e)
             })?;
         
-        Ok(UserDto {
-            id: user.id,
-            username: user.username,
-            email: user.email,
-            created_at: user.created_at,
-            updated_at: user.updated_at,
-            role: user.role,
-        })
+        Ok(Self::to_dto(user))
     }
     
     /// Obtiene un usuario por su email
+    #[instrument(skip_all)]
     pub fn get_user_by_email(email_value: &str, conn: &mut DbConnection) -> AppResult<User> {
         use crate::db::schema::users::dsl::*;
         
@@ -73,21 +99,17 @@ impl UserService {
     }
     
     /// Crea un nuevo usuario
+    #[instrument(skip_all)]
     pub fn create_user(dto: CreateUserDto, conn: &mut DbConnection) -> AppResult<UserDto> {
         use crate::db::schema::users;
         
         // Validar datos
-        if dto.username.trim().is_empty() {
+        if !is_not_empty(&dto.username) {
             return Err(AppError::ValidationError("El nombre de usuario no puede estar vacío".to_string()));
         }
         
-        if dto.email.trim().is_empty() {
-            return Err(AppError::ValidationError("El email no puede estar vacío".to_string()));
-        }
-        
-        if dto.password.trim().is_empty() || dto.password.len() < 6 {
-            return Err(AppError::ValidationError("La contraseña debe tener al menos 6 caracteres".to_string()));
-        }
+        Self::validate_email(&dto.email)?;
+        Self::validate_password(&dto.password)?;
         
         // Verificar si ya existe un usuario con el mismo email
         use crate::db::schema::users::dsl::*;
@@ -128,47 +150,82 @@ impl UserService {
         let user = diesel::insert_into(users::table)
             .values(&new_user)
             .get_result::<User>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+            .map_err(AppError::DatabaseError)?;
         
-        Ok(UserDto {
-            id: user.id,
-            username: user.username,
-            email: user.email,
-            created_at: user.created_at,
-            updated_at: user.updated_at,
-            role: user.role,
+        Ok(Self::to_dto(user))
+    }
+    
+    /// Registro público, según la política `registration_policy` de los ajustes
+    /// del sitio. El rol es `user` o, con un código de invitación, el de la
+    /// invitación; nunca el que pida el cliente.
+    #[instrument(skip_all)]
+    pub fn register(dto: CreateUserDto, conn: &mut DbConnection) -> AppResult<UserDto> {
+        let policy = SiteSettingsService::get(conn)?.registration_policy;
+        let invite_code = dto.invite_code.as_deref().map(str::trim).filter(|c| !c.is_empty()).map(str::to_string);
+
+        if policy == REGISTRATION_CLOSED {
+            return Err(AppError::AuthorizationError("El registro de nuevas cuentas está cerrado".to_string()));
+        }
+        if policy == REGISTRATION_INVITE_ONLY && invite_code.is_none() {
+            return Err(AppError::AuthorizationError("El registro requiere un código de invitación".to_string()));
+        }
+
+        // El uso de la invitación se devuelve si la cuenta no llega a crearse
+        conn.transaction::<_, AppError, _>(|conn| {
+            let role = match invite_code {
+                Some(code) => Some(InvitationService::consume(&code, conn)?.role),
+                None => None,
+            };
+            Self::create_user(CreateUserDto { role, invite_code: None, ..dto }, conn)
         })
     }
     
-    /// Actualiza un usuario existente
-    pub fn update_user(user_id: i32, dto: UpdateUserDto, conn: &mut DbConnection) -> AppResult<UserDto> {
+    /// Modificación de un usuario desde la administración. Solo puede hacerla un
+    /// administrador (se comprueba en la base de datos, no en el token), y sobre su
+    /// propia cuenta no puede cambiar el rol, ni la contraseña o el email sin
+    /// confirmarlos: para eso están `change_password` y `request_email_change`.
+    #[instrument(skip(dto, conn))]
+    pub fn update_user(user_id: i32, dto: UpdateUserDto, acting_user_id: i32, conn: &mut DbConnection) -> AppResult<UserDto> {
         use crate::db::schema::users::dsl::*;
         
+        Self::require_admin(acting_user_id, conn)?;
+        
         // Verificar que el usuario existe
-        let user = users
-            .filter(id.eq(user_id))
-            .first::<User>(conn)
-            .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Usuario con ID {} no encontrado", user_id)),
-                _ => AppError::DatabaseError(e)
-            })?;
+        let user = Self::find_user(user_id, conn)?;
+        
+        if user.id == acting_user_id {
+            if dto.role.as_ref().is_some_and(|r| r != &user.role) {
+                return Err(AppError::ValidationError("No puedes cambiar tu propio rol".to_string()));
+            }
+            if dto.password.is_some() {
+                return Err(AppError::ValidationError(
+                    "Cambia tu contraseña con PUT /api/me/password".to_string()
+                ));
+            }
+            if dto.email.as_ref().is_some_and(|e| e != &user.email) {
+                return Err(AppError::ValidationError("Cambia tu email con PUT /api/me/email".to_string()));
+            }
+        }
+        
+        if let Some(ref new_role) = dto.role {
+            if !is_not_empty(new_role) || new_role.len() > 50 {
+                return Err(AppError::ValidationError("El rol debe tener entre 1 y 50 caracteres".to_string()));
+            }
+        }
         
         // Verificar si se está cambiando el email y si el nuevo email ya existe
         if let Some(ref new_email) = dto.email {
             if new_email != &user.email {
-                let email_exists = users
-                    .filter(email.eq(new_email))
-                    .first::<User>(conn)
-                    .is_ok();
-                
-                if email_exists {
-                    return Err(AppError::ValidationError(format!("Ya existe un usuario con el email '{}'", new_email)));
-                }
+                Self::validate_email(new_email)?;
+                Self::ensure_email_available(new_email, conn)?;
             }
         }
         
         // Verificar si se está cambiando el username y si el nuevo username ya existe
         if let Some(ref new_username) = dto.username {
+            if !is_not_empty(new_username) {
+                return Err(AppError::ValidationError("El nombre de usuario no puede estar vacío".to_string()));
+            }
             if new_username != &user.username {
                 let username_exists = users
                     .filter(username.eq(new_username))
@@ -183,15 +240,14 @@ impl UserService {
         
         // Preparar datos para actualización
         let password_hash_value = if let Some(ref new_password) = dto.password {
-            if new_password.is_empty() || new_password.len() < 6 {
-                return Err(AppError::ValidationError("La contraseña debe tener al menos 6 caracteres".to_string()));
-            }
+            Self::validate_password(new_password)?;
             // Hash de la contraseña con bcrypt
             Some(hash_password(new_password)?)
         } else {
             None
         };
         
+        let email_changed = dto.email.as_ref().is_some_and(|e| e != &user.email);
         let update_data = UpdateUser {
             username: dto.username,
             email: dto.email,
@@ -200,49 +256,243 @@ impl UserService {
             role: dto.role,
         };
         
-        // Actualizar el usuario
+        let updated_user = conn.transaction::<_, AppError, _>(|conn| {
+            // Un email fijado por un administrador anula el cambio que estuviera pendiente
+            if email_changed {
+                Self::clear_pending_email(user_id, conn)?;
+            }
+            diesel::update(users.filter(id.eq(user_id)))
+                .set(&update_data)
+                .get_result::<User>(conn)
+                .map_err(AppError::DatabaseError)
+        })?;
+        
+        Ok(Self::to_dto(updated_user))
+    }
+    
+    /// Cambia la contraseña del propio usuario, que debe indicar la actual
+    #[instrument(skip(dto, conn))]
+    pub fn change_password(user_id: i32, dto: ChangePasswordDto, conn: &mut DbConnection) -> AppResult<()> {
+        use crate::db::schema::users::dsl::*;
+        
+        let user = Self::find_user(user_id, conn)?;
+        Self::check_current_password(&user, &dto.current_password)?;
+        Self::validate_password(&dto.new_password)?;
+        
+        diesel::update(users.filter(id.eq(user_id)))
+            .set((
+                password_hash.eq(hash_password(&dto.new_password)?),
+                updated_at.eq(Some(Local::now().naive_local())),
+            ))
+            .execute(conn)
+            .map_err(AppError::DatabaseError)?;
+        
+        tracing::info!(user_id, "Contraseña cambiada por el usuario");
+        Ok(())
+    }
+    
+    /// Inicia el cambio de email del propio usuario: guarda la nueva dirección como
+    /// pendiente y le envía un código de confirmación. El email no cambia hasta
+    /// que se confirma con `confirm_email_change`.
+    #[instrument(skip(dto, conn))]
+    pub fn request_email_change(user_id: i32, dto: ChangeEmailDto, conn: &mut DbConnection) -> AppResult<UserDto> {
+        use crate::db::schema::users::dsl::*;
+        
+        let user = Self::find_user(user_id, conn)?;
+        Self::check_current_password(&user, &dto.current_password)?;
+        
+        let new_email = dto.email.trim().to_string();
+        Self::validate_email(&new_email)?;
+        if new_email == user.email {
+            return Err(AppError::ValidationError("El nuevo email es igual al actual".to_string()));
+        }
+        Self::ensure_email_available(&new_email, conn)?;
+        
+        let token = Uuid::new_v4().simple().to_string();
         let updated_user = diesel::update(users.filter(id.eq(user_id)))
-            .set(&update_data)
+            .set((
+                pending_email.eq(Some(&new_email)),
+                email_change_token_hash.eq(Some(hash_token(&token))),
+                email_change_expires_at.eq(Some(Local::now().naive_local() + Duration::hours(EMAIL_CHANGE_TTL_HOURS))),
+            ))
             .get_result::<User>(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
-        
-        Ok(UserDto {
-            id: updated_user.id,
-            username: updated_user.username,
-            email: updated_user.email,
-            created_at: updated_user.created_at,
-            updated_at: updated_user.updated_at,
-            role: updated_user.role,
-        })
+            .map_err(AppError::DatabaseError)?;
+        
+        mail::send(&mail::Message {
+            to: new_email,
+            subject: "Confirma tu nuevo email".to_string(),
+            body: format!(
+                "Hola, {}:\n\nPara usar esta dirección en tu cuenta, envía este código a POST /api/auth/confirm-email \
+                 en las próximas {} horas:\n\n{}\n\nSi no lo has pedido tú, ignora este mensaje.",
+                updated_user.username, EMAIL_CHANGE_TTL_HOURS, token
+            ),
+        });
+        
+        Ok(Self::to_dto(updated_user))
     }
     
-    /// Elimina un usuario
-    pub fn delete_user(user_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+    /// Confirma un cambio de email con el código enviado a la nueva dirección
+    #[instrument(skip_all)]
+    pub fn confirm_email_change(token: &str, conn: &mut DbConnection) -> AppResult<UserDto> {
         use crate::db::schema::users::dsl::*;
         
-        // Verificar que el usuario existe
-        let _ = users
-            .filter(id.eq(user_id))
+        let invalid = || AppError::ValidationError("El código de confirmación no es válido o ha caducado".to_string());
+        
+        let user = users
+            .filter(email_change_token_hash.eq(hash_token(token.trim())))
             .first::<User>(conn)
             .map_err(|e| match e {
-                DieselError::NotFound => AppError::NotFound(format!("Usuario con ID {} no encontrado", user_id)),
+                DieselError::NotFound => invalid(),
                 _ => AppError::DatabaseError(e)
             })?;
         
-        // Eliminar el usuario
-        let deleted = diesel::delete(users.filter(id.eq(user_id)))
-            .execute(conn)
-            .map_err(|e| AppError::DatabaseError(e))?;
+        let new_email = match (user.pending_email.clone(), user.email_change_expires_at) {
+            (Some(new_email), Some(expires_at)) if expires_at > Local::now().naive_local() => new_email,
+            _ => return Err(invalid()),
+        };
+        
+        conn.transaction::<_, AppError, _>(|conn| {
+            // Otra cuenta ha podido quedarse la dirección mientras tanto
+            Self::ensure_email_available(&new_email, conn)?;
+            Self::clear_pending_email(user.id, conn)?;
+            diesel::update(users.filter(id.eq(user.id)))
+                .set((email.eq(&new_email), updated_at.eq(Some(Local::now().naive_local()))))
+                .get_result::<User>(conn)
+                .map_err(AppError::DatabaseError)
+        })
+        .map(Self::to_dto)
+    }
+    
+    /// Suspende una cuenta desde la administración: no podrá iniciar sesión, y los
+    /// tokens que ya tenga dejan de valer. Sin `until` es un bloqueo permanente.
+    /// Suspender una cuenta ya suspendida sustituye el motivo y la fecha de fin.
+    #[instrument(skip(dto, conn))]
+    pub fn suspend_user(user_id: i32, dto: SuspendUserDto, acting_user_id: i32, conn: &mut DbConnection) -> AppResult<UserDto> {
+        use crate::db::schema::users::dsl::*;
+        
+        Self::require_admin(acting_user_id, conn)?;
+        if user_id == acting_user_id {
+            return Err(AppError::ValidationError("No puedes suspender tu propia cuenta".to_string()));
+        }
+        
+        let now = Local::now().naive_local();
+        if dto.until.is_some_and(|until| until <= now) {
+            return Err(AppError::ValidationError("La fecha de fin de la suspensión debe ser futura".to_string()));
+        }
+        let reason = dto.reason.map(|r| r.trim().to_string()).filter(|r| is_not_empty(r));
+        
+        let user = Self::find_user(user_id, conn)?;
+        let since = if Self::is_suspended(&user) { user.suspended_at } else { Some(now) };
+        
+        let updated_user = diesel::update(users.filter(id.eq(user_id)))
+            .set((
+                suspended_at.eq(since),
+                suspended_until.eq(dto.until),
+                suspension_reason.eq(reason),
+                suspended_by.eq(Some(acting_user_id)),
+            ))
+            .get_result::<User>(conn)
+            .map_err(AppError::DatabaseError)?;
+        
+        tracing::info!(user_id, acting_user_id, until = ?dto.until, "Cuenta suspendida");
+        Ok(Self::to_dto(updated_user))
+    }
+    
+    /// Levanta la suspensión de una cuenta
+    #[instrument(skip(conn))]
+    pub fn unsuspend_user(user_id: i32, acting_user_id: i32, conn: &mut DbConnection) -> AppResult<UserDto> {
+        use crate::db::schema::users::dsl::*;
+        
+        Self::require_admin(acting_user_id, conn)?;
+        Self::find_user(user_id, conn)?;
+        
+        let updated_user = diesel::update(users.filter(id.eq(user_id)))
+            .set((
+                suspended_at.eq(None::<chrono::NaiveDateTime>),
+                suspended_until.eq(None::<chrono::NaiveDateTime>),
+                suspension_reason.eq(None::<String>),
+                suspended_by.eq(None::<i32>),
+            ))
+            .get_result::<User>(conn)
+            .map_err(AppError::DatabaseError)?;
+        
+        tracing::info!(user_id, acting_user_id, "Suspensión levantada");
+        Ok(Self::to_dto(updated_user))
+    }
+    
+    /// Comprueba que la cuenta de un token sigue existiendo y no está suspendida, y
+    /// la devuelve para que el middleware use su rol actual y no el del token
+    #[instrument(skip(conn))]
+    pub fn check_active(user_id: i32, conn: &mut DbConnection) -> AppResult<User> {
+        let user = Self::find_user(user_id, conn).map_err(|e| match e {
+            AppError::NotFound(_) => AppError::AuthenticationError("La cuenta ya no existe".to_string()),
+            e => e,
+        })?;
+        Self::ensure_not_suspended(&user)?;
+        Ok(user)
+    }
+    
+    /// Indica si la suspensión de la cuenta sigue en vigor
+    pub fn is_suspended(user: &User) -> bool {
+        user.suspended_at.is_some() && user.suspended_until.is_none_or(|until| until > Local::now().naive_local())
+    }
+    
+    /// Elimina un usuario desde la administración, con la misma limpieza que
+    /// `PrivacyService::delete_account`. Solo puede hacerlo un administrador, que
+    /// no puede eliminar su propia cuenta (para eso está la de cuenta propia, con
+    /// contraseña) ni la del último administrador.
+    #[instrument(skip(conn))]
+    pub fn delete_user(user_id: i32, acting_user_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        use crate::db::schema::users::dsl::*;
         
-        if deleted == 0 {
-            return Err(AppError::NotFound(format!("No se pudo eliminar el usuario con ID {}", user_id)));
+        Self::require_admin(acting_user_id, conn)?;
+        if user_id == acting_user_id {
+            return Err(AppError::ValidationError("No puedes eliminar tu propia cuenta desde la administración".to_string()));
         }
         
+        let stored_keys = conn.transaction::<_, AppError, _>(|conn| {
+            // Bloquear la cuenta y, si es de un administrador, las de los demás,
+            // para que dos borrados simultáneos no dejen el sitio sin ninguno
+            let user = users
+                .filter(id.eq(user_id))
+                .for_update()
+                .first::<User>(conn)
+                .map_err(|e| match e {
+                    DieselError::NotFound => AppError::NotFound(format!("Usuario con ID {} no encontrado", user_id)),
+                    _ => AppError::DatabaseError(e)
+                })?;
+            
+            if user.role == "admin" {
+                let other_admins = users
+                    .filter(role.eq("admin"))
+                    .filter(id.ne(user_id))
+                    .select(id)
+                    .for_update()
+                    .load::<i32>(conn)?;
+                if other_admins.is_empty() {
+                    return Err(AppError::ValidationError("No se puede eliminar al último administrador".to_string()));
+                }
+            }
+            
+            PrivacyService::erase_account(&user, conn)
+        })?;
+        
+        // Una vez borradas las filas, un fallo al borrar los ficheros solo deja basura en el almacenamiento
+        MediaService::remove_stored(&stored_keys);
+        
+        tracing::info!(user_id, acting_user_id, "Cuenta eliminada por un administrador");
         Ok(())
     }
     
     /// Autentica un usuario y genera un token JWT
+    #[instrument(skip_all)]
     pub fn login(dto: LoginDto, conn: &mut DbConnection) -> AppResult<AuthResponseDto> {
+        let result = Self::authenticate(dto, conn);
+        metrics::record_login(result.is_ok());
+        result
+    }
+    
+    fn authenticate(dto: LoginDto, conn: &mut DbConnection) -> AppResult<AuthResponseDto> {
         // Buscar usuario por email
         let user = Self::get_user_by_email(&dto.email, conn)?;
         
@@ -252,28 +502,21 @@ impl UserService {
             return Err(AppError::AuthenticationError("Credenciales inválidas".to_string()));
         }
         
+        Self::ensure_not_suspended(&user)?;
+        
         // Generar token JWT
         let claims = Claims::new(user.id, user.username.clone(), user.role.clone(), 24); // Token válido por 24 horas
         let token = create_token(claims)
             .map_err(|_| AppError::InternalServerError("Error al generar token".to_string()))?;
         
-        // Crear respuesta
-        let user_dto = UserDto {
-            id: user.id,
-            username: user.username,
-            email: user.email,
-            created_at: user.created_at,
-            updated_at: user.updated_at,
-            role: user.role,
-        };
-        
         Ok(AuthResponseDto {
             token,
-            user: user_dto,
+            user: Self::to_dto(user),
         })
     }
     
     /// Refresca un token JWT
+    #[instrument(skip(conn))]
     pub fn refresh_token(user_id: i32, conn: &mut DbConnection) -> AppResult<TokenResponseDto> {
         use crate::db::schema::users::dsl;
         
@@ -286,6 +529,8 @@ impl UserService {
                 _ => AppError::DatabaseError(e)
             })?;
         
+        Self::ensure_not_suspended(&user)?;
+        
         // Generar nuevo token JWT
         let claims = Claims::new(user.id, user.username, user.role, 24); // Token válido por 24 horas
         let token = create_token(claims)
@@ -293,4 +538,118 @@ impl UserService {
         
         Ok(TokenResponseDto { token })
     }
-} 
\ No newline at end of file
+    
+    fn to_dto(user: User) -> UserDto {
+        let status = match (Self::is_suspended(&user), user.suspended_until) {
+            (false, _) => "active",
+            (true, Some(_)) => "suspended",
+            (true, None) => "banned",
+        };
+        UserDto {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            role: user.role,
+            pending_email: user.pending_email,
+            status: status.to_string(),
+            suspended_at: user.suspended_at,
+            suspended_until: user.suspended_until,
+            suspension_reason: user.suspension_reason,
+            suspended_by: user.suspended_by,
+        }
+    }
+    
+    fn find_user(user_id: i32, conn: &mut DbConnection) -> AppResult<User> {
+        use crate::db::schema::users::dsl::*;
+        
+        users
+            .filter(id.eq(user_id))
+            .first::<User>(conn)
+            .map_err(|e| match e {
+                DieselError::NotFound => AppError::NotFound(format!("Usuario con ID {} no encontrado", user_id)),
+                _ => AppError::DatabaseError(e)
+            })
+    }
+    
+    fn require_admin(acting_user_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        match Self::find_user(acting_user_id, conn) {
+            Ok(actor) if actor.role == "admin" && !Self::is_suspended(&actor) => Ok(()),
+            Ok(_) | Err(AppError::NotFound(_)) => Err(AppError::AuthorizationError(
+                "Solo un administrador puede gestionar usuarios".to_string()
+            )),
+            Err(e) => Err(e),
+        }
+    }
+    
+    fn ensure_not_suspended(user: &User) -> AppResult<()> {
+        if !Self::is_suspended(user) {
+            return Ok(());
+        }
+        let mut message = match user.suspended_until {
+            Some(until) => format!("La cuenta está suspendida hasta el {}", until.format("%Y-%m-%d %H:%M")),
+            None => "La cuenta está bloqueada".to_string(),
+        };
+        if let Some(ref reason) = user.suspension_reason {
+            message.push_str(&format!(": {}", reason));
+        }
+        Err(AppError::AuthenticationError(message))
+    }
+    
+    /// Confirma una operación sobre la propia cuenta con la contraseña actual
+    pub fn check_current_password(user: &User, current_password: &str) -> AppResult<()> {
+        if !verify_password(current_password, &user.password_hash)? {
+            return Err(AppError::AuthenticationError("La contraseña actual no es correcta".to_string()));
+        }
+        Ok(())
+    }
+    
+    fn validate_password(password: &str) -> AppResult<()> {
+        if !is_not_empty(password) || !has_min_length(password, MIN_PASSWORD_LENGTH) {
+            return Err(AppError::ValidationError(format!(
+                "La contraseña debe tener al menos {} caracteres",
+                MIN_PASSWORD_LENGTH
+            )));
+        }
+        Ok(())
+    }
+    
+    fn validate_email(value: &str) -> AppResult<()> {
+        if !is_not_empty(value) {
+            return Err(AppError::ValidationError("El email no puede estar vacío".to_string()));
+        }
+        if !is_valid_email(value) {
+            return Err(AppError::ValidationError(format!("El email '{}' no es válido", value)));
+        }
+        Ok(())
+    }
+    
+    fn ensure_email_available(value: &str, conn: &mut DbConnection) -> AppResult<()> {
+        use crate::db::schema::users::dsl::*;
+        
+        let email_exists = users
+            .filter(email.eq(value))
+            .first::<User>(conn)
+            .is_ok();
+        
+        if email_exists {
+            return Err(AppError::ValidationError(format!("Ya existe un usuario con el email '{}'", value)));
+        }
+        Ok(())
+    }
+    
+    fn clear_pending_email(user_id: i32, conn: &mut DbConnection) -> AppResult<()> {
+        use crate::db::schema::users::dsl::*;
+        
+        diesel::update(users.filter(id.eq(user_id)))
+            .set((
+                pending_email.eq(None::<String>),
+                email_change_token_hash.eq(None::<String>),
+                email_change_expires_at.eq(None::<chrono::NaiveDateTime>),
+            ))
+            .execute(conn)
+            .map_err(AppError::DatabaseError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,642 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::Duration;
+use chrono::{Local, NaiveDateTime};
+use diesel::prelude::*;
+use tracing::instrument;
+use uuid::Uuid;
+use crate::auth::hash_password;
+use crate::config;
+use crate::db::{lower, DbConnection};
+use crate::db::schema::{categories, comments, users};
+use crate::models::entities::{
+    Comment, NewComment, NewPost, NewUser, Post, User, IMPORT_ENTITY_ATTACHMENT, IMPORT_ENTITY_AUTHOR,
+    IMPORT_ENTITY_CATEGORY, IMPORT_ENTITY_COMMENT, IMPORT_ENTITY_POST, POST_STATUS_DRAFT, POST_STATUS_PRIVATE,
+    POST_STATUS_PUBLISHED, SLUG_ENTITY_POST,
+};
+use crate::models::dto::CreateCategoryDto;
+use crate::repositories::{ImportKeyRepository, MediaRepository, PostRepository, TagRepository};
+use crate::services::media_service::UploadedFile;
+use crate::services::{CategoryService, MediaService};
+use crate::utils::error::{AppError, AppResult};
+use crate::utils::slug::{is_valid_slug, slugify, with_unique_slug_in_history};
+use crate::utils::wxr::{percent_decode, WxrAuthor, WxrCategory, WxrDocument, WxrItem};
+
+/// Tiempo máximo para descargar cada adjunto del sitio original
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Aviso emitido durante la importación, para mostrar el progreso
+pub enum ImportEvent {
+    Progress(String),
+    Error(String),
+}
+
+#[derive(Default)]
+pub struct WordPressImportOptions {
+    /// No descarga los adjuntos; los posts conservan las URLs del sitio original
+    pub skip_attachments: bool,
+}
+
+/// Recuento de un tipo de entidad
+#[derive(Default, Debug)]
+pub struct ImportCount {
+    pub imported: usize,
+    /// Ya importadas en una ejecución anterior, o asociadas a una existente
+    pub existing: usize,
+    pub failed: usize,
+}
+
+#[derive(Default, Debug)]
+pub struct ImportReport {
+    pub authors: ImportCount,
+    pub categories: ImportCount,
+    pub attachments: ImportCount,
+    pub posts: ImportCount,
+    pub comments: ImportCount,
+    /// Entradas que no se importan por su tipo o estado (páginas, papelera, spam...)
+    pub ignored: usize,
+}
+
+impl ImportReport {
+    pub fn failed(&self) -> usize {
+        self.authors.failed + self.categories.failed + self.attachments.failed + self.posts.failed + self.comments.failed
+    }
+}
+
+pub struct WordPressImportService;
+
+impl WordPressImportService {
+    /// Importa un fichero WXR ya analizado. Cada entidad se importa por separado:
+    /// un error se notifica y la importación continúa con la siguiente.
+    ///
+    /// Es idempotente: las entidades se identifican por su GUID (o login, o
+    /// nicename) en `import_keys`, así que repetirla solo añade lo nuevo.
+    #[instrument(skip_all, fields(source = %document.site_url))]
+    pub fn import(
+        document: &WxrDocument,
+        options: &WordPressImportOptions,
+        conn: &mut DbConnection,
+        on_event: &mut dyn FnMut(ImportEvent),
+    ) -> ImportReport {
+        let mut importer = Importer {
+            source: document.site_url.trim_end_matches('/').to_string(),
+            conn,
+            on_event,
+            report: ImportReport::default(),
+            users: HashMap::new(),
+            wp_users: HashMap::new(),
+            categories: HashMap::new(),
+            attachments: HashMap::new(),
+        };
+
+        for author in &document.authors {
+            importer.import_author(author);
+        }
+        importer.import_categories(&document.categories);
+
+        let attachments: Vec<&WxrItem> = document.items.iter().filter(|i| i.post_type == "attachment").collect();
+        if options.skip_attachments {
+            importer.report.ignored += attachments.len();
+        } else {
+            for (n, item) in attachments.iter().enumerate() {
+                importer.import_attachment(item, n + 1, attachments.len());
+            }
+        }
+
+        let entries: Vec<&WxrItem> = document.items.iter().filter(|i| i.post_type == "post").collect();
+        for (n, item) in entries.iter().enumerate() {
+            importer.import_post(item, &document.items, n + 1, entries.len());
+        }
+
+        // Páginas, menús, revisiones y demás tipos de contenido
+        importer.report.ignored += document
+            .items
+            .iter()
+            .filter(|i| i.post_type != "post" && i.post_type != "attachment")
+            .count();
+
+        importer.report
+    }
+}
+
+/// Adjunto ya importado: su fichero en la biblioteca y la URL que sustituye a la original
+struct ImportedAttachment {
+    media_id: i32,
+    original_url: Option<String>,
+    url: String,
+}
+
+/// Estado de una importación en curso: correspondencia entre los identificadores
+/// de WordPress y las entidades locales
+struct Importer<'a> {
+    source: String,
+    conn: &'a mut DbConnection,
+    on_event: &'a mut dyn FnMut(ImportEvent),
+    report: ImportReport,
+    /// Login de WordPress → usuario
+    users: HashMap<String, i32>,
+    /// ID de usuario de WordPress → usuario
+    wp_users: HashMap<i64, i32>,
+    /// Nicename → categoría
+    categories: HashMap<String, i32>,
+    /// ID del adjunto en WordPress → fichero
+    attachments: HashMap<i64, ImportedAttachment>,
+}
+
+impl Importer<'_> {
+    fn progress(&mut self, message: String) {
+        (self.on_event)(ImportEvent::Progress(message));
+    }
+
+    fn error(&mut self, message: String) {
+        (self.on_event)(ImportEvent::Error(message));
+    }
+
+    /// Asocia el autor al usuario con su mismo email (sin distinguir
+    /// mayúsculas); si no existe, crea la cuenta con una contraseña aleatoria.
+    /// Coincidir solo en el nombre de usuario no basta: el fichero podría
+    /// atribuir posts a una cuenta ajena.
+    fn import_author(&mut self, author: &WxrAuthor) -> Option<i32> {
+        match self.resolve_author(author) {
+            Ok((user_id, created)) => {
+                if created {
+                    self.report.authors.imported += 1;
+                    self.progress(format!("Autor '{}' creado (ID {})", author.login, user_id));
+                } else {
+                    self.report.authors.existing += 1;
+                }
+                self.users.insert(author.login.clone(), user_id);
+                if let Some(wp_id) = author.id {
+                    self.wp_users.insert(wp_id, user_id);
+                }
+                Some(user_id)
+            }
+            Err(e) => {
+                self.report.authors.failed += 1;
+                self.error(format!("Autor '{}': {}", author.login, e));
+                None
+            }
+        }
+    }
+
+    fn resolve_author(&mut self, author: &WxrAuthor) -> AppResult<(i32, bool)> {
+        let source = self.source.clone();
+        self.conn.transaction::<_, AppError, _>(|conn| {
+            if let Some(user_id) = ImportKeyRepository::find(&source, IMPORT_ENTITY_AUTHOR, &author.login, conn)? {
+                if users::table.find(user_id).first::<User>(conn).optional()?.is_some() {
+                    return Ok((user_id, false));
+                }
+            }
+
+            let email = author.email.as_deref().map(str::trim).filter(|e| !e.is_empty());
+            let existing = match email {
+                Some(email) => users::table
+                    .filter(lower(users::email.nullable()).eq(email.to_lowercase()))
+                    .first::<User>(conn)
+                    .optional()?,
+                None => None,
+            };
+
+            let (user_id, created) = match existing {
+                Some(user) => (user.id, false),
+                None => {
+                    let now = Local::now().naive_local();
+                    let password = Uuid::new_v4().simple().to_string();
+                    let user = diesel::insert_into(users::table)
+                        .values(&NewUser {
+                            username: Self::free_username(&author.login, conn)?,
+                            email: email
+                                .map(str::to_string)
+                                .unwrap_or_else(|| format!("{}@wordpress.invalid", author.login)),
+                            password_hash: hash_password(&password)?,
+                            created_at: Some(now),
+                            updated_at: Some(now),
+                            role: "user".to_string(),
+                        })
+                        .get_result::<User>(conn)?;
+                    (user.id, true)
+                }
+            };
+
+            ImportKeyRepository::record(&source, IMPORT_ENTITY_AUTHOR, &author.login, user_id, conn)?;
+            Ok((user_id, created))
+        })
+    }
+
+    /// El login del autor o, si ya lo usa otra cuenta, el primero libre con un
+    /// sufijo numérico
+    fn free_username(login: &str, conn: &mut DbConnection) -> AppResult<String> {
+        let mut candidate = login.to_string();
+        let mut suffix = 2;
+        while users::table
+            .filter(users::username.eq(&candidate))
+            .select(users::id)
+            .first::<i32>(conn)
+            .optional()?
+            .is_some()
+        {
+            candidate = format!("{}-{}", login, suffix);
+            suffix += 1;
+        }
+        Ok(candidate)
+    }
+
+    /// Usuario de un autor por su login; los que no aparecen en la lista de
+    /// autores del fichero se crean al vuelo
+    fn author_id(&mut self, login: Option<&str>) -> Option<i32> {
+        let login = login?;
+        if let Some(&user_id) = self.users.get(login) {
+            return Some(user_id);
+        }
+        self.import_author(&WxrAuthor {
+            id: None,
+            login: login.to_string(),
+            email: None,
+        })
+    }
+
+    /// Importa las categorías respetando la jerarquía: cada una espera a que se
+    /// haya importado su padre. Si el padre no está en el fichero, queda en la raíz.
+    fn import_categories(&mut self, wxr_categories: &[WxrCategory]) {
+        let mut pending: Vec<&WxrCategory> = wxr_categories.iter().collect();
+
+        while !pending.is_empty() {
+            let known: Vec<&str> = wxr_categories.iter().map(|c| c.nicename.as_str()).collect();
+            let (ready, waiting): (Vec<&WxrCategory>, Vec<&WxrCategory>) = pending.into_iter().partition(|c| {
+                match c.parent.as_deref() {
+                    Some(parent) if known.contains(&parent) => self.categories.contains_key(parent),
+                    _ => true,
+                }
+            });
+
+            // Un ciclo o un padre que falló: el resto se importa en la raíz
+            let (ready, waiting) = if ready.is_empty() { (waiting, Vec::new()) } else { (ready, waiting) };
+
+            for category in ready {
+                let parent_id = category.parent.as_deref().and_then(|p| self.categories.get(p).copied());
+                match self.resolve_category(category, parent_id) {
+                    Ok((category_id, created)) => {
+                        if created {
+                            self.report.categories.imported += 1;
+                            self.progress(format!("Categoría '{}' creada (ID {})", category.name, category_id));
+                        } else {
+                            self.report.categories.existing += 1;
+                        }
+                        self.categories.insert(category.nicename.clone(), category_id);
+                    }
+                    Err(e) => {
+                        self.report.categories.failed += 1;
+                        self.error(format!("Categoría '{}': {}", category.nicename, e));
+                    }
+                }
+            }
+            pending = waiting;
+        }
+    }
+
+    /// Una categoría con el mismo slug que una existente se asocia a esta
+    fn resolve_category(&mut self, category: &WxrCategory, parent_id: Option<i32>) -> AppResult<(i32, bool)> {
+        let source = self.source.clone();
+        let conn = &mut *self.conn;
+
+        if let Some(category_id) = ImportKeyRepository::find(&source, IMPORT_ENTITY_CATEGORY, &category.nicename, conn)? {
+            if categories::table.find(category_id).select(categories::id).first::<i32>(conn).optional()?.is_some() {
+                return Ok((category_id, false));
+            }
+        }
+
+        let slug = wp_slug(&category.nicename).unwrap_or_else(|| slugify(&category.name));
+        // Un slug antiguo lleva a la categoría renombrada, en vez de duplicarla
+        let (category_id, created) = match CategoryService::find_id_by_slug(&slug, conn)? {
+            Some(existing_id) => (existing_id, false),
+            None => {
+                let dto = CategoryService::create_category(
+                    CreateCategoryDto {
+                        name: category.name.clone(),
+                        slug: Some(slug),
+                        description: category.description.clone(),
+                        parent_id,
+                        position: None,
+                    },
+                    conn,
+                )?;
+                (dto.id, true)
+            }
+        };
+
+        ImportKeyRepository::record(&source, IMPORT_ENTITY_CATEGORY, &category.nicename, category_id, conn)?;
+        Ok((category_id, created))
+    }
+
+    /// Descarga el adjunto y lo guarda en la biblioteca de medios con sus variantes
+    fn import_attachment(&mut self, item: &WxrItem, n: usize, total: usize) {
+        let source = self.source.clone();
+        match ImportKeyRepository::find(&source, IMPORT_ENTITY_ATTACHMENT, &item.guid, self.conn) {
+            Ok(Some(media_id)) => match MediaRepository::find_by_id(media_id, self.conn) {
+                Ok(existing) => {
+                    self.report.attachments.existing += 1;
+                    let url = MediaService::to_dto(existing, Vec::new()).url;
+                    self.attachments.insert(item.id, ImportedAttachment {
+                        media_id,
+                        original_url: item.attachment_url.clone(),
+                        url,
+                    });
+                    return;
+                }
+                // El fichero se borró después de importarlo: se vuelve a importar
+                Err(diesel::result::Error::NotFound) => {}
+                Err(e) => {
+                    self.report.attachments.failed += 1;
+                    self.error(format!("[{}/{}] Adjunto '{}': {}", n, total, item.guid, AppError::DatabaseError(e)));
+                    return;
+                }
+            },
+            Ok(None) => {}
+            Err(e) => {
+                self.report.attachments.failed += 1;
+                self.error(format!("[{}/{}] Adjunto '{}': {}", n, total, item.guid, AppError::DatabaseError(e)));
+                return;
+            }
+        }
+
+        let url = match item.attachment_url.as_deref() {
+            Some(url) => url,
+            None => {
+                self.report.attachments.failed += 1;
+                self.error(format!("[{}/{}] Adjunto '{}': no indica wp:attachment_url", n, total, item.guid));
+                return;
+            }
+        };
+
+        let user_id = match self.author_id(item.author_login.as_deref()) {
+            Some(user_id) => user_id,
+            None => {
+                self.report.attachments.failed += 1;
+                self.error(format!("[{}/{}] Adjunto '{}': no tiene un autor válido", n, total, url));
+                return;
+            }
+        };
+
+        let result = download(url).and_then(|data| {
+            let filename = url.rsplit('/').next().unwrap_or(url).to_string();
+            let alt_text = Some(item.title.clone()).filter(|t| !t.is_empty());
+            self.conn.transaction::<_, AppError, _>(|conn| {
+                let media = MediaService::upload(user_id, true, UploadedFile { filename, data, alt_text }, conn)?;
+                ImportKeyRepository::record(&source, IMPORT_ENTITY_ATTACHMENT, &item.guid, media.id, conn)?;
+                Ok(media)
+            })
+        });
+
+        match result {
+            Ok(media) => {
+                self.report.attachments.imported += 1;
+                self.progress(format!("[{}/{}] Adjunto '{}' importado (ID {})", n, total, url, media.id));
+                self.attachments.insert(item.id, ImportedAttachment {
+                    media_id: media.id,
+                    original_url: Some(url.to_string()),
+                    url: media.url,
+                });
+            }
+            Err(e) => {
+                self.report.attachments.failed += 1;
+                self.error(format!("[{}/{}] Adjunto '{}': {}", n, total, url, e));
+            }
+        }
+    }
+
+    fn import_post(&mut self, item: &WxrItem, items: &[WxrItem], n: usize, total: usize) {
+        let status = match item.status.as_str() {
+            "publish" => POST_STATUS_PUBLISHED,
+            "draft" | "pending" | "future" => POST_STATUS_DRAFT,
+            "private" => POST_STATUS_PRIVATE,
+            // Papelera, borradores automáticos y revisiones
+            _ => {
+                self.report.ignored += 1;
+                return;
+            }
+        };
+
+        let source = self.source.clone();
+        let existing = match ImportKeyRepository::find(&source, IMPORT_ENTITY_POST, &item.guid, self.conn) {
+            Ok(existing) => existing,
+            Err(e) => {
+                self.report.posts.failed += 1;
+                self.error(format!("[{}/{}] Post '{}': {}", n, total, item.guid, AppError::DatabaseError(e)));
+                return;
+            }
+        };
+
+        let post_id = match existing {
+            Some(post_id) => {
+                // Ya importado: solo se añaden los comentarios nuevos. Si se borró
+                // después de importarlo, se respeta el borrado.
+                match PostRepository::find_by_id(post_id, self.conn) {
+                    Ok(_) => {
+                        self.report.posts.existing += 1;
+                        post_id
+                    }
+                    Err(_) => {
+                        self.report.ignored += 1;
+                        return;
+                    }
+                }
+            }
+            None => {
+                let author_id = self.author_id(item.author_login.as_deref());
+                match self.create_post(item, items, status, author_id) {
+                    Ok((post, requested_slug)) => {
+                        self.report.posts.imported += 1;
+                        self.progress(format!("[{}/{}] Post '{}' importado (ID {}, slug '{}')", n, total, post.title, post.id, post.slug));
+                        if post.slug != requested_slug {
+                            self.progress(format!("    El slug '{}' ya estaba en uso", requested_slug));
+                        }
+                        post.id
+                    }
+                    Err(e) => {
+                        self.report.posts.failed += 1;
+                        self.error(format!("[{}/{}] Post '{}': {}", n, total, item.guid, e));
+                        return;
+                    }
+                }
+            }
+        };
+
+        self.import_comments(item, post_id);
+    }
+
+    /// Crea el post con sus categorías, etiquetas y galería en una única transacción.
+    /// Devuelve también el slug de WordPress, que puede diferir del final.
+    fn create_post(&mut self, item: &WxrItem, items: &[WxrItem], status: &str, author_id: Option<i32>) -> AppResult<(Post, String)> {
+        let title = match item.title.trim() {
+            "" => format!("Sin título ({})", item.id),
+            title => title.to_string(),
+        };
+        let slug = wp_slug(&item.slug)
+            .or_else(|| Some(slugify(&title)).filter(|s| !s.is_empty()))
+            .unwrap_or_else(|| format!("post-{}", item.id));
+
+        // Las URLs de los adjuntos importados pasan a apuntar a la biblioteca de medios
+        let mut body = item.content.clone();
+        for attachment in self.attachments.values() {
+            if let Some(ref original) = attachment.original_url {
+                body = body.replace(original.as_str(), &attachment.url);
+            }
+        }
+
+        let created_at = item.date.as_deref().and_then(parse_date).unwrap_or_else(|| Local::now().naive_local());
+        let mut new_post = NewPost {
+            title,
+            slug: slug.clone(),
+            body,
+            created_at: Some(created_at),
+            updated_at: Some(item.modified.as_deref().and_then(parse_date).unwrap_or(created_at)),
+            excerpt: item.excerpt.clone(),
+            featured_media_id: item.thumbnail_id.and_then(|id| self.attachments.get(&id)).map(|a| a.media_id),
+            featured_image_url: None,
+            author_id,
+            status: status.to_string(),
+        };
+        let category_ids: Vec<i32> = item.categories.iter().filter_map(|c| self.categories.get(c).copied()).collect();
+        let gallery: Vec<i32> = items
+            .iter()
+            .filter(|i| i.post_type == "attachment" && i.parent_id == Some(item.id))
+            .filter_map(|i| self.attachments.get(&i.id))
+            .map(|a| a.media_id)
+            .collect();
+
+        let source = self.source.clone();
+        let post = self.conn.transaction::<_, AppError, _>(|conn| {
+            // Un slug en uso (p. ej. por otro blog importado) o que redirige a
+            // otro post se desambigua con un sufijo
+            let post = with_unique_slug_in_history(SLUG_ENTITY_POST, None, &slug, conn, |candidate, conn| {
+                new_post.slug = candidate.to_string();
+                Ok(PostRepository::create_with_categories(&new_post, &category_ids, conn)?)
+            })?;
+
+            if !item.tags.is_empty() {
+                TagRepository::set_post_tags(post.id, &item.tags, conn)?;
+            }
+            for &media_id in &gallery {
+                MediaRepository::attach(post.id, media_id, None, conn)?;
+            }
+
+            ImportKeyRepository::record(&source, IMPORT_ENTITY_POST, &item.guid, post.id, conn)?;
+            Ok(post)
+        })?;
+
+        Ok((post, slug))
+    }
+
+    /// Importa los comentarios aprobados de un post con sus respuestas, en una
+    /// única transacción. Los ya importados se omiten.
+    fn import_comments(&mut self, item: &WxrItem, post_id: i32) {
+        let mut wxr_comments: Vec<_> = item.comments.iter().collect();
+        wxr_comments.sort_by_key(|c| c.id);
+
+        let source = self.source.clone();
+        let wp_users = &self.wp_users;
+        let mut counts = ImportCount::default();
+        let mut ignored = 0;
+
+        let result = self.conn.transaction::<_, AppError, _>(|conn| {
+            // ID del comentario en WordPress → comentario
+            let mut imported: HashMap<i64, i32> = HashMap::new();
+
+            for comment in wxr_comments {
+                let key = format!("{}#comment-{}", item.guid, comment.id);
+                if let Some(comment_id) = ImportKeyRepository::find(&source, IMPORT_ENTITY_COMMENT, &key, conn)? {
+                    let exists = comments::table.find(comment_id).first::<Comment>(conn).optional()?.is_some();
+                    if exists {
+                        imported.insert(comment.id, comment_id);
+                        counts.existing += 1;
+                        continue;
+                    }
+                }
+
+                // Sin moderación, importar los pendientes los publicaría
+                if comment.approved != "1" {
+                    ignored += 1;
+                    continue;
+                }
+
+                let created_at = comment.date.as_deref().and_then(parse_date);
+                let new_comment = NewComment {
+                    post_id,
+                    user_id: comment.user_id.and_then(|id| wp_users.get(&id).copied()),
+                    author_name: comment.author.clone(),
+                    author_email: comment.author_email.clone().filter(|e| !e.is_empty()),
+                    content: comment.content.clone(),
+                    created_at,
+                    updated_at: created_at,
+                    // Una respuesta a un comentario no importado queda como comentario raíz
+                    parent_id: comment.parent_id.and_then(|id| imported.get(&id).copied()),
+                };
+                let created = diesel::insert_into(comments::table)
+                    .values(&new_comment)
+                    .get_result::<Comment>(conn)?;
+
+                ImportKeyRepository::record(&source, IMPORT_ENTITY_COMMENT, &key, created.id, conn)?;
+                imported.insert(comment.id, created.id);
+                counts.imported += 1;
+            }
+
+            Ok(())
+        });
+
+        match result {
+            Ok(()) => {
+                self.report.comments.imported += counts.imported;
+                self.report.comments.existing += counts.existing;
+                self.report.ignored += ignored;
+                if counts.imported > 0 {
+                    self.progress(format!("Post {}: {} comentarios importados", post_id, counts.imported));
+                }
+            }
+            Err(e) => {
+                self.report.comments.failed += item.comments.len();
+                self.error(format!("Comentarios del post '{}': {}", item.guid, e));
+            }
+        }
+    }
+}
+
+/// Slug de WordPress decodificado, si es válido tal cual
+fn wp_slug(slug: &str) -> Option<String> {
+    let decoded = percent_decode(slug.trim());
+    if decoded.is_empty() {
+        return None;
+    }
+    if is_valid_slug(&decoded) {
+        return Some(decoded);
+    }
+    Some(slugify(&decoded)).filter(|s| !s.is_empty())
+}
+
+fn parse_date(date: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M:%S").ok()
+}
+
+/// Descarga un adjunto del sitio original respetando el tamaño máximo de subida
+fn download(url: &str) -> AppResult<Vec<u8>> {
+    let response = ureq::get(url)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .call()
+        .map_err(|e| AppError::ValidationError(format!("No se pudo descargar: {}", e)))?;
+
+    let max_bytes = config::media_max_upload_bytes();
+    let mut data = Vec::new();
+    response
+        .into_reader()
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| AppError::ValidationError(format!("No se pudo descargar: {}", e)))?;
+
+    if data.len() > max_bytes {
+        return Err(AppError::ValidationError(format!(
+            "El fichero supera el tamaño máximo de {} bytes",
+            max_bytes
+        )));
+    }
+
+    Ok(data)
+}
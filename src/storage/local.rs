@@ -0,0 +1,70 @@
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use crate::utils::error::{AppError, AppResult};
+use super::{default_public_url, validate_key, StorageBackend};
+
+/// Guarda los ficheros en un directorio del sistema de ficheros local
+pub struct LocalStorage {
+    root: PathBuf,
+    base_url: String,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>, base_url: impl Into<String>) -> Self {
+        LocalStorage {
+            root: root.into(),
+            base_url: base_url.into(),
+        }
+    }
+
+    /// `MEDIA_ROOT` (por defecto `./uploads`) y `MEDIA_BASE_URL` (por defecto `/media`)
+    pub fn from_env() -> Self {
+        Self::new(
+            env::var("MEDIA_ROOT").unwrap_or_else(|_| "./uploads".to_string()),
+            env::var("MEDIA_BASE_URL").unwrap_or_else(|_| "/media".to_string()),
+        )
+    }
+
+    fn path_for(&self, key: &str) -> AppResult<PathBuf> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+impl StorageBackend for LocalStorage {
+    fn put(&self, key: &str, data: &[u8], _content_type: &str) -> AppResult<()> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Escribir en un temporal y renombrar para no dejar ficheros a medias
+        let tmp = path.with_extension("part");
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> AppResult<Vec<u8>> {
+        let path = self.path_for(key)?;
+        fs::read(&path).map_err(|e| match e.kind() {
+            ErrorKind::NotFound => AppError::NotFound(format!("Fichero '{}' no encontrado", key)),
+            _ => AppError::IoError(e),
+        })
+    }
+
+    fn delete(&self, key: &str) -> AppResult<()> {
+        let path = self.path_for(key)?;
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::IoError(e)),
+        }
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        default_public_url(&self.base_url, key)
+    }
+}
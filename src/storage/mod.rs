@@ -0,0 +1,133 @@
+//! Almacenamiento de los ficheros subidos a la biblioteca de medios.
+//!
+//! El backend se elige con `STORAGE_BACKEND` (`local` por defecto, o `s3`) y se
+//! crea una sola vez al arrancar con [`init`]. Las operaciones son bloqueantes, así
+//! que deben llamarse desde `web::block`.
+
+mod local;
+mod s3;
+
+use std::env;
+use std::sync::OnceLock;
+use crate::utils::error::{AppError, AppResult};
+
+use local::LocalStorage;
+use s3::S3Storage;
+
+/// Destino donde se guardan los ficheros, identificados por una clave relativa
+/// del tipo `2026/10/<uuid>.jpg`
+pub trait StorageBackend: Send + Sync {
+    /// Guarda (o sobrescribe) el contenido asociado a `key`
+    fn put(&self, key: &str, data: &[u8], content_type: &str) -> AppResult<()>;
+
+    /// Lee el contenido asociado a `key`; `NotFound` si no existe
+    fn get(&self, key: &str) -> AppResult<Vec<u8>>;
+
+    /// Elimina el fichero; no es un error que ya no exista
+    fn delete(&self, key: &str) -> AppResult<()>;
+
+    /// URL pública con la que los clientes descargan el fichero
+    fn public_url(&self, key: &str) -> String;
+}
+
+static BACKEND: OnceLock<Box<dyn StorageBackend>> = OnceLock::new();
+
+/// Crea el backend configurado; falla si la configuración está incompleta
+pub fn init() -> AppResult<()> {
+    let backend = from_env()?;
+    let _ = BACKEND.set(backend);
+    Ok(())
+}
+
+/// Backend de almacenamiento de la aplicación
+pub fn backend() -> &'static dyn StorageBackend {
+    BACKEND
+        .get()
+        .expect("storage::init() debe llamarse al arrancar")
+        .as_ref()
+}
+
+/// Construye el backend configurado en las variables de entorno
+fn from_env() -> AppResult<Box<dyn StorageBackend>> {
+    match env::var("STORAGE_BACKEND").unwrap_or_else(|_| "local".to_string()).as_str() {
+        "local" => Ok(Box::new(LocalStorage::from_env())),
+        "s3" => Ok(Box::new(S3Storage::from_env()?)),
+        other => Err(AppError::InternalServerError(format!(
+            "STORAGE_BACKEND desconocido: '{}' (valores válidos: local, s3)",
+            other
+        ))),
+    }
+}
+
+/// URL de un fichero servido por la propia aplicación en `/media/{key}`
+fn default_public_url(base: &str, key: &str) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), key)
+}
+
+/// Tipo MIME a partir de la extensión de la clave, para servir el fichero
+pub fn content_type_for(key: &str) -> &'static str {
+    match key.rsplit('.').next().map(|ext| ext.to_ascii_lowercase()).as_deref() {
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("pdf") => "application/pdf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Las claves las genera la aplicación, pero se validan igualmente antes de
+/// convertirlas en rutas o URLs
+fn validate_key(key: &str) -> AppResult<()> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && key.split('/').all(|segment| !segment.is_empty() && segment != "." && segment != "..")
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '-' | '_' | '.'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::NotFound(format!("Fichero '{}' no encontrado", key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_key_accepts_generated_keys() {
+        assert!(validate_key("2026/10/0f3c9a.jpg").is_ok());
+        assert!(validate_key("2026/10/0f3c9a-thumb.webp").is_ok());
+        assert!(validate_key("avatars/user_1.png").is_ok());
+    }
+
+    #[test]
+    fn validate_key_rejects_paths_outside_the_root() {
+        for key in ["", "/etc/passwd", "../secret", "2026/../../x", "2026/./x", "2026//x", "2026/10/"] {
+            assert!(matches!(validate_key(key), Err(AppError::NotFound(_))), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn validate_key_rejects_unexpected_characters() {
+        for key in ["2026/10/a b.jpg", "2026/10/a%2e.jpg", "2026\\10\\a.jpg", "2026/10/ñ.jpg", "a?b=c"] {
+            assert!(validate_key(key).is_err(), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn default_public_url_joins_without_double_slash() {
+        assert_eq!(default_public_url("/media", "2026/10/a.jpg"), "/media/2026/10/a.jpg");
+        assert_eq!(default_public_url("https://cdn.example.com/", "a.jpg"), "https://cdn.example.com/a.jpg");
+    }
+
+    #[test]
+    fn content_type_for_uses_the_extension() {
+        assert_eq!(content_type_for("2026/10/a.JPG"), "image/jpeg");
+        assert_eq!(content_type_for("a.webp"), "image/webp");
+        assert_eq!(content_type_for("a.pdf"), "application/pdf");
+        assert_eq!(content_type_for("a.exe"), "application/octet-stream");
+        assert_eq!(content_type_for("sin-extension"), "application/octet-stream");
+    }
+}
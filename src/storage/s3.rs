@@ -0,0 +1,243 @@
+use std::env;
+use std::io::Read;
+use std::time::Duration;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use crate::utils::error::{AppError, AppResult};
+use super::{default_public_url, validate_key, StorageBackend};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Guarda los ficheros en un bucket compatible con S3 (AWS, MinIO, R2...).
+///
+/// Usa direcciones de tipo ruta (`{endpoint}/{bucket}/{key}`) y firma las
+/// peticiones con AWS Signature Version 4.
+pub struct S3Storage {
+    endpoint: String,
+    host: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+    public_url: Option<String>,
+    base_url: String,
+    agent: ureq::Agent,
+}
+
+impl S3Storage {
+    /// Lee `S3_ENDPOINT`, `S3_BUCKET`, `S3_ACCESS_KEY_ID`, `S3_SECRET_ACCESS_KEY` y,
+    /// opcionalmente, `S3_REGION` (por defecto `us-east-1`) y `S3_PUBLIC_URL`.
+    /// Sin `S3_PUBLIC_URL` los ficheros se sirven a través de `MEDIA_BASE_URL`.
+    pub fn from_env() -> AppResult<Self> {
+        let required = |name: &str| {
+            env::var(name).map_err(|_| AppError::InternalServerError(format!("{} no está configurada", name)))
+        };
+
+        let endpoint = required("S3_ENDPOINT")?.trim_end_matches('/').to_string();
+        // Solo se admite el endpoint sin ruta, p. ej. `http://localhost:9000`
+        let host = endpoint
+            .strip_prefix("https://")
+            .or_else(|| endpoint.strip_prefix("http://"))
+            .map(|rest| rest.split('/').next().unwrap_or_default().to_string())
+            .filter(|host| !host.is_empty())
+            .ok_or_else(|| AppError::InternalServerError(format!("S3_ENDPOINT inválido: '{}'", endpoint)))?;
+
+        Ok(S3Storage {
+            endpoint,
+            host,
+            bucket: required("S3_BUCKET")?,
+            region: env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            access_key: required("S3_ACCESS_KEY_ID")?,
+            secret_key: required("S3_SECRET_ACCESS_KEY")?,
+            public_url: env::var("S3_PUBLIC_URL").ok().filter(|u| !u.is_empty()),
+            base_url: env::var("MEDIA_BASE_URL").unwrap_or_else(|_| "/media".to_string()),
+            agent: ureq::AgentBuilder::new()
+                .timeout_connect(Duration::from_secs(5))
+                .timeout(Duration::from_secs(60))
+                .build(),
+        })
+    }
+
+    fn object_path(&self, key: &str) -> String {
+        format!("/{}/{}", self.bucket, uri_encode(key))
+    }
+
+    /// Prepara una petición firmada para el objeto `key`
+    fn request(&self, method: &str, key: &str, payload: &[u8]) -> AppResult<ureq::Request> {
+        validate_key(key)?;
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(payload));
+        let path = self.object_path(key);
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{}\n{}\n\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\n{}\n{}",
+            method, path, self.host, payload_hash, amz_date, signed_headers, payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let k_date = hmac(format!("AWS4{}", self.secret_key).as_bytes(), date.as_bytes());
+        let k_region = hmac(&k_date, self.region.as_bytes());
+        let k_service = hmac(&k_region, b"s3");
+        let k_signing = hmac(&k_service, b"aws4_request");
+        let signature = hex::encode(hmac(&k_signing, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            self.access_key, scope, signed_headers, signature
+        );
+
+        Ok(self
+            .agent
+            .request(method, &format!("{}{}", self.endpoint, path))
+            // El mismo Host que se ha firmado, con el puerto tal cual aparece en S3_ENDPOINT
+            .set("Host", &self.host)
+            .set("x-amz-content-sha256", &payload_hash)
+            .set("x-amz-date", &amz_date)
+            .set("Authorization", &authorization))
+    }
+}
+
+impl StorageBackend for S3Storage {
+    fn put(&self, key: &str, data: &[u8], content_type: &str) -> AppResult<()> {
+        self.request("PUT", key, data)?
+            .set("Content-Type", content_type)
+            .send_bytes(data)
+            .map_err(|e| s3_error("PUT", key, e))?;
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> AppResult<Vec<u8>> {
+        let response = self.request("GET", key, &[])?
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(404, _) => AppError::NotFound(format!("Fichero '{}' no encontrado", key)),
+                e => s3_error("GET", key, e),
+            })?;
+
+        let mut data = Vec::new();
+        response.into_reader().read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    fn delete(&self, key: &str) -> AppResult<()> {
+        // S3 responde 204 aunque el objeto no exista
+        self.request("DELETE", key, &[])?
+            .call()
+            .map_err(|e| s3_error("DELETE", key, e))?;
+        Ok(())
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        match &self.public_url {
+            Some(base) => default_public_url(base, key),
+            None => default_public_url(&self.base_url, key),
+        }
+    }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC acepta claves de cualquier longitud");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Codificación de rutas de SigV4: todo salvo los caracteres no reservados y `/`
+fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn s3_error(method: &str, key: &str, error: ureq::Error) -> AppError {
+    let detail = match error {
+        ureq::Error::Status(status, response) => {
+            format!("{} {}", status, response.into_string().unwrap_or_default())
+        }
+        other => other.to_string(),
+    };
+    AppError::InternalServerError(format!("S3 {} '{}': {}", method, key, detail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage(public_url: Option<&str>) -> S3Storage {
+        S3Storage {
+            endpoint: "http://localhost:9000".to_string(),
+            host: "localhost:9000".to_string(),
+            bucket: "media".to_string(),
+            region: "us-east-1".to_string(),
+            access_key: "test".to_string(),
+            secret_key: "test".to_string(),
+            public_url: public_url.map(str::to_string),
+            base_url: "/media".to_string(),
+            agent: ureq::AgentBuilder::new().build(),
+        }
+    }
+
+    #[test]
+    fn object_path_includes_the_bucket() {
+        assert_eq!(storage(None).object_path("2026/10/a-thumb.jpg"), "/media/2026/10/a-thumb.jpg");
+    }
+
+    #[test]
+    fn public_url_prefers_s3_public_url() {
+        assert_eq!(storage(None).public_url("2026/10/a.jpg"), "/media/2026/10/a.jpg");
+        assert_eq!(
+            storage(Some("https://cdn.example.com/media/")).public_url("2026/10/a.jpg"),
+            "https://cdn.example.com/media/2026/10/a.jpg"
+        );
+    }
+
+    #[test]
+    fn request_rejects_invalid_keys_before_signing() {
+        let storage = storage(None);
+        for key in ["../a.jpg", "/a.jpg", "a b.jpg"] {
+            assert!(matches!(storage.request("GET", key, &[]), Err(AppError::NotFound(_))), "{:?}", key);
+        }
+        assert!(storage.request("GET", "2026/10/a.jpg", &[]).is_ok());
+    }
+
+    #[test]
+    fn uri_encode_keeps_unreserved_characters_and_slashes() {
+        assert_eq!(uri_encode("2026/10/a-b_c.d~e"), "2026/10/a-b_c.d~e");
+        assert_eq!(uri_encode("a b+c"), "a%20b%2Bc");
+    }
+
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        // Caso de prueba 2 de la RFC 4231
+        let mac = hmac(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(hex::encode(mac), "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+    }
+
+    /// Sube, lee y borra un objeto en un bucket real. Necesita las variables de
+    /// `S3Storage::from_env`: `cargo test -- --ignored s3_round_trip`
+    #[test]
+    #[ignore = "necesita un bucket S3 configurado con S3_ENDPOINT, S3_BUCKET, S3_ACCESS_KEY_ID y S3_SECRET_ACCESS_KEY"]
+    fn s3_round_trip() {
+        let storage = S3Storage::from_env().expect("configuración S3 incompleta");
+        let key = format!("test/{}.txt", uuid::Uuid::new_v4().simple());
+
+        storage.put(&key, b"hola", "text/plain").unwrap();
+        assert_eq!(storage.get(&key).unwrap(), b"hola");
+        storage.delete(&key).unwrap();
+        assert!(matches!(storage.get(&key), Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,122 @@
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    http::header::{HeaderName, HeaderValue},
+    Error as ActixError,
+};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use std::rc::Rc;
+use std::time::Instant;
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::telemetry::CURRENT_REQUEST_ID;
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longitud máxima aceptada para un `X-Request-Id` recibido del cliente
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Middleware que abre un span por petición con su `X-Request-Id` (propagado o
+/// generado), la ruta y, cuando el usuario se autentica, su ID. El identificador
+/// se devuelve en la cabecera `X-Request-Id` de la respuesta.
+pub struct RequestTracing;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestTracing
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Transform = RequestTracingMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestTracingMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct RequestTracingMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestTracingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = ActixError> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = ActixError;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let start = Instant::now();
+
+        // Reutilizar el identificador del cliente o del proxy si es razonable
+        let request_id = req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|h| h.to_str().ok())
+            .map(str::trim)
+            .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+            .map(str::to_string)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        let route = req
+            .request()
+            .match_pattern()
+            .unwrap_or_else(|| "unmatched".to_string());
+
+        let span = tracing::info_span!(
+            "http_request",
+            request_id = %request_id,
+            method = %req.method(),
+            route = %route,
+            user_id = tracing::field::Empty,
+            status = tracing::field::Empty,
+        );
+
+
+        let header_value = HeaderValue::from_str(&request_id).ok();
+        let fut = CURRENT_REQUEST_ID.scope(request_id, async move {
+            let result = service.call(req).await;
+            let span = tracing::Span::current();
+
+            match result {
+                Ok(mut res) => {
+                    span.record("status", res.status().as_u16());
+                    tracing::info!(latency_ms = start.elapsed().as_millis() as u64, "Petición completada");
+
+                    if let Some(value) = header_value {
+                        res.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+                    }
+                    Ok(res)
+                }
+                Err(e) => {
+                    // Errores devueltos por middlewares internos (p. ej. JwtAuth)
+                    span.record("status", e.as_response_error().status_code().as_u16());
+                    tracing::warn!(latency_ms = start.elapsed().as_millis() as u64, error = %e, "Petición rechazada");
+
+                    // La respuesta se construye aquí, dentro del ámbito de la petición,
+                    // para que el cuerpo lleve su identificador
+                    let mut response = e.error_response();
+                    if let Some(value) = header_value {
+                        response.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+                    }
+                    Err(InternalError::from_response(e.to_string(), response).into())
+                }
+            }
+        });
+
+        Box::pin(fut.instrument(span))
+    }
+}
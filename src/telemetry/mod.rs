@@ -0,0 +1,36 @@
+pub mod middleware;
+
+use std::env;
+use tracing_subscriber::EnvFilter;
+
+pub use middleware::RequestTracing;
+
+tokio::task_local! {
+    /// Identificador de la petición en curso, disponible para cualquier capa
+    /// que se ejecute dentro del manejo de la petición
+    pub static CURRENT_REQUEST_ID: String;
+}
+
+/// Inicializa el suscriptor de tracing. Con `LOG_FORMAT=json` los eventos se
+/// emiten como JSON (un objeto por línea); en otro caso, en texto legible.
+/// Los mensajes emitidos con el crate `log` se redirigen también a tracing.
+pub fn init() {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let json = env::var("LOG_FORMAT").map(|v| v.eq_ignore_ascii_case("json")).unwrap_or(false);
+
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+    if json {
+        builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .init();
+    } else {
+        builder.init();
+    }
+}
+
+/// Devuelve el identificador de la petición en curso, si lo hay
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|id| id.clone()).ok()
+}
@@ -0,0 +1,521 @@
+//! Formato de las copias de seguridad del sitio: un único documento JSON o un
+//! fichero NDJSON con un registro por línea.
+//!
+//! El formato es independiente de las entidades de la base de datos y lleva
+//! versión, para que una copia pueda restaurarse después de cambiar el esquema.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use chrono::NaiveDateTime;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::entities::POST_STATUSES;
+
+/// Identifica los ficheros de copia de seguridad
+pub const BACKUP_FORMAT: &str = "blog-rust-backup";
+/// Versión del formato; aumenta con cada cambio incompatible
+pub const BACKUP_VERSION: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// Un único objeto JSON con una lista por tipo de entidad
+    Json,
+    /// Una cabecera y después un registro por línea, con su tipo en `type`
+    Ndjson,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BackupHeader {
+    pub format: String,
+    pub version: u32,
+    pub created_at: NaiveDateTime,
+    /// Si los usuarios llevan el hash de su contraseña
+    pub includes_password_hashes: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BackupUser {
+    pub id: i32,
+    pub username: String,
+    pub email: String,
+    /// Solo si la copia se hizo incluyendo las contraseñas
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_hash: Option<String>,
+    pub role: String,
+    /// Perfil público; las copias anteriores a los perfiles no lo incluyen. El
+    /// avatar subido no se copia, porque la biblioteca de medios tampoco.
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub bio: Option<String>,
+    #[serde(default)]
+    pub website: Option<String>,
+    #[serde(default)]
+    pub social_links: Option<Value>,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BackupCategory {
+    pub id: i32,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub parent_id: Option<i32>,
+    pub position: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Post sin su imagen destacada de la biblioteca de medios, que no forma parte de la copia
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BackupPost {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub body: String,
+    pub excerpt: Option<String>,
+    pub featured_image_url: Option<String>,
+    pub author_id: Option<i32>,
+    pub status: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BackupPostCategory {
+    pub post_id: i32,
+    pub category_id: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct BackupComment {
+    pub id: i32,
+    pub post_id: i32,
+    pub user_id: Option<i32>,
+    pub parent_id: Option<i32>,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub content: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Contenido completo de una copia. Los IDs son los del sitio de origen; al
+/// restaurar se asignan otros nuevos.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Archive {
+    #[serde(flatten)]
+    pub header: BackupHeader,
+    pub users: Vec<BackupUser>,
+    pub categories: Vec<BackupCategory>,
+    pub posts: Vec<BackupPost>,
+    pub post_categories: Vec<BackupPostCategory>,
+    pub comments: Vec<BackupComment>,
+}
+
+/// Escribe la copia en el formato indicado
+pub fn write(archive: &Archive, format: ArchiveFormat, out: &mut dyn Write) -> Result<(), String> {
+    match format {
+        ArchiveFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, archive).map_err(|e| e.to_string())?;
+            writeln!(out).map_err(|e| e.to_string())
+        }
+        ArchiveFormat::Ndjson => {
+            write_record(out, "header", &archive.header)?;
+            for user in &archive.users {
+                write_record(out, "user", user)?;
+            }
+            for category in &archive.categories {
+                write_record(out, "category", category)?;
+            }
+            for post in &archive.posts {
+                write_record(out, "post", post)?;
+            }
+            for post_category in &archive.post_categories {
+                write_record(out, "post_category", post_category)?;
+            }
+            for comment in &archive.comments {
+                write_record(out, "comment", comment)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Lee una copia en cualquiera de los dos formatos. El NDJSON se reconoce por
+/// su primera línea, una cabecera con `"type": "header"`.
+pub fn read(content: &str) -> Result<Archive, String> {
+    let first_line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or_default();
+    let is_ndjson = serde_json::from_str::<Value>(first_line)
+        .map(|v| v.get("type").and_then(Value::as_str) == Some("header"))
+        .unwrap_or(false);
+
+    let archive = if is_ndjson {
+        read_ndjson(content)?
+    } else {
+        serde_json::from_str::<Archive>(content).map_err(|e| format!("JSON no válido: {}", e))?
+    };
+
+    if archive.header.format != BACKUP_FORMAT {
+        return Err(format!("El fichero no es una copia de seguridad ('{}')", archive.header.format));
+    }
+    if archive.header.version > BACKUP_VERSION {
+        return Err(format!(
+            "La copia usa la versión {} del formato y esta versión del blog solo admite hasta la {}",
+            archive.header.version, BACKUP_VERSION
+        ));
+    }
+
+    Ok(archive)
+}
+
+/// Comprueba la integridad referencial de la copia: IDs únicos, referencias a
+/// entidades incluidas en ella y jerarquías sin ciclos. Devuelve los problemas
+/// encontrados.
+///
+/// Los emails se comparan sin distinguir mayúsculas, igual que al asociar los
+/// usuarios de la copia con las cuentas existentes durante la restauración.
+pub fn validate(archive: &Archive) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let users = unique_ids("usuarios", archive.users.iter().map(|u| u.id), &mut problems);
+    let categories = unique_ids("categorías", archive.categories.iter().map(|c| c.id), &mut problems);
+    let posts = unique_ids("posts", archive.posts.iter().map(|p| p.id), &mut problems);
+    unique_ids("comentarios", archive.comments.iter().map(|c| c.id), &mut problems);
+
+    unique_values("usuarios", "email", archive.users.iter().map(|u| u.email.to_lowercase()), &mut problems);
+    unique_values("usuarios", "nombre de usuario", archive.users.iter().map(|u| u.username.clone()), &mut problems);
+    unique_values("categorías", "slug", archive.categories.iter().map(|c| c.slug.clone()), &mut problems);
+    unique_values("posts", "slug", archive.posts.iter().map(|p| p.slug.clone()), &mut problems);
+
+    for category in &archive.categories {
+        if let Some(parent) = category.parent_id {
+            if !categories.contains(&parent) {
+                problems.push(format!("La categoría {} tiene como padre la categoría {}, que no está en la copia", category.id, parent));
+            }
+        }
+    }
+    let category_parents: HashMap<i32, i32> =
+        archive.categories.iter().filter_map(|c| c.parent_id.map(|p| (c.id, p))).collect();
+    if let Some(id) = find_cycle(&category_parents) {
+        problems.push(format!("La jerarquía de categorías tiene un ciclo que pasa por la categoría {}", id));
+    }
+
+    for post in &archive.posts {
+        if !POST_STATUSES.contains(&post.status.as_str()) {
+            problems.push(format!("El post {} tiene un estado no válido: '{}'", post.id, post.status));
+        }
+        if let Some(author) = post.author_id {
+            if !users.contains(&author) {
+                problems.push(format!("El autor del post {} es el usuario {}, que no está en la copia", post.id, author));
+            }
+        }
+    }
+
+    let mut pairs = HashSet::new();
+    for link in &archive.post_categories {
+        if !posts.contains(&link.post_id) {
+            problems.push(format!("post_categories hace referencia al post {}, que no está en la copia", link.post_id));
+        }
+        if !categories.contains(&link.category_id) {
+            problems.push(format!("post_categories hace referencia a la categoría {}, que no está en la copia", link.category_id));
+        }
+        if !pairs.insert((link.post_id, link.category_id)) {
+            problems.push(format!("El post {} aparece más de una vez en la categoría {}", link.post_id, link.category_id));
+        }
+    }
+
+    let comment_posts: HashMap<i32, i32> = archive.comments.iter().map(|c| (c.id, c.post_id)).collect();
+    for comment in &archive.comments {
+        if !posts.contains(&comment.post_id) {
+            problems.push(format!("El comentario {} pertenece al post {}, que no está en la copia", comment.id, comment.post_id));
+        }
+        if let Some(user) = comment.user_id {
+            if !users.contains(&user) {
+                problems.push(format!("El comentario {} es del usuario {}, que no está en la copia", comment.id, user));
+            }
+        }
+        if let Some(parent) = comment.parent_id {
+            match comment_posts.get(&parent) {
+                None => problems.push(format!("El comentario {} responde al comentario {}, que no está en la copia", comment.id, parent)),
+                Some(&post) if post != comment.post_id => {
+                    problems.push(format!("El comentario {} responde al comentario {}, que es de otro post", comment.id, parent))
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    let comment_parents: HashMap<i32, i32> =
+        archive.comments.iter().filter_map(|c| c.parent_id.map(|p| (c.id, p))).collect();
+    if let Some(id) = find_cycle(&comment_parents) {
+        problems.push(format!("Las respuestas forman un ciclo que pasa por el comentario {}", id));
+    }
+
+    problems
+}
+
+fn write_record<T: Serialize>(out: &mut dyn Write, record_type: &str, record: &T) -> Result<(), String> {
+    let mut value = serde_json::to_value(record).map_err(|e| e.to_string())?;
+    if let Value::Object(ref mut map) = value {
+        map.insert("type".to_string(), Value::String(record_type.to_string()));
+    }
+    serde_json::to_writer(&mut *out, &value).map_err(|e| e.to_string())?;
+    writeln!(out).map_err(|e| e.to_string())
+}
+
+fn read_ndjson(content: &str) -> Result<Archive, String> {
+    let mut header = None;
+    let mut archive_users = Vec::new();
+    let mut categories = Vec::new();
+    let mut posts = Vec::new();
+    let mut post_categories = Vec::new();
+    let mut comments = Vec::new();
+
+    for (n, line) in content.lines().enumerate().filter(|(_, l)| !l.trim().is_empty()) {
+        let line_number = n + 1;
+        let value: Value = serde_json::from_str(line).map_err(|e| format!("Línea {}: JSON no válido: {}", line_number, e))?;
+        let record_type = value.get("type").and_then(Value::as_str).unwrap_or_default().to_string();
+        match record_type.as_str() {
+            "header" => header = Some(record(value, line_number)?),
+            "user" => archive_users.push(record(value, line_number)?),
+            "category" => categories.push(record(value, line_number)?),
+            "post" => posts.push(record(value, line_number)?),
+            "post_category" => post_categories.push(record(value, line_number)?),
+            "comment" => comments.push(record(value, line_number)?),
+            other => return Err(format!("Línea {}: tipo de registro desconocido: '{}'", line_number, other)),
+        }
+    }
+
+    Ok(Archive {
+        header: header.ok_or_else(|| "Falta la cabecera de la copia".to_string())?,
+        users: archive_users,
+        categories,
+        posts,
+        post_categories,
+        comments,
+    })
+}
+
+fn record<T: DeserializeOwned>(value: Value, line_number: usize) -> Result<T, String> {
+    serde_json::from_value(value).map_err(|e| format!("Línea {}: {}", line_number, e))
+}
+
+fn unique_ids(entity: &str, ids: impl Iterator<Item = i32>, problems: &mut Vec<String>) -> HashSet<i32> {
+    let mut seen = HashSet::new();
+    for id in ids {
+        if !seen.insert(id) {
+            problems.push(format!("El ID {} está repetido en {}", id, entity));
+        }
+    }
+    seen
+}
+
+fn unique_values(entity: &str, field: &str, values: impl Iterator<Item = String>, problems: &mut Vec<String>) {
+    let mut seen = HashSet::new();
+    for value in values {
+        if !seen.insert(value.clone()) {
+            problems.push(format!("El {} '{}' está repetido en {}", field, value, entity));
+        }
+    }
+}
+
+/// Primer nodo que forma parte de un ciclo en una relación hijo → padre
+fn find_cycle(parents: &HashMap<i32, i32>) -> Option<i32> {
+    let mut ids: Vec<i32> = parents.keys().copied().collect();
+    ids.sort_unstable();
+    for start in ids {
+        let mut visited = HashSet::new();
+        let mut current = start;
+        while let Some(&parent) = parents.get(&current) {
+            if !visited.insert(current) {
+                return Some(current);
+            }
+            current = parent;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: i32, username: &str, email: &str) -> BackupUser {
+        BackupUser {
+            id,
+            username: username.to_string(),
+            email: email.to_string(),
+            password_hash: None,
+            role: "user".to_string(),
+            display_name: None,
+            bio: None,
+            website: None,
+            social_links: None,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn category(id: i32, slug: &str, parent_id: Option<i32>) -> BackupCategory {
+        BackupCategory {
+            id,
+            name: slug.to_string(),
+            slug: slug.to_string(),
+            description: None,
+            parent_id,
+            position: 0,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn post(id: i32, slug: &str, author_id: Option<i32>) -> BackupPost {
+        BackupPost {
+            id,
+            title: slug.to_string(),
+            slug: slug.to_string(),
+            body: String::new(),
+            excerpt: None,
+            featured_image_url: None,
+            author_id,
+            status: "published".to_string(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    fn comment(id: i32, post_id: i32, parent_id: Option<i32>) -> BackupComment {
+        BackupComment {
+            id,
+            post_id,
+            user_id: None,
+            parent_id,
+            author_name: None,
+            author_email: None,
+            content: "Hola".to_string(),
+            created_at: None,
+            updated_at: None,
+        }
+    }
+
+    /// Copia válida: dos usuarios, una categoría con una subcategoría, dos posts
+    /// y una respuesta a un comentario
+    fn valid_archive() -> Archive {
+        Archive {
+            header: BackupHeader {
+                format: BACKUP_FORMAT.to_string(),
+                version: BACKUP_VERSION,
+                created_at: NaiveDateTime::default(),
+                includes_password_hashes: false,
+            },
+            users: vec![user(1, "ana", "ana@ejemplo.com"), user(2, "luis", "luis@ejemplo.com")],
+            categories: vec![category(1, "rust", None), category(2, "web", Some(1))],
+            posts: vec![post(1, "hola", Some(1)), post(2, "adios", None)],
+            post_categories: vec![BackupPostCategory { post_id: 1, category_id: 2 }],
+            comments: vec![comment(1, 1, None), comment(2, 1, Some(1))],
+        }
+    }
+
+    #[test]
+    fn validate_accepts_a_consistent_archive() {
+        assert!(validate(&valid_archive()).is_empty());
+    }
+
+    #[test]
+    fn validate_reports_duplicates() {
+        let mut archive = valid_archive();
+        archive.users.push(user(1, "ana", "ANA@ejemplo.com"));
+        archive.categories.push(category(3, "rust", None));
+        archive.posts.push(post(3, "hola", None));
+        archive.post_categories.push(BackupPostCategory { post_id: 1, category_id: 2 });
+
+        let problems = validate(&archive);
+        assert_eq!(
+            problems,
+            vec![
+                "El ID 1 está repetido en usuarios",
+                // Los emails no distinguen mayúsculas
+                "El email 'ana@ejemplo.com' está repetido en usuarios",
+                "El nombre de usuario 'ana' está repetido en usuarios",
+                "El slug 'rust' está repetido en categorías",
+                "El slug 'hola' está repetido en posts",
+                "El post 1 aparece más de una vez en la categoría 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_reports_dangling_references() {
+        let mut archive = valid_archive();
+        archive.categories.push(category(3, "huerfana", Some(9)));
+        archive.posts.push(post(3, "sin-autor", Some(9)));
+        archive.post_categories.push(BackupPostCategory { post_id: 9, category_id: 8 });
+        let mut foreign = comment(3, 9, None);
+        foreign.user_id = Some(7);
+        archive.comments.push(foreign);
+        archive.comments.push(comment(4, 1, Some(6)));
+
+        let problems = validate(&archive);
+        assert_eq!(
+            problems,
+            vec![
+                "La categoría 3 tiene como padre la categoría 9, que no está en la copia",
+                "El autor del post 3 es el usuario 9, que no está en la copia",
+                "post_categories hace referencia al post 9, que no está en la copia",
+                "post_categories hace referencia a la categoría 8, que no está en la copia",
+                "El comentario 3 pertenece al post 9, que no está en la copia",
+                "El comentario 3 es del usuario 7, que no está en la copia",
+                "El comentario 4 responde al comentario 6, que no está en la copia",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_reports_replies_to_another_post_and_invalid_status() {
+        let mut archive = valid_archive();
+        archive.comments.push(comment(3, 2, Some(1)));
+        archive.posts[1].status = "trash".to_string();
+
+        let problems = validate(&archive);
+        assert_eq!(
+            problems,
+            vec![
+                "El post 2 tiene un estado no válido: 'trash'",
+                "El comentario 3 responde al comentario 1, que es de otro post",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_reports_cycles() {
+        let mut archive = valid_archive();
+        archive.categories[0].parent_id = Some(2);
+        archive.comments[0].parent_id = Some(2);
+
+        let problems = validate(&archive);
+        assert_eq!(
+            problems,
+            vec![
+                "La jerarquía de categorías tiene un ciclo que pasa por la categoría 1",
+                "Las respuestas forman un ciclo que pasa por el comentario 1",
+            ]
+        );
+
+        // Una categoría que es su propio padre también es un ciclo
+        let mut self_parent = valid_archive();
+        self_parent.categories[1].parent_id = Some(2);
+        assert_eq!(validate(&self_parent), vec!["La jerarquía de categorías tiene un ciclo que pasa por la categoría 2"]);
+    }
+
+    #[test]
+    fn find_cycle_ignores_chains_without_cycles() {
+        let parents: HashMap<i32, i32> = [(1, 2), (2, 3), (4, 3)].into_iter().collect();
+        assert_eq!(find_cycle(&parents), None);
+
+        let parents: HashMap<i32, i32> = [(5, 1), (1, 2), (2, 3), (3, 1)].into_iter().collect();
+        assert_eq!(find_cycle(&parents), Some(1));
+    }
+}
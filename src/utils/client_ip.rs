@@ -0,0 +1,75 @@
+//! IP del cliente que hace una petición.
+//!
+//! `X-Forwarded-For` la escribe el cliente tanto como el proxy, así que solo se
+//! tiene en cuenta si la conexión viene de un proxy de confianza
+//! (`TRUSTED_PROXIES`); si no, la IP es la de la conexión.
+
+use std::net::IpAddr;
+use actix_web::HttpRequest;
+use crate::config;
+
+/// IP del cliente de la petición, en forma canónica
+pub fn from_request(req: &HttpRequest) -> Option<String> {
+    let peer = req.peer_addr()?.ip();
+    let forwarded_for: Vec<&str> = req
+        .headers()
+        .get_all("x-forwarded-for")
+        .filter_map(|value| value.to_str().ok())
+        .collect();
+    Some(resolve(peer, &forwarded_for, &config::trusted_proxies()).to_string())
+}
+
+/// Recorre `X-Forwarded-For` de derecha a izquierda mientras cada salto sea un
+/// proxy de confianza: la primera dirección que no lo es es la del cliente. Las
+/// de su izquierda las pudo escribir el propio cliente y se ignoran.
+fn resolve(peer: IpAddr, forwarded_for: &[&str], trusted: &[IpAddr]) -> IpAddr {
+    let mut client = peer;
+    let hops = forwarded_for.iter().flat_map(|value| value.split(',')).rev();
+    for hop in hops {
+        if !trusted.contains(&client) {
+            break;
+        }
+        match hop.trim().parse() {
+            Ok(ip) => client = ip,
+            // Un salto ilegible corta la cadena: lo anterior no es fiable
+            Err(_) => break,
+        }
+    }
+    client
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn resolve_ignores_forwarded_for_from_untrusted_peers() {
+        let peer = ip("203.0.113.7");
+        assert_eq!(resolve(peer, &["1.2.3.4"], &[]), peer);
+        assert_eq!(resolve(peer, &["1.2.3.4"], &[ip("10.0.0.1")]), peer);
+    }
+
+    #[test]
+    fn resolve_takes_the_client_from_a_trusted_proxy() {
+        let proxy = ip("10.0.0.1");
+        assert_eq!(resolve(proxy, &["198.51.100.2"], &[proxy]), ip("198.51.100.2"));
+        // Sin cabecera, la IP es la del proxy
+        assert_eq!(resolve(proxy, &[], &[proxy]), proxy);
+    }
+
+    #[test]
+    fn resolve_skips_only_trusted_hops() {
+        let proxies = [ip("10.0.0.1"), ip("10.0.0.2")];
+        // El cliente escribió 1.2.3.4 por su cuenta; el proxy añadió la real
+        assert_eq!(
+            resolve(ip("10.0.0.1"), &["1.2.3.4, 198.51.100.2", "10.0.0.2"], &proxies),
+            ip("198.51.100.2")
+        );
+        assert_eq!(resolve(ip("10.0.0.1"), &["1.2.3.4, basura"], &proxies), ip("10.0.0.1"));
+        assert_eq!(resolve(ip("10.0.0.1"), &[" 2001:db8::1 "], &proxies), ip("2001:db8::1"));
+    }
+}
@@ -1,6 +1,9 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
 use diesel::result::Error as DieselError;
+use serde::Serialize;
 use thiserror::Error;
 use std::io::Error as IoError;
+use crate::telemetry;
 
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -26,4 +29,50 @@ pub enum AppError {
     InternalServerError(String),
 }
 
-pub type AppResult<T> = Result<T, AppError>; 
\ No newline at end of file
+pub type AppResult<T> = Result<T, AppError>;
+
+/// Cuerpo JSON de las respuestas de error
+#[derive(Serialize)]
+pub struct ErrorPayload {
+    pub error: String,
+    pub request_id: Option<String>,
+}
+
+impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            AppError::AuthenticationError(_) => StatusCode::UNAUTHORIZED,
+            AppError::AuthorizationError(_) => StatusCode::FORBIDDEN,
+            AppError::DatabaseError(_) | AppError::IoError(_) | AppError::InternalServerError(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        // Los detalles de los errores internos solo van al log, nunca al cliente
+        let error = match self {
+            AppError::NotFound(msg)
+            | AppError::ValidationError(msg)
+            | AppError::AuthenticationError(msg)
+            | AppError::AuthorizationError(msg) => msg.clone(),
+            _ => {
+                tracing::error!(error = %self, "Error interno");
+                "Error interno del servidor".to_string()
+            }
+        };
+
+        HttpResponse::build(self.status_code()).json(error_body(error))
+    }
+}
+
+/// Construye el cuerpo de error con el identificador de la petición en curso.
+/// Lo usan también los controladores que montan su propia respuesta de error.
+pub fn error_body(error: impl Into<String>) -> ErrorPayload {
+    ErrorPayload {
+        error: error.into(),
+        request_id: telemetry::current_request_id(),
+    }
+}
@@ -0,0 +1,298 @@
+//! Ficheros Markdown con cabecera de metadatos (front matter), como los de
+//! Jekyll y Hugo: YAML entre líneas `---` o TOML entre líneas `+++`.
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Formato de la cabecera
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrontMatterFormat {
+    Yaml,
+    Toml,
+}
+
+impl FrontMatterFormat {
+    fn delimiter(self) -> &'static str {
+        match self {
+            FrontMatterFormat::Yaml => "---",
+            FrontMatterFormat::Toml => "+++",
+        }
+    }
+}
+
+/// Metadatos reconocidos de la cabecera; el resto de claves se ignora
+#[derive(Debug, Default)]
+pub struct FrontMatter {
+    pub title: Option<String>,
+    pub date: Option<NaiveDateTime>,
+    pub slug: Option<String>,
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+    pub draft: bool,
+    /// Estado del post (`published`, `draft` o `private`); tiene prioridad sobre `draft`
+    pub status: Option<String>,
+}
+
+/// Cabecera tal como se escribe al exportar
+#[derive(Serialize)]
+pub struct ExportedFrontMatter {
+    pub title: String,
+    /// `AAAA-MM-DDTHH:MM:SS`, hora local
+    pub date: String,
+    pub slug: String,
+    /// Slugs de las categorías
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+    /// Para Jekyll y Hugo, que no conocen `status`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub draft: bool,
+    pub status: String,
+}
+
+/// Separa la cabecera del cuerpo. Un fichero sin cabecera es todo cuerpo.
+pub fn parse(content: &str) -> Result<(FrontMatter, String), String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let format = match content.lines().next().map(str::trim_end) {
+        Some("---") => FrontMatterFormat::Yaml,
+        Some("+++") => FrontMatterFormat::Toml,
+        _ => return Ok((FrontMatter::default(), content.to_string())),
+    };
+    let delimiter = format.delimiter();
+
+    // La cabecera termina en la siguiente línea que solo contiene el delimitador
+    let after_open = &content[content.find('\n').map_or(content.len(), |i| i + 1)..];
+    let mut offset = 0;
+    let mut header_end = None;
+    for line in after_open.split_inclusive('\n') {
+        if line.trim_end() == delimiter {
+            header_end = Some((offset, offset + line.len()));
+            break;
+        }
+        offset += line.len();
+    }
+    let (header_end, body_start) = header_end
+        .ok_or_else(|| format!("La cabecera no se cierra con una línea '{}'", delimiter))?;
+
+    let header = &after_open[..header_end];
+    let body = after_open[body_start..].trim_start_matches(['\n', '\r']).to_string();
+
+    let values = match format {
+        FrontMatterFormat::Yaml if header.trim().is_empty() => Map::new(),
+        FrontMatterFormat::Yaml => match serde_yaml::from_str::<Value>(header) {
+            Ok(Value::Object(map)) => map,
+            Ok(_) => return Err("La cabecera YAML debe ser un mapa de claves".to_string()),
+            Err(e) => return Err(format!("Cabecera YAML no válida: {}", e)),
+        },
+        FrontMatterFormat::Toml => {
+            let table = header
+                .parse::<toml::Table>()
+                .map_err(|e| format!("Cabecera TOML no válida: {}", e))?;
+            table.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect()
+        }
+    };
+
+    Ok((from_values(&values)?, body))
+}
+
+/// Compone un fichero con la cabecera en el formato indicado y el cuerpo
+pub fn render(front_matter: &ExportedFrontMatter, body: &str, format: FrontMatterFormat) -> Result<String, String> {
+    let header = match format {
+        FrontMatterFormat::Yaml => serde_yaml::to_string(front_matter).map_err(|e| e.to_string())?,
+        FrontMatterFormat::Toml => toml::to_string(front_matter).map_err(|e| e.to_string())?,
+    };
+    let delimiter = format.delimiter();
+    Ok(format!("{}\n{}{}\n\n{}\n", delimiter, header, delimiter, body.trim_end()))
+}
+
+/// Fecha de la cabecera: RFC 3339, `AAAA-MM-DD HH:MM:SS [±zona]` o solo `AAAA-MM-DD`.
+/// Las fechas con zona horaria se pasan a hora local.
+pub fn parse_date(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Local).naive_local());
+    }
+    if let Ok(date) = DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S %z") {
+        return Some(date.with_timezone(&Local).naive_local());
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+/// Fecha del prefijo `AAAA-MM-DD-` de los nombres de fichero de Jekyll
+pub fn file_name_date(file_name: &str) -> Option<NaiveDateTime> {
+    NaiveDate::parse_from_str(file_name.get(..10)?, "%Y-%m-%d")
+        .ok()
+        .filter(|_| file_name[10..].starts_with('-'))
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+}
+
+fn from_values(values: &Map<String, Value>) -> Result<FrontMatter, String> {
+    let date = match values.get("date") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(date)) => {
+            Some(parse_date(date).ok_or_else(|| format!("Fecha no válida: '{}'", date))?)
+        }
+        Some(other) => return Err(format!("Fecha no válida: {}", other)),
+    };
+
+    let draft = match values.get("draft") {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(draft)) => *draft,
+        Some(other) => return Err(format!("'draft' debe ser true o false, no {}", other)),
+    };
+
+    Ok(FrontMatter {
+        title: string(values.get("title")),
+        date,
+        slug: string(values.get("slug")),
+        categories: string_list(values.get("categories").or_else(|| values.get("category"))),
+        tags: string_list(values.get("tags")),
+        draft,
+        status: string(values.get("status")),
+    })
+}
+
+fn string(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Una lista, o una cadena con los elementos separados por espacios (estilo Jekyll)
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    match value {
+        Some(Value::Array(items)) => items.iter().filter_map(|item| string(Some(item))).collect(),
+        Some(Value::String(s)) => s.split_whitespace().map(str::to_string).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(table.into_iter().map(|(k, v)| (k, toml_to_json(v))).collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datetime(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn parse_reads_yaml_header() {
+        let content = "---\ntitle: Hola mundo\ndate: 2024-05-01 10:30:00\nslug: hola\ncategories: [rust, web]\ntags: uno dos\ndraft: true\nlayout: post\n---\n\nCuerpo\n";
+        let (front_matter, body) = parse(content).unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("Hola mundo"));
+        assert_eq!(front_matter.date, Some(datetime("2024-05-01 10:30:00")));
+        assert_eq!(front_matter.slug.as_deref(), Some("hola"));
+        assert_eq!(front_matter.categories, vec!["rust", "web"]);
+        // Una cadena se separa por espacios, como en Jekyll
+        assert_eq!(front_matter.tags, vec!["uno", "dos"]);
+        assert!(front_matter.draft);
+        assert_eq!(front_matter.status, None);
+        assert_eq!(body, "Cuerpo\n");
+    }
+
+    #[test]
+    fn parse_reads_toml_header() {
+        let content = "+++\ntitle = \"Hola\"\ndate = 2024-05-01\ncategory = \"rust\"\nstatus = \"private\"\n+++\nCuerpo";
+        let (front_matter, body) = parse(content).unwrap();
+        assert_eq!(front_matter.title.as_deref(), Some("Hola"));
+        assert_eq!(front_matter.date, Some(datetime("2024-05-01 00:00:00")));
+        assert_eq!(front_matter.categories, vec!["rust"]);
+        assert_eq!(front_matter.status.as_deref(), Some("private"));
+        assert!(!front_matter.draft);
+        assert_eq!(body, "Cuerpo");
+    }
+
+    #[test]
+    fn parse_without_header_is_all_body() {
+        let (front_matter, body) = parse("\u{feff}# Título\n\ntexto").unwrap();
+        assert_eq!(front_matter.title, None);
+        assert_eq!(body, "# Título\n\ntexto");
+
+        let (_, body) = parse("---\n---\ntexto").unwrap();
+        assert_eq!(body, "texto");
+    }
+
+    #[test]
+    fn parse_rejects_invalid_headers() {
+        assert!(parse("---\ntitle: x\n").unwrap_err().contains("no se cierra"));
+        assert!(parse("---\n- uno\n- dos\n---\n").unwrap_err().contains("mapa de claves"));
+        assert!(parse("+++\ntitle = \n+++\n").unwrap_err().starts_with("Cabecera TOML no válida"));
+        assert!(parse("---\ndate: ayer\n---\n").unwrap_err().contains("Fecha no válida"));
+        assert!(parse("---\ndraft: si\n---\n").unwrap_err().contains("'draft'"));
+    }
+
+    #[test]
+    fn render_round_trips_through_parse() {
+        let header = ExportedFrontMatter {
+            title: "Hola: mundo".to_string(),
+            date: "2024-05-01T10:30:00".to_string(),
+            slug: "hola-mundo".to_string(),
+            categories: vec!["rust".to_string()],
+            tags: vec!["web".to_string()],
+            draft: true,
+            status: "private".to_string(),
+        };
+        for format in [FrontMatterFormat::Yaml, FrontMatterFormat::Toml] {
+            let content = render(&header, "Cuerpo\n\n", format).unwrap();
+            let (front_matter, body) = parse(&content).unwrap();
+            assert_eq!(front_matter.title.as_deref(), Some("Hola: mundo"));
+            assert_eq!(front_matter.date, Some(datetime("2024-05-01 10:30:00")));
+            assert_eq!(front_matter.slug.as_deref(), Some("hola-mundo"));
+            assert_eq!(front_matter.categories, vec!["rust"]);
+            assert_eq!(front_matter.tags, vec!["web"]);
+            assert!(front_matter.draft);
+            assert_eq!(front_matter.status.as_deref(), Some("private"));
+            assert_eq!(body, "Cuerpo\n");
+        }
+    }
+
+    #[test]
+    fn parse_date_accepts_common_formats() {
+        assert_eq!(parse_date("2024-05-01T10:30:00"), Some(datetime("2024-05-01 10:30:00")));
+        assert_eq!(parse_date(" 2024-05-01 10:30:00 "), Some(datetime("2024-05-01 10:30:00")));
+        assert_eq!(parse_date("2024-05-01 10:30"), Some(datetime("2024-05-01 10:30:00")));
+        assert_eq!(parse_date("2024-05-01"), Some(datetime("2024-05-01 00:00:00")));
+        assert_eq!(parse_date("01/05/2024"), None);
+        assert_eq!(parse_date("2024-13-01"), None);
+    }
+
+    #[test]
+    fn parse_date_converts_zoned_dates_to_local_time() {
+        let expected = DateTime::parse_from_rfc3339("2024-05-01T10:30:00+02:00")
+            .unwrap()
+            .with_timezone(&Local)
+            .naive_local();
+        assert_eq!(parse_date("2024-05-01T10:30:00+02:00"), Some(expected));
+        assert_eq!(parse_date("2024-05-01 10:30:00 +0200"), Some(expected));
+    }
+
+    #[test]
+    fn file_name_date_reads_jekyll_prefix() {
+        assert_eq!(file_name_date("2024-05-01-hola.md"), Some(datetime("2024-05-01 00:00:00")));
+        assert_eq!(file_name_date("2024-05-01.md"), None);
+        assert_eq!(file_name_date("hola.md"), None);
+        assert_eq!(file_name_date("2024-02-30-hola.md"), None);
+        assert_eq!(file_name_date("ñ"), None);
+    }
+}
@@ -0,0 +1,219 @@
+use std::io::Cursor;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageEncoder, ImageReader, Limits};
+use crate::utils::error::{AppError, AppResult};
+
+/// Lado del recorte cuadrado usado como miniatura
+const THUMBNAIL_SIZE: u32 = 300;
+/// Anchos de las variantes para `srcset`; solo se generan los menores que el original
+const RESPONSIVE_WIDTHS: [u32; 3] = [480, 960, 1600];
+/// Dimensión máxima aceptada, para no reservar memoria sin límite al decodificar
+const MAX_DIMENSION: u32 = 12_000;
+const JPEG_QUALITY: u8 = 85;
+const WEBP_QUALITY: f32 = 80.0;
+
+/// Imagen codificada lista para guardar
+pub struct EncodedImage {
+    pub data: Vec<u8>,
+    pub mime_type: &'static str,
+    pub extension: &'static str,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Variante derivada del original: `thumbnail` o `responsive`
+pub struct ImageVariant {
+    pub kind: &'static str,
+    pub image: EncodedImage,
+}
+
+pub struct ProcessedImage {
+    /// Original sin metadatos EXIF y con la orientación ya aplicada
+    pub original: EncodedImage,
+    pub variants: Vec<ImageVariant>,
+}
+
+/// Decodifica la imagen, elimina sus metadatos y genera la miniatura y las
+/// variantes redimensionadas, cada una en el formato original y en WebP.
+///
+/// Los GIF se conservan tal cual para no perder la animación (no llevan EXIF).
+pub fn process(data: &[u8], mime_type: &str) -> AppResult<ProcessedImage> {
+    let image = decode(data)?;
+    let (width, height) = (image.width(), image.height());
+
+    // Formato de las variantes que no son WebP: JPEG para fotos, PNG para lo demás (transparencias)
+    let variant_format = if mime_type == "image/jpeg" { Format::Jpeg } else { Format::Png };
+
+    let original = match mime_type {
+        "image/jpeg" => encode(&image, Format::Jpeg)?,
+        "image/png" => encode(&image, Format::Png)?,
+        "image/webp" => encode(&image, Format::WebP)?,
+        _ => EncodedImage {
+            data: data.to_vec(),
+            mime_type: "image/gif",
+            extension: "gif",
+            width,
+            height,
+        },
+    };
+
+    let mut variants = Vec::new();
+
+    // Cada reducción parte de la anterior, ya más pequeña, en lugar del original completo
+    let mut source = image.clone();
+    for target in RESPONSIVE_WIDTHS.into_iter().rev().filter(|w| *w < width) {
+        let resized = source.resize(target, u32::MAX, FilterType::Lanczos3);
+        variants.push(ImageVariant { kind: "responsive", image: encode(&resized, variant_format)? });
+        variants.push(ImageVariant { kind: "responsive", image: encode(&resized, Format::WebP)? });
+        if resized.width() >= THUMBNAIL_SIZE && resized.height() >= THUMBNAIL_SIZE {
+            source = resized;
+        }
+    }
+
+    let thumbnail = source.resize_to_fill(THUMBNAIL_SIZE.min(width), THUMBNAIL_SIZE.min(height), FilterType::Lanczos3);
+    variants.push(ImageVariant { kind: "thumbnail", image: encode(&thumbnail, variant_format)? });
+    variants.push(ImageVariant { kind: "thumbnail", image: encode(&thumbnail, Format::WebP)? });
+
+    // El original a tamaño completo también se ofrece en WebP, salvo que ya lo sea
+    if original.mime_type != "image/webp" {
+        variants.push(ImageVariant { kind: "responsive", image: encode(&image, Format::WebP)? });
+    }
+
+    Ok(ProcessedImage { original, variants })
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Jpeg,
+    Png,
+    WebP,
+}
+
+fn decode(data: &[u8]) -> AppResult<DynamicImage> {
+    let invalid = |e: image::ImageError| AppError::ValidationError(format!("Imagen no válida: {}", e));
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+
+    let mut reader = ImageReader::new(Cursor::new(data)).with_guessed_format()?;
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder().map_err(invalid)?;
+    let orientation = decoder.orientation().map_err(invalid)?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+    image.apply_orientation(orientation);
+
+    Ok(image)
+}
+
+fn encode(image: &DynamicImage, format: Format) -> AppResult<EncodedImage> {
+    let failed = |e: image::ImageError| AppError::InternalServerError(format!("Error al codificar imagen: {}", e));
+    let (width, height) = (image.width(), image.height());
+    let mut data = Vec::new();
+
+    let (mime_type, extension) = match format {
+        Format::Jpeg => {
+            let rgb = image.to_rgb8();
+            JpegEncoder::new_with_quality(&mut data, JPEG_QUALITY)
+                .write_image(&rgb, width, height, image::ExtendedColorType::Rgb8)
+                .map_err(failed)?;
+            ("image/jpeg", "jpg")
+        }
+        Format::Png => {
+            let rgba = image.to_rgba8();
+            PngEncoder::new(&mut data)
+                .write_image(&rgba, width, height, image::ExtendedColorType::Rgba8)
+                .map_err(failed)?;
+            ("image/png", "png")
+        }
+        Format::WebP => {
+            // libwebp permite compresión con pérdida; el codificador de `image` solo sin pérdida
+            let rgba = image.to_rgba8();
+            data = webp::Encoder::from_rgba(&rgba, width, height).encode(WEBP_QUALITY).to_vec();
+            if data.is_empty() {
+                // Reserva por si libwebp rechaza la imagen
+                WebPEncoder::new_lossless(&mut data)
+                    .write_image(&rgba, width, height, image::ExtendedColorType::Rgba8)
+                    .map_err(failed)?;
+            }
+            ("image/webp", "webp")
+        }
+    };
+
+    Ok(EncodedImage { data, mime_type, extension, width, height })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgba, RgbaImage};
+
+    fn sample(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+        let image = RgbaImage::from_fn(width, height, |x, y| Rgba([(x % 256) as u8, (y % 256) as u8, 128, 255]));
+        let mut data = Vec::new();
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut Cursor::new(&mut data), format)
+            .unwrap();
+        data
+    }
+
+    fn widths(processed: &ProcessedImage, kind: &str, mime_type: &str) -> Vec<u32> {
+        processed
+            .variants
+            .iter()
+            .filter(|v| v.kind == kind && v.image.mime_type == mime_type)
+            .map(|v| v.image.width)
+            .collect()
+    }
+
+    #[test]
+    fn large_jpeg_gets_responsive_variants_and_thumbnail() {
+        let processed = process(&sample(1200, 800, ImageFormat::Png), "image/jpeg").unwrap();
+
+        assert_eq!(processed.original.mime_type, "image/jpeg");
+        assert_eq!((processed.original.width, processed.original.height), (1200, 800));
+        assert_eq!(widths(&processed, "responsive", "image/jpeg"), vec![960, 480]);
+        // Las reducciones en WebP y el original completo en WebP
+        assert_eq!(widths(&processed, "responsive", "image/webp"), vec![960, 480, 1200]);
+
+        let thumbnails: Vec<_> = processed.variants.iter().filter(|v| v.kind == "thumbnail").collect();
+        assert_eq!(thumbnails.len(), 2);
+        assert!(thumbnails.iter().all(|t| t.image.width == THUMBNAIL_SIZE && t.image.height == THUMBNAIL_SIZE));
+    }
+
+    #[test]
+    fn small_image_only_gets_thumbnail_at_its_own_size() {
+        let processed = process(&sample(200, 100, ImageFormat::Png), "image/png").unwrap();
+
+        assert!(widths(&processed, "responsive", "image/png").is_empty());
+        let thumbnail = processed.variants.iter().find(|v| v.kind == "thumbnail").unwrap();
+        assert_eq!((thumbnail.image.width, thumbnail.image.height), (200, 100));
+        assert_eq!(thumbnail.image.mime_type, "image/png");
+    }
+
+    #[test]
+    fn webp_original_is_not_duplicated() {
+        let processed = process(&sample(400, 400, ImageFormat::WebP), "image/webp").unwrap();
+
+        assert_eq!(processed.original.mime_type, "image/webp");
+        assert!(widths(&processed, "responsive", "image/webp").is_empty());
+    }
+
+    #[test]
+    fn gif_original_is_kept_byte_for_byte() {
+        let data = sample(64, 64, ImageFormat::Gif);
+        let processed = process(&data, "image/gif").unwrap();
+
+        assert_eq!(processed.original.data, data);
+        assert_eq!(processed.original.mime_type, "image/gif");
+    }
+
+    #[test]
+    fn invalid_data_is_a_validation_error() {
+        assert!(matches!(process(b"no es una imagen", "image/png"), Err(AppError::ValidationError(_))));
+    }
+}
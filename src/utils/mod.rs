@@ -1,7 +1,10 @@
 pub mod error;
 pub mod validation;
 pub mod slug;
-
-// Exportamos públicamente para que sean accesibles desde otros módulos
-pub use slug::slugify;
-pub use error::{AppError, AppResult}; 
\ No newline at end of file
+pub mod serde_helpers;
+pub mod image_processing;
+pub mod text;
+pub mod wxr;
+pub mod front_matter;
+pub mod backup;
+pub mod client_ip;
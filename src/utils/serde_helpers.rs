@@ -0,0 +1,11 @@
+use serde::{Deserialize, Deserializer};
+
+/// Distingue en un DTO de actualización un campo ausente (`None`) de un campo
+/// enviado como `null` (`Some(None)`). Se usa junto con `#[serde(default)]`.
+pub fn double_option<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
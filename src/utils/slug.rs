@@ -1,34 +1,216 @@
 use deunicode::deunicode;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use diesel::Connection;
+use crate::config;
+use crate::db::DbConnection;
+use crate::repositories::SlugHistoryRepository;
+use crate::utils::error::{AppError, AppResult};
 
-/// Convierte un texto en un slug URL-friendly
+/// Sufijos que se prueban (`-2` ... `-100`) antes de dar un slug por imposible
+const MAX_SUFFIX: u32 = 100;
+
+/// Convierte un texto en un slug URL-friendly, con la longitud máxima y las
+/// palabras vacías configuradas (`SLUG_MAX_LENGTH`, `SLUG_STOP_WORDS`)
 pub fn slugify(text: &str) -> String {
-    // Convertir a ASCII (eliminar acentos y caracteres especiales)
-    let ascii = deunicode(text);
+    slugify_with(text, config::slug_max_length(), &config::slug_stop_words())
+}
+
+/// Como `slugify`, con la longitud máxima (0 sin límite) y las palabras vacías indicadas.
+///
+/// El slug se corta en un límite de palabra; solo si la primera palabra ya excede
+/// la longitud se corta a mitad. Si todas las palabras son vacías, se conservan.
+pub fn slugify_with(text: &str, max_length: usize, stop_words: &[String]) -> String {
+    let words = words(text);
+    let stop_words: Vec<String> = stop_words.iter().flat_map(|w| self::words(w)).collect();
     
-    // Convertir a minúsculas
-    let lowercase = ascii.to_lowercase();
+    // Eliminar las palabras vacías, salvo que no quede ninguna
+    let mut kept: Vec<&String> = words.iter().filter(|w| !stop_words.contains(w)).collect();
+    if kept.is_empty() {
+        kept = words.iter().collect();
+    }
     
-    // Reemplazar espacios y caracteres no alfanuméricos con guiones
     let mut slug = String::new();
-    let mut prev_dash = true; // Para evitar guiones consecutivos
-    
-    for c in lowercase.chars() {
-        if c.is_alphanumeric() {
-            slug.push(c);
-            prev_dash = false;
-        } else if !prev_dash {
+    for word in kept {
+        let needed = if slug.is_empty() { word.len() } else { slug.len() + 1 + word.len() };
+        if max_length > 0 && needed > max_length {
+            if slug.is_empty() {
+                // Tras `deunicode` el texto es ASCII, así que cortar por bytes es seguro
+                slug.push_str(&word[..max_length]);
+            }
+            break;
+        }
+        if !slug.is_empty() {
             slug.push('-');
-            prev_dash = true;
         }
+        slug.push_str(word);
     }
     
-    // Eliminar guiones al inicio y final
-    slug = slug.trim_matches('-').to_string();
-    
     // Si el slug está vacío, devolver un valor por defecto
     if slug.is_empty() {
         return "post".to_string();
     }
     
     slug
-} 
\ No newline at end of file
+}
+
+/// Indica si el texto ya es un slug: palabras en `[a-z0-9]` unidas por guiones
+pub fn is_valid_slug(text: &str) -> bool {
+    !text.is_empty()
+        && text.split('-').all(|w| !w.is_empty() && w.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()))
+}
+
+/// Palabras del texto en ASCII y minúsculas; el resto de caracteres las separan
+fn words(text: &str) -> Vec<String> {
+    deunicode(text)
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Añade el sufijo `-n` a un slug, recortándolo para no superar la longitud máxima
+fn with_suffix(base: &str, n: u32, max_length: usize) -> String {
+    let suffix = format!("-{}", n);
+    let mut base = base;
+    if max_length > suffix.len() && base.len() + suffix.len() > max_length {
+        base = base[..max_length - suffix.len()].trim_end_matches('-');
+    }
+    format!("{}{}", base, suffix)
+}
+
+/// Ejecuta `op` con `base` y, si choca con la restricción `UNIQUE` del slug,
+/// vuelve a intentarlo con `base-2`, `base-3`... Cada intento va en su propio
+/// savepoint, así que una colisión (también con una petición concurrente que
+/// acaba de usar el mismo slug) no aborta la transacción en curso.
+pub fn with_unique_slug<T, F>(base: &str, conn: &mut DbConnection, op: F) -> AppResult<T>
+where
+    F: FnMut(&str, &mut DbConnection) -> AppResult<T>,
+{
+    try_candidates(base, conn, |_, _| Ok(false), op)
+}
+
+/// Como `with_unique_slug` para entidades con historial de slugs (`post`,
+/// `category`): un slug antiguo de otra entidad también cuenta como colisión,
+/// para que un slug generado no se quede con su redirección. Solo un slug
+/// indicado por el usuario puede reclamarlo. `entity_id` es la entidad que se
+/// renombra, que sí puede recuperar sus propios slugs antiguos.
+pub fn with_unique_slug_in_history<T, F>(
+    entity_type: &str,
+    entity_id: Option<i32>,
+    base: &str,
+    conn: &mut DbConnection,
+    op: F,
+) -> AppResult<T>
+where
+    F: FnMut(&str, &mut DbConnection) -> AppResult<T>,
+{
+    try_candidates(
+        base,
+        conn,
+        |candidate, conn| {
+            Ok(SlugHistoryRepository::find(entity_type, candidate, conn)?
+                .is_some_and(|entry| Some(entry.entity_id) != entity_id))
+        },
+        op,
+    )
+}
+
+/// Prueba `base`, `base-2`, `base-3`... saltando los que `taken` da por
+/// ocupados, hasta que `op` no choca con la restricción `UNIQUE` del slug
+fn try_candidates<T, S, F>(base: &str, conn: &mut DbConnection, mut taken: S, mut op: F) -> AppResult<T>
+where
+    S: FnMut(&str, &mut DbConnection) -> AppResult<bool>,
+    F: FnMut(&str, &mut DbConnection) -> AppResult<T>,
+{
+    let max_length = config::slug_max_length();
+    
+    for n in 1..=MAX_SUFFIX {
+        let candidate = if n == 1 { base.to_string() } else { with_suffix(base, n, max_length) };
+        if taken(&candidate, conn)? {
+            continue;
+        }
+        match conn.transaction(|conn| op(&candidate, conn)) {
+            Err(ref e) if is_slug_conflict(e) => continue,
+            result => return result,
+        }
+    }
+    
+    Err(AppError::ValidationError(format!("No se encontró un slug libre a partir de '{}'", base)))
+}
+
+/// Traduce el choque con la restricción `UNIQUE` de un slug indicado por el
+/// usuario (p. ej. otra petición acaba de usarlo) a un error de validación; el
+/// resto de errores se devuelven tal cual
+pub fn explicit_slug_error(error: AppError, slug: &str) -> AppError {
+    if is_slug_conflict(&error) {
+        AppError::ValidationError(format!("El slug '{}' ya está en uso", slug))
+    } else {
+        error
+    }
+}
+
+/// Indica si el error es una violación de una restricción `<tabla>_slug_key`
+fn is_slug_conflict(error: &AppError) -> bool {
+    match error {
+        AppError::DatabaseError(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, info)) => {
+            info.constraint_name().is_some_and(|name| name.ends_with("_slug_key"))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stop_words(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn slugify_with_transliterates_unicode() {
+        assert_eq!(slugify_with("¡Hola, Mundo! Año 2026", 0, &[]), "hola-mundo-ano-2026");
+        assert_eq!(slugify_with("Crème brûlée  &  café", 0, &[]), "creme-brulee-cafe");
+        assert_eq!(slugify_with("Привет мир", 0, &[]), "privet-mir");
+    }
+
+    #[test]
+    fn slugify_with_removes_stop_words_unless_nothing_is_left() {
+        let stop = stop_words(&["el", "de", "La"]);
+        assert_eq!(slugify_with("El libro de la selva", 0, &stop), "libro-selva");
+        assert_eq!(slugify_with("De la", 0, &stop), "de-la");
+    }
+
+    #[test]
+    fn slugify_with_cuts_on_a_word_boundary() {
+        assert_eq!(slugify_with("uno dos tres cuatro", 12, &[]), "uno-dos-tres");
+        assert_eq!(slugify_with("uno dos tres cuatro", 11, &[]), "uno-dos");
+        // Solo la primera palabra se corta a mitad
+        assert_eq!(slugify_with("supercalifragilistico", 5, &[]), "super");
+    }
+
+    #[test]
+    fn slugify_with_falls_back_when_empty() {
+        assert_eq!(slugify_with("", 0, &[]), "post");
+        assert_eq!(slugify_with("¿¡!?", 0, &[]), "post");
+    }
+
+    #[test]
+    fn with_suffix_trims_the_base_to_fit() {
+        assert_eq!(with_suffix("mi-post", 2, 0), "mi-post-2");
+        assert_eq!(with_suffix("mi-post", 2, 80), "mi-post-2");
+        assert_eq!(with_suffix("abcdefghij", 12, 10), "abcdefg-12");
+        // No deja un guion colgando antes del sufijo
+        assert_eq!(with_suffix("abcd-fghij", 2, 7), "abcd-2");
+    }
+
+    #[test]
+    fn is_valid_slug_accepts_only_lowercase_words_and_dashes() {
+        assert!(is_valid_slug("hola-mundo-2026"));
+        assert!(is_valid_slug("a"));
+        for slug in ["", "-hola", "hola-", "hola--mundo", "Hola", "hola_mundo", "año", "hola mundo", "../x"] {
+            assert!(!is_valid_slug(slug), "{:?}", slug);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+/// Palabras por minuto usadas para estimar el tiempo de lectura
+const WORDS_PER_MINUTE: usize = 200;
+/// Longitud máxima, en caracteres, del extracto generado automáticamente
+const EXCERPT_MAX_CHARS: usize = 200;
+
+/// Reduce un cuerpo en Markdown o HTML a texto plano: elimina las etiquetas,
+/// conserva el texto de enlaces e imágenes sin su destino y descarta los
+/// símbolos de formato. Los espacios consecutivos se colapsan en uno.
+pub fn plain_text(body: &str) -> String {
+    let mut text = String::with_capacity(body.len());
+    let mut chars = body.chars().peekable();
+    let mut prev = ' ';
+
+    while let Some(c) = chars.next() {
+        match c {
+            // Etiqueta HTML: se sustituye por un espacio para no pegar palabras. Un `<`
+            // que no abre una etiqueta (`a < b`) se conserva como texto.
+            '<' if is_tag_start(chars.clone()) => {
+                for c in chars.by_ref() {
+                    if c == '>' {
+                        break;
+                    }
+                }
+                text.push(' ');
+            }
+            // Destino de un enlace o imagen Markdown: `[texto](url)`
+            ']' if chars.peek() == Some(&'(') => {
+                for c in chars.by_ref() {
+                    if c == ')' {
+                        break;
+                    }
+                }
+            }
+            // `!` solo es marcado cuando abre una imagen: `![alt](url)`
+            '!' if chars.peek() == Some(&'[') => {}
+            // `_` dentro de una palabra (`snake_case`) no es énfasis
+            '_' if prev.is_alphanumeric() && chars.peek().is_some_and(|next| next.is_alphanumeric()) => text.push(c),
+            '#' | '*' | '_' | '`' | '>' | '~' | '[' | ']' | '|' => text.push(' '),
+            _ => text.push(c),
+        }
+        prev = c;
+    }
+
+    // Colapsa los espacios y pega a la palabra anterior la puntuación que
+    // quedó separada al quitar una etiqueta (`<b>hola</b>.`)
+    let mut plain = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        let attaches = word.starts_with(['.', ',', ';', ':', '?', '!', ')']);
+        if !plain.is_empty() && !attaches {
+            plain.push(' ');
+        }
+        plain.push_str(word);
+    }
+
+    plain
+}
+
+/// Indica si lo que sigue a un `<` es una etiqueta: empieza por una letra, `/` o
+/// `!` (comentarios y `<!DOCTYPE>`) y se cierra con `>`
+fn is_tag_start(mut rest: impl Iterator<Item = char>) -> bool {
+    match rest.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '/' || c == '!' => rest.any(|c| c == '>'),
+        _ => false,
+    }
+}
+
+/// Número de palabras del cuerpo, sin contar marcado ni signos sueltos
+pub fn word_count(body: &str) -> usize {
+    plain_text(body)
+        .split_whitespace()
+        .filter(|w| w.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+/// Minutos estimados de lectura, redondeando hacia arriba; al menos 1 si hay texto
+pub fn reading_time_minutes(words: usize) -> usize {
+    words.div_ceil(WORDS_PER_MINUTE)
+}
+
+/// Extracto generado a partir del cuerpo: el comienzo del texto plano,
+/// cortado en un límite de palabra y terminado en `…` si se ha recortado
+pub fn excerpt(body: &str) -> String {
+    let text = plain_text(body);
+    if text.chars().count() <= EXCERPT_MAX_CHARS {
+        return text;
+    }
+
+    let cut = text
+        .char_indices()
+        .nth(EXCERPT_MAX_CHARS)
+        .map_or(text.len(), |(i, _)| i);
+    let truncated = &text[..cut];
+    let truncated = match truncated.rfind(' ') {
+        Some(space) if space > 0 => &truncated[..space],
+        _ => truncated,
+    };
+
+    format!("{}…", truncated.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_html_tags_and_markdown_markup() {
+        assert_eq!(plain_text("<p>Hola <b>mundo</b>.</p>"), "Hola mundo.");
+        assert_eq!(plain_text("# Título\n\n**Negrita** y *cursiva* con `código`"), "Título Negrita y cursiva con código");
+        assert_eq!(plain_text("<!-- comentario --><br/>texto"), "texto");
+    }
+
+    #[test]
+    fn keeps_link_and_image_text_without_destination() {
+        assert_eq!(plain_text("Ver [la guía](https://example.com/guia) y ![un gráfico](g.png)"), "Ver la guía y un gráfico");
+    }
+
+    #[test]
+    fn literal_less_than_is_not_a_tag() {
+        assert_eq!(plain_text("Si a < b entonces b > a"), "Si a < b entonces b a");
+        assert_eq!(plain_text("x <3 y"), "x <3 y");
+        assert_eq!(plain_text("sin cierre <b y el resto del texto"), "sin cierre <b y el resto del texto");
+    }
+
+    #[test]
+    fn underscore_inside_words_is_kept() {
+        assert_eq!(plain_text("usa snake_case y MAX_VALUE"), "usa snake_case y MAX_VALUE");
+        assert_eq!(plain_text("texto _con énfasis_ y __init__"), "texto con énfasis y init");
+    }
+
+    #[test]
+    fn counts_words_and_reading_time() {
+        assert_eq!(word_count("<p>uno, dos</p> - tres **cuatro**"), 4);
+        assert_eq!(reading_time_minutes(0), 0);
+        assert_eq!(reading_time_minutes(1), 1);
+        assert_eq!(reading_time_minutes(WORDS_PER_MINUTE + 1), 2);
+    }
+
+    #[test]
+    fn excerpt_cuts_on_a_word_boundary() {
+        assert_eq!(excerpt("Texto corto."), "Texto corto.");
+
+        let body = "palabra, ".repeat(50);
+        let excerpt = excerpt(&body);
+        assert!(excerpt.ends_with("palabra…"));
+        assert!(excerpt.chars().count() <= EXCERPT_MAX_CHARS + 1);
+    }
+}
@@ -13,5 +13,5 @@ pub fn has_min_length(value: &str, min_length: usize) -> bool {
 /// Valida que un email tenga un formato básico válido
 pub fn is_valid_email(email: &str) -> bool {
     // Validación básica: contiene @ y al menos un punto después
-    email.contains('@') && email.split('@').nth(1).map_or(false, |domain| domain.contains('.'))
+    email.contains('@') && email.split('@').nth(1).is_some_and(|domain| domain.contains('.'))
 } 
\ No newline at end of file
@@ -0,0 +1,385 @@
+//! Lectura de ficheros WXR (WordPress eXtended RSS), el formato de
+//! «Herramientas → Exportar» de WordPress.
+//!
+//! Solo se extrae lo que la importación necesita; el resto del fichero se ignora.
+
+use roxmltree::{Document, Node};
+
+const NS_CONTENT: &str = "http://purl.org/rss/1.0/modules/content/";
+const NS_DC: &str = "http://purl.org/dc/elements/1.1/";
+/// Prefijo común de los espacios de nombres `wp` (1.0, 1.1, 1.2) y `excerpt`
+const NS_WP_PREFIX: &str = "http://wordpress.org/export/";
+
+#[derive(Debug)]
+pub struct WxrDocument {
+    /// URL base del sitio exportado; identifica el origen de la importación
+    pub site_url: String,
+    pub authors: Vec<WxrAuthor>,
+    pub categories: Vec<WxrCategory>,
+    pub items: Vec<WxrItem>,
+}
+
+#[derive(Debug)]
+pub struct WxrAuthor {
+    pub id: Option<i64>,
+    pub login: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct WxrCategory {
+    pub nicename: String,
+    pub name: String,
+    pub parent: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Entrada del canal: un post, una página, un adjunto u otro tipo de contenido
+#[derive(Debug)]
+pub struct WxrItem {
+    pub id: i64,
+    pub guid: String,
+    pub title: String,
+    pub post_type: String,
+    pub status: String,
+    /// Slug tal como lo guarda WordPress (puede venir codificado con `%xx`)
+    pub slug: String,
+    pub content: String,
+    pub excerpt: Option<String>,
+    /// Fecha local de publicación, `YYYY-MM-DD HH:MM:SS`
+    pub date: Option<String>,
+    pub modified: Option<String>,
+    pub author_login: Option<String>,
+    pub parent_id: Option<i64>,
+    /// Nicenames de las categorías
+    pub categories: Vec<String>,
+    /// Nombres de las etiquetas
+    pub tags: Vec<String>,
+    pub attachment_url: Option<String>,
+    /// ID del adjunto usado como imagen destacada (`_thumbnail_id`)
+    pub thumbnail_id: Option<i64>,
+    pub comments: Vec<WxrComment>,
+}
+
+#[derive(Debug)]
+pub struct WxrComment {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub author: Option<String>,
+    pub author_email: Option<String>,
+    pub date: Option<String>,
+    pub content: String,
+    /// `1` aprobado, `0` pendiente, `spam` o `trash`
+    pub approved: String,
+    /// ID del usuario de WordPress que lo escribió, si tenía sesión
+    pub user_id: Option<i64>,
+}
+
+/// Analiza el contenido de un fichero WXR
+pub fn parse(xml: &str) -> Result<WxrDocument, String> {
+    let document = Document::parse(xml).map_err(|e| format!("XML no válido: {}", e))?;
+    let channel = document
+        .root_element()
+        .children()
+        .find(|n| n.has_tag_name("channel"))
+        .ok_or_else(|| "El fichero no es una exportación de WordPress: falta <channel>".to_string())?;
+
+    let site_url = wp_text(channel, "base_site_url")
+        .or_else(|| child_text(channel, "", "link"))
+        .ok_or_else(|| "El fichero no indica la URL del sitio (wp:base_site_url)".to_string())?;
+
+    let mut authors = Vec::new();
+    let mut categories = Vec::new();
+    let mut items = Vec::new();
+
+    for node in channel.children().filter(Node::is_element) {
+        match (node.tag_name().namespace(), node.tag_name().name()) {
+            (Some(ns), "author") if is_wp(ns) => {
+                if let Some(login) = wp_text(node, "author_login") {
+                    authors.push(WxrAuthor {
+                        id: wp_text(node, "author_id").and_then(|id| id.parse().ok()),
+                        login,
+                        email: wp_text(node, "author_email"),
+                    });
+                }
+            }
+            (Some(ns), "category") if is_wp(ns) => {
+                if let Some(nicename) = wp_text(node, "category_nicename") {
+                    categories.push(WxrCategory {
+                        name: wp_text(node, "cat_name").unwrap_or_else(|| nicename.clone()),
+                        nicename,
+                        parent: wp_text(node, "category_parent"),
+                        description: wp_text(node, "category_description"),
+                    });
+                }
+            }
+            (None, "item") => items.push(parse_item(node)?),
+            _ => {}
+        }
+    }
+
+    Ok(WxrDocument { site_url, authors, categories, items })
+}
+
+fn parse_item(node: Node) -> Result<WxrItem, String> {
+    let id = wp_text(node, "post_id")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| "Hay un <item> sin wp:post_id".to_string())?;
+    let guid = child_text(node, "", "guid").unwrap_or_else(|| format!("wp-post-{}", id));
+
+    let mut categories = Vec::new();
+    let mut tags = Vec::new();
+    for category in node.children().filter(|n| n.tag_name().namespace().is_none() && n.has_tag_name("category")) {
+        match (category.attribute("domain"), category.attribute("nicename")) {
+            (Some("category"), Some(nicename)) => categories.push(nicename.to_string()),
+            (Some("post_tag"), _) => {
+                if let Some(name) = text(category) {
+                    tags.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let thumbnail_id = wp_children(node, "postmeta")
+        .find(|meta| wp_text(*meta, "meta_key").as_deref() == Some("_thumbnail_id"))
+        .and_then(|meta| wp_text(meta, "meta_value"))
+        .and_then(|value| value.parse().ok());
+
+    let comments = wp_children(node, "comment").filter_map(parse_comment).collect();
+
+    Ok(WxrItem {
+        id,
+        guid,
+        title: child_text(node, "", "title").unwrap_or_default(),
+        post_type: wp_text(node, "post_type").unwrap_or_else(|| "post".to_string()),
+        status: wp_text(node, "status").unwrap_or_else(|| "publish".to_string()),
+        slug: wp_text(node, "post_name").unwrap_or_default(),
+        content: child_text(node, NS_CONTENT, "encoded").unwrap_or_default(),
+        excerpt: node
+            .children()
+            .find(|n| n.tag_name().name() == "encoded" && n.tag_name().namespace().is_some_and(is_excerpt))
+            .and_then(text),
+        date: wp_text(node, "post_date").filter(|d| !d.starts_with("0000")),
+        modified: wp_text(node, "post_modified").filter(|d| !d.starts_with("0000")),
+        author_login: child_text(node, NS_DC, "creator"),
+        parent_id: wp_text(node, "post_parent").and_then(|id| id.parse().ok()).filter(|&id| id != 0),
+        categories,
+        tags,
+        attachment_url: wp_text(node, "attachment_url"),
+        thumbnail_id,
+        comments,
+    })
+}
+
+fn parse_comment(node: Node) -> Option<WxrComment> {
+    Some(WxrComment {
+        id: wp_text(node, "comment_id")?.parse().ok()?,
+        parent_id: wp_text(node, "comment_parent").and_then(|id| id.parse().ok()).filter(|&id| id != 0),
+        author: wp_text(node, "comment_author"),
+        author_email: wp_text(node, "comment_author_email"),
+        date: wp_text(node, "comment_date").filter(|d| !d.starts_with("0000")),
+        content: wp_text(node, "comment_content").unwrap_or_default(),
+        approved: wp_text(node, "comment_approved").unwrap_or_else(|| "1".to_string()),
+        user_id: wp_text(node, "comment_user_id").and_then(|id| id.parse().ok()).filter(|&id| id != 0),
+    })
+}
+
+/// Decodifica las secuencias `%xx` de un slug de WordPress (`caf%c3%a9` → `café`)
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(byte) = value.get(i + 1..i + 3).and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn is_wp(namespace: &str) -> bool {
+    namespace.starts_with(NS_WP_PREFIX) && !is_excerpt(namespace)
+}
+
+fn is_excerpt(namespace: &str) -> bool {
+    namespace.starts_with(NS_WP_PREFIX) && namespace.trim_end_matches('/').ends_with("excerpt")
+}
+
+fn wp_children<'a, 'input>(node: Node<'a, 'input>, name: &'a str) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children()
+        .filter(move |n| n.tag_name().name() == name && n.tag_name().namespace().is_some_and(is_wp))
+}
+
+fn wp_text(node: Node, name: &str) -> Option<String> {
+    wp_children(node, name).next().and_then(text)
+}
+
+/// Texto de un hijo; `namespace` vacío indica un elemento sin espacio de nombres
+fn child_text(node: Node, namespace: &str, name: &str) -> Option<String> {
+    node.children()
+        .find(|n| n.tag_name().name() == name && n.tag_name().namespace().unwrap_or("") == namespace)
+        .and_then(text)
+}
+
+/// Texto de un elemento (incluidas las secciones CDATA), sin espacios en los extremos
+fn text(node: Node) -> Option<String> {
+    let text: String = node.children().filter_map(|n| n.text()).collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"
+    xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/"
+    xmlns:content="http://purl.org/rss/1.0/modules/content/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+    <title>Mi blog</title>
+    <link>https://ejemplo.com</link>
+    <wp:base_site_url>https://ejemplo.com/wp</wp:base_site_url>
+    <wp:author>
+        <wp:author_id>3</wp:author_id>
+        <wp:author_login><![CDATA[ana]]></wp:author_login>
+        <wp:author_email><![CDATA[ana@ejemplo.com]]></wp:author_email>
+    </wp:author>
+    <wp:author><wp:author_email>sin-login@ejemplo.com</wp:author_email></wp:author>
+    <wp:category>
+        <wp:category_nicename>rust</wp:category_nicename>
+        <wp:category_parent>programacion</wp:category_parent>
+    </wp:category>
+    <item>
+        <title>Hola mundo</title>
+        <dc:creator><![CDATA[ana]]></dc:creator>
+        <content:encoded><![CDATA[<p>Contenido</p>]]></content:encoded>
+        <excerpt:encoded><![CDATA[  Resumen  ]]></excerpt:encoded>
+        <wp:post_id>10</wp:post_id>
+        <wp:post_date>2024-05-01 10:00:00</wp:post_date>
+        <wp:post_modified>0000-00-00 00:00:00</wp:post_modified>
+        <wp:post_name>hola-mundo</wp:post_name>
+        <wp:status>draft</wp:status>
+        <wp:post_parent>0</wp:post_parent>
+        <category domain="category" nicename="rust"><![CDATA[Rust]]></category>
+        <category domain="post_tag" nicename="web"><![CDATA[Web]]></category>
+        <wp:postmeta>
+            <wp:meta_key>_thumbnail_id</wp:meta_key>
+            <wp:meta_value>11</wp:meta_value>
+        </wp:postmeta>
+        <wp:comment>
+            <wp:comment_id>5</wp:comment_id>
+            <wp:comment_author>Luis</wp:comment_author>
+            <wp:comment_content>Gracias</wp:comment_content>
+            <wp:comment_approved>0</wp:comment_approved>
+            <wp:comment_parent>0</wp:comment_parent>
+            <wp:comment_user_id>0</wp:comment_user_id>
+        </wp:comment>
+        <wp:comment><wp:comment_content>Sin ID</wp:comment_content></wp:comment>
+    </item>
+    <item>
+        <wp:post_id>11</wp:post_id>
+        <wp:post_type>attachment</wp:post_type>
+        <wp:post_parent>10</wp:post_parent>
+        <wp:attachment_url>https://ejemplo.com/foto.png</wp:attachment_url>
+    </item>
+</channel>
+</rss>"#;
+
+    #[test]
+    fn parse_reads_channel_authors_and_categories() {
+        let document = parse(SAMPLE).unwrap();
+        assert_eq!(document.site_url, "https://ejemplo.com/wp");
+
+        // Los autores sin login se ignoran
+        assert_eq!(document.authors.len(), 1);
+        assert_eq!(document.authors[0].id, Some(3));
+        assert_eq!(document.authors[0].login, "ana");
+        assert_eq!(document.authors[0].email.as_deref(), Some("ana@ejemplo.com"));
+
+        assert_eq!(document.categories.len(), 1);
+        assert_eq!(document.categories[0].nicename, "rust");
+        // Sin cat_name, el nombre es el nicename
+        assert_eq!(document.categories[0].name, "rust");
+        assert_eq!(document.categories[0].parent.as_deref(), Some("programacion"));
+    }
+
+    #[test]
+    fn parse_reads_items_with_defaults() {
+        let document = parse(SAMPLE).unwrap();
+        assert_eq!(document.items.len(), 2);
+
+        let post = &document.items[0];
+        assert_eq!(post.id, 10);
+        assert_eq!(post.guid, "wp-post-10");
+        assert_eq!(post.title, "Hola mundo");
+        assert_eq!(post.post_type, "post");
+        assert_eq!(post.status, "draft");
+        assert_eq!(post.slug, "hola-mundo");
+        assert_eq!(post.content, "<p>Contenido</p>");
+        assert_eq!(post.excerpt.as_deref(), Some("Resumen"));
+        assert_eq!(post.date.as_deref(), Some("2024-05-01 10:00:00"));
+        assert_eq!(post.modified, None);
+        assert_eq!(post.author_login.as_deref(), Some("ana"));
+        assert_eq!(post.parent_id, None);
+        assert_eq!(post.categories, vec!["rust"]);
+        assert_eq!(post.tags, vec!["Web"]);
+        assert_eq!(post.thumbnail_id, Some(11));
+
+        // Los comentarios sin ID se descartan
+        assert_eq!(post.comments.len(), 1);
+        assert_eq!(post.comments[0].id, 5);
+        assert_eq!(post.comments[0].author.as_deref(), Some("Luis"));
+        assert_eq!(post.comments[0].approved, "0");
+        assert_eq!(post.comments[0].parent_id, None);
+        assert_eq!(post.comments[0].user_id, None);
+
+        let attachment = &document.items[1];
+        assert_eq!(attachment.post_type, "attachment");
+        assert_eq!(attachment.status, "publish");
+        assert_eq!(attachment.parent_id, Some(10));
+        assert_eq!(attachment.attachment_url.as_deref(), Some("https://ejemplo.com/foto.png"));
+    }
+
+    #[test]
+    fn parse_falls_back_to_the_channel_link() {
+        let xml = r#"<rss xmlns:wp="http://wordpress.org/export/1.1/"><channel><link>https://ejemplo.com</link></channel></rss>"#;
+        let document = parse(xml).unwrap();
+        assert_eq!(document.site_url, "https://ejemplo.com");
+        assert!(document.items.is_empty());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_documents() {
+        assert!(parse("<rss><channel>").unwrap_err().starts_with("XML no válido"));
+        assert!(parse("<rss></rss>").unwrap_err().contains("falta <channel>"));
+        assert!(parse("<rss><channel></channel></rss>").unwrap_err().contains("wp:base_site_url"));
+
+        let without_id = r#"<rss><channel><link>https://ejemplo.com</link><item><title>X</title></item></channel></rss>"#;
+        assert_eq!(parse(without_id).unwrap_err(), "Hay un <item> sin wp:post_id");
+    }
+
+    #[test]
+    fn percent_decode_decodes_utf8_sequences() {
+        assert_eq!(percent_decode("caf%c3%a9"), "café");
+        assert_eq!(percent_decode("caf%C3%A9-con-leche"), "café-con-leche");
+        assert_eq!(percent_decode("sin-escapes"), "sin-escapes");
+    }
+
+    #[test]
+    fn percent_decode_keeps_invalid_sequences() {
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+        // Un byte suelto que no es UTF-8 válido se sustituye
+        assert_eq!(percent_decode("a%ffb"), "a\u{fffd}b");
+    }
+}